@@ -23,7 +23,7 @@ for bin in databend-query databend-meta; do
     fi
 done
 echo 'Start databend-meta...'
-nohup databend-meta --single &
+nohup databend-meta --single --grpc-disable-password-auth &
 echo "Waiting on databend-meta 10 seconds..."
 ./wait_tcp.py --port 9191 --timeout 10
 echo 'Start databend-query...'
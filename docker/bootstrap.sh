@@ -10,6 +10,7 @@ databend-meta \
     --log-file-dir /var/log/databend \
     --log-stderr-level WARN \
     --raft-dir /var/lib/databend/meta \
+    --grpc-disable-password-auth \
     --single &>/tmp/std-meta.log &
 PROCESSES+=($!)
 # wait for meta to be ready
@@ -63,8 +63,8 @@ where
         self.map.remove(&RangeMapKey::new(range, id));
     }
 
-    pub fn remove_by_key(&mut self, key: &RangeMapKey<RV, ID>) {
-        self.map.remove(key);
+    pub fn remove_by_key(&mut self, key: &RangeMapKey<RV, ID>) -> Option<V> {
+        self.map.remove(key)
     }
 
     /// Returns an iterator of all keys.
@@ -12,37 +12,134 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
 use jwt_simple::prelude::*;
 
-#[derive(Serialize, Deserialize, Debug)]
+/// How long a verified claim is reused without verifying the token again.
+const CLAIM_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// The max number of verified claims to cache, expired ones are evicted when it is reached.
+const CLAIM_CACHE_CAPACITY: usize = 4096;
+
+/// How long an issued token is valid by default. A client handshakes again when its token expires.
+pub const DEFAULT_TOKEN_TTL: std::time::Duration = std::time::Duration::from_secs(24 * 3600);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GrpcClaim {
     pub username: String,
+
+    /// Bitmap of optional features negotiated in handshake.
+    ///
+    /// It is absent in a token issued by an older server.
+    #[serde(default)]
+    pub features: u64,
+}
+
+struct CachedClaim {
+    claim: GrpcClaim,
+    valid_until: Instant,
 }
 
 #[derive(Clone)]
 pub struct GrpcToken {
     key: HS256Key,
+    /// How long an issued token is valid.
+    ttl: std::time::Duration,
+    /// Verified claims by token, so that the RPCs of a connection do not verify the same token every time.
+    cache: Arc<Mutex<HashMap<String, CachedClaim>>>,
+    /// The number of tokens actually verified, i.e., not served by the cache.
+    verified: Arc<AtomicU64>,
 }
 
 impl GrpcToken {
     pub fn create() -> Self {
         let key = HS256Key::generate();
-        Self { key }
+        Self {
+            key,
+            ttl: DEFAULT_TOKEN_TTL,
+            cache: Default::default(),
+            verified: Default::default(),
+        }
+    }
+
+    /// Create with a key derived from a secret, so that the tokens issued by any process
+    /// sharing the secret are accepted by the others.
+    pub fn create_with_secret(secret: &[u8]) -> Self {
+        Self {
+            key: HS256Key::from_bytes(secret),
+            ttl: DEFAULT_TOKEN_TTL,
+            cache: Default::default(),
+            verified: Default::default(),
+        }
+    }
+
+    /// Set how long an issued token is valid.
+    pub fn with_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.ttl = ttl;
+        self
     }
 
     pub fn try_create_token(&self, claim: GrpcClaim) -> Result<String> {
-        let claims = Claims::with_custom_claims(claim, Duration::from_days(3650));
+        let valid_for = Duration::from_millis(self.ttl.as_millis() as u64);
+        let claims = Claims::with_custom_claims(claim, valid_for);
         self.key.authenticate(claims).map_err_to_code(
             ErrorCode::AuthenticateFailure,
             || "Cannot create flight token, because authenticate failure",
         )
     }
 
+    /// Verify a token and return the claim in it.
+    ///
+    /// A verified claim is cached for a short while, but never past the expiration of the token.
     pub fn try_verify_token(&self, token: String) -> Result<GrpcClaim> {
+        let now = Instant::now();
+
+        {
+            let cache = self.cache.lock().unwrap();
+            if let Some(cached) = cache.get(&token) {
+                if now < cached.valid_until {
+                    return Ok(cached.claim.clone());
+                }
+            }
+        }
+
+        self.verified.fetch_add(1, Ordering::Relaxed);
         let claims = self.key.verify_token::<GrpcClaim>(&token, None)?;
+
+        let mut ttl = CLAIM_CACHE_TTL;
+        if let Some(expires_at) = claims.expires_at {
+            let remaining = expires_at
+                .as_millis()
+                .saturating_sub(Clock::now_since_epoch().as_millis());
+            ttl = ttl.min(std::time::Duration::from_millis(remaining));
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= CLAIM_CACHE_CAPACITY {
+            cache.retain(|_, c| now < c.valid_until);
+            if cache.len() >= CLAIM_CACHE_CAPACITY {
+                cache.clear();
+            }
+        }
+        cache.insert(token, CachedClaim {
+            claim: claims.custom.clone(),
+            valid_until: now + ttl,
+        });
+
         Ok(claims.custom)
     }
+
+    /// The number of tokens verified with the key, excluding those served by the cache.
+    pub fn verified_count(&self) -> u64 {
+        self.verified.load(Ordering::Relaxed)
+    }
 }
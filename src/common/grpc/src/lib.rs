@@ -21,6 +21,7 @@ pub use dns_resolver::DNSResolver;
 pub use dns_resolver::GrpcConnectionError;
 pub use grpc_token::GrpcClaim;
 pub use grpc_token::GrpcToken;
+pub use grpc_token::DEFAULT_TOKEN_TTL;
 
 mod client_conf;
 mod dns_resolver;
@@ -23,11 +23,72 @@ fn test_flight_token() -> Result<()> {
 
     let claim = GrpcClaim {
         username: String::from(user),
+        features: 1,
     };
 
     let jwt = token.try_create_token(claim)?;
     let claim = token.try_verify_token(jwt)?;
 
     assert_eq!(claim.username, user);
+    assert_eq!(claim.features, 1);
+    Ok(())
+}
+
+#[test]
+fn test_flight_token_verify_cache() -> Result<()> {
+    let token = GrpcToken::create();
+
+    let jwt = token.try_create_token(GrpcClaim {
+        username: String::from("batman"),
+        features: 0,
+    })?;
+
+    // Two back-to-back RPCs with the same token verify it only once.
+    let claim = token.try_verify_token(jwt.clone())?;
+    assert_eq!(claim.username, "batman");
+    let claim = token.try_verify_token(jwt.clone())?;
+    assert_eq!(claim.username, "batman");
+    assert_eq!(token.verified_count(), 1);
+
+    // The cache is shared by clones of the token.
+    token.clone().try_verify_token(jwt)?;
+    assert_eq!(token.verified_count(), 1);
+
+    // Another token is verified on its own.
+    let jwt = token.try_create_token(GrpcClaim {
+        username: String::from("robin"),
+        features: 0,
+    })?;
+    let claim = token.try_verify_token(jwt)?;
+    assert_eq!(claim.username, "robin");
+    assert_eq!(token.verified_count(), 2);
+
+    // An invalid token is never cached.
+    assert!(token.try_verify_token("invalid".to_string()).is_err());
+    assert!(token.try_verify_token("invalid".to_string()).is_err());
+    assert_eq!(token.verified_count(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_flight_token_with_secret() -> Result<()> {
+    let claim = || GrpcClaim {
+        username: String::from("batman"),
+        features: 0,
+    };
+
+    // A token issued with a secret is accepted by another one with the same secret.
+    let jwt = GrpcToken::create_with_secret(b"foo").try_create_token(claim())?;
+    let got = GrpcToken::create_with_secret(b"foo").try_verify_token(jwt.clone())?;
+    assert_eq!(got.username, "batman");
+
+    assert!(
+        GrpcToken::create_with_secret(b"bar")
+            .try_verify_token(jwt.clone())
+            .is_err()
+    );
+    assert!(GrpcToken::create().try_verify_token(jwt).is_err());
+
     Ok(())
 }
@@ -28,6 +28,10 @@ use crate::kv_app_error::KVAppError;
 /// Convert either KVAppError or MetaAPIError to MetaAPIError
 pub fn reply_to_api_result<T>(msg: RaftReply) -> Result<T, MetaAPIError>
 where T: DeserializeOwned {
+    let msg = msg
+        .decompress()
+        .map_err(|e| InvalidReply::new("can not decompress RaftReply.compressed_data", &e))?;
+
     if !msg.data.is_empty() {
         let res: T = serde_json::from_str(&msg.data)
             .map_err(|e| InvalidReply::new("can not decode RaftReply.data", &e))?;
@@ -45,6 +49,10 @@ where T: DeserializeOwned {
 /// Convert either KVAppError or MetaError to MetaError
 pub fn reply_to_meta_result<T>(raft_reply: RaftReply) -> Result<T, MetaError>
 where T: DeserializeOwned {
+    let raft_reply = raft_reply
+        .decompress()
+        .map_err(|e| InvalidReply::new("can not decompress RaftReply.compressed_data", &e))?;
+
     if !raft_reply.data.is_empty() {
         let res: T = serde_json::from_str(&raft_reply.data)
             .map_err(|e| InvalidReply::new("can not decode RaftReply.data", &e))?;
@@ -84,8 +92,13 @@ mod tests {
         i: i32,
     }
 
+    use common_meta_types::anyerror::AnyError;
     use common_meta_types::protobuf::RaftReply;
+    use common_meta_types::ForwardRPCError;
+    use common_meta_types::ForwardToLeader;
     use common_meta_types::MetaAPIError;
+    use common_meta_types::MetaDataError;
+    use common_meta_types::MetaDataReadError;
     use common_meta_types::MetaNetworkError;
 
     use crate::reply::reply_to_api_result;
@@ -97,6 +110,8 @@ mod tests {
         let msg = RaftReply {
             data: "foo".to_string(),
             error: "".to_string(),
+            compressed_data: vec![],
+            binary_data: vec![],
         };
         let res: Result<Foo, MetaAPIError> = reply_to_api_result(msg);
         match res {
@@ -117,6 +132,8 @@ mod tests {
         let msg = RaftReply {
             data: "".to_string(),
             error: "foo".to_string(),
+            compressed_data: vec![],
+            binary_data: vec![],
         };
         let res: Result<Foo, MetaAPIError> = reply_to_api_result(msg);
         match res {
@@ -134,4 +151,42 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_error_reply_round_trip() -> anyhow::Result<()> {
+        // Ok reply has an empty error
+
+        let msg = RaftReply::from(Ok::<_, MetaAPIError>(Foo { i: 3 }));
+        assert_eq!("", msg.error);
+        let res: Foo = reply_to_api_result(msg)?;
+        assert_eq!(3, res.i);
+
+        // The error category survives server reply -> client decode
+
+        let leader_err = MetaAPIError::ForwardToLeader(ForwardToLeader {
+            leader_id: Some(2),
+            leader_node: None,
+        });
+        let msg = RaftReply::from(Err::<Foo, _>(leader_err.clone()));
+        assert_eq!("", msg.data);
+        let res: Result<Foo, MetaAPIError> = reply_to_api_result(msg);
+        assert_eq!(Some(leader_err), res.err());
+
+        // A data error returned by a forward target becomes a remote error on the forwarding node
+
+        let read_err = MetaDataError::ReadError(MetaDataReadError::new(
+            "get_kv",
+            "foo",
+            &AnyError::error("bar"),
+        ));
+        let msg = RaftReply::from(Err::<Foo, _>(MetaAPIError::DataError(read_err.clone())));
+        let res: Result<Foo, MetaAPIError> = reply_to_api_result(msg);
+        let forward_err = ForwardRPCError::RemoteError(res.err().unwrap());
+        assert_eq!(
+            MetaAPIError::RemoteError(read_err),
+            MetaAPIError::from(forward_err)
+        );
+
+        Ok(())
+    }
 }
@@ -78,6 +78,7 @@ impl From<MetaGrpcReq> for RaftRequest {
         let raft_request = RaftRequest {
             // Safe unwrap(): serialize to string must be ok.
             data: serde_json::to_string(&v).unwrap(),
+            binary_data: vec![],
         };
 
         debug!(
@@ -94,6 +95,7 @@ impl MetaGrpcReq {
         let raft_request = RaftRequest {
             data: serde_json::to_string(self)
                 .map_err(|e| InvalidArgument::new(e, "fail to encode request"))?,
+            binary_data: vec![],
         };
 
         debug!(
@@ -141,6 +143,7 @@ impl From<MetaGrpcReadReq> for RaftRequest {
         let raft_request = RaftRequest {
             // Safe unwrap(): serialize to string must be ok.
             data: serde_json::to_string(&v).unwrap(),
+            binary_data: vec![],
         };
 
         debug!(
@@ -157,6 +160,7 @@ impl MetaGrpcReadReq {
         let raft_request = RaftRequest {
             data: serde_json::to_string(self)
                 .map_err(|e| InvalidArgument::new(e, "fail to encode request"))?,
+            binary_data: vec![],
         };
 
         debug!(
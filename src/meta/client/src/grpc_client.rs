@@ -46,6 +46,7 @@ use common_meta_api::reply::reply_to_api_result;
 use common_meta_kvapi::kvapi::ListKVReply;
 use common_meta_types::anyerror::AnyError;
 use common_meta_types::protobuf as pb;
+use common_meta_types::protobuf::handshake_request::AuthMethod;
 use common_meta_types::protobuf::meta_service_client::MetaServiceClient;
 use common_meta_types::protobuf::ClientInfo;
 use common_meta_types::protobuf::ClusterStatus;
@@ -58,6 +59,7 @@ use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::protobuf::WatchRequest;
 use common_meta_types::protobuf::WatchResponse;
 use common_meta_types::ConnectionError;
+use common_meta_types::Features;
 use common_meta_types::GrpcConfig;
 use common_meta_types::MetaClientError;
 use common_meta_types::MetaError;
@@ -869,6 +871,8 @@ impl MetaGrpcClient {
             HandshakeRequest {
                 protocol_version: my_ver,
                 payload,
+                features: Features::SUPPORTED.bits(),
+                auth_method: AuthMethod::BasicAuth as i32,
             }
         }));
 
@@ -21,18 +21,36 @@ use common_meta_client::to_digit_ver;
 use common_meta_client::MIN_METASRV_SEMVER;
 use common_meta_types::protobuf::meta_service_server::MetaService;
 use common_meta_types::protobuf::meta_service_server::MetaServiceServer;
+use common_meta_types::protobuf::CancelStreamReply;
+use common_meta_types::protobuf::CancelStreamRequest;
 use common_meta_types::protobuf::ClientInfo;
 use common_meta_types::protobuf::ClusterStatus;
+use common_meta_types::protobuf::ClusterVersionReply;
+use common_meta_types::protobuf::CountPrefixReply;
+use common_meta_types::protobuf::CountPrefixRequest;
 use common_meta_types::protobuf::Empty;
 use common_meta_types::protobuf::ExportedChunk;
+use common_meta_types::protobuf::FailedAppliesReply;
 use common_meta_types::protobuf::HandshakeResponse;
+use common_meta_types::protobuf::ImportReply;
+use common_meta_types::protobuf::IncrementReply;
+use common_meta_types::protobuf::IncrementRequest;
 use common_meta_types::protobuf::MemberListReply;
 use common_meta_types::protobuf::MemberListRequest;
 use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::protobuf::ReadLogReply;
+use common_meta_types::protobuf::ReadLogRequest;
+use common_meta_types::protobuf::SetClusterVersionRequest;
+use common_meta_types::protobuf::SetReadOnlyRequest;
+use common_meta_types::protobuf::SetSchemaVersionReply;
+use common_meta_types::protobuf::SetSchemaVersionRequest;
 use common_meta_types::protobuf::StreamItem;
+use common_meta_types::protobuf::TransferLeaderRequest;
 use common_meta_types::protobuf::TxnReply;
 use common_meta_types::protobuf::TxnRequest;
+use common_meta_types::protobuf::WaitAppliedReply;
+use common_meta_types::protobuf::WaitAppliedRequest;
 use common_meta_types::protobuf::WatchRequest;
 use common_meta_types::protobuf::WatchResponse;
 use futures::Stream;
@@ -61,6 +79,7 @@ impl MetaService for GrpcServiceForTestImpl {
             Ok(HandshakeResponse {
                 protocol_version: to_digit_ver(&MIN_METASRV_SEMVER),
                 payload: vec![],
+                features: 0,
             })
         });
         Ok(Response::new(Box::pin(output)))
@@ -91,6 +110,13 @@ impl MetaService for GrpcServiceForTestImpl {
         todo!()
     }
 
+    async fn import(
+        &self,
+        _request: Request<Streaming<ExportedChunk>>,
+    ) -> Result<Response<ImportReply>, Status> {
+        todo!()
+    }
+
     type WatchStream =
         Pin<Box<dyn Stream<Item = Result<WatchResponse, tonic::Status>> + Send + 'static>>;
 
@@ -122,6 +148,76 @@ impl MetaService for GrpcServiceForTestImpl {
         todo!()
     }
 
+    async fn transfer_leader(
+        &self,
+        _request: Request<TransferLeaderRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        todo!()
+    }
+
+    async fn read_log(
+        &self,
+        _request: Request<ReadLogRequest>,
+    ) -> Result<Response<ReadLogReply>, Status> {
+        todo!()
+    }
+
+    async fn count_prefix(
+        &self,
+        _request: Request<CountPrefixRequest>,
+    ) -> Result<Response<CountPrefixReply>, Status> {
+        todo!()
+    }
+
+    async fn increment(
+        &self,
+        _request: Request<IncrementRequest>,
+    ) -> Result<Response<IncrementReply>, Status> {
+        todo!()
+    }
+
+    async fn cancel_stream(
+        &self,
+        _request: Request<CancelStreamRequest>,
+    ) -> Result<Response<CancelStreamReply>, Status> {
+        todo!()
+    }
+
+    async fn wait_applied(
+        &self,
+        _request: Request<WaitAppliedRequest>,
+    ) -> Result<Response<WaitAppliedReply>, Status> {
+        todo!()
+    }
+
+    async fn set_read_only(
+        &self,
+        _request: Request<SetReadOnlyRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        todo!()
+    }
+
+    async fn set_cluster_version(
+        &self,
+        _request: Request<SetClusterVersionRequest>,
+    ) -> Result<Response<ClusterVersionReply>, Status> {
+        todo!()
+    }
+
+    async fn set_schema_version(
+        &self,
+        _request: Request<SetSchemaVersionRequest>,
+    ) -> Result<Response<SetSchemaVersionReply>, Status> {
+        todo!()
+    }
+
+    async fn get_failed_applies(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<FailedAppliesReply>, Status> {
+        todo!()
+    }
+
     async fn get_client_info(
         &self,
         _request: Request<Empty>,
@@ -127,6 +127,8 @@ where F: Fn(&str, Vec<u8>) -> Result<Vec<u8>, anyhow::Error>
         match log_entry.cmd {
             Cmd::AddNode { .. } => Ok(None),
             Cmd::RemoveNode { .. } => Ok(None),
+            // A counter is decimal text, not a protobuf message.
+            Cmd::IncrementKV(_) => Ok(None),
             Cmd::UpsertKV(ups) => {
                 let x = LogEntry {
                     txid: log_entry.txid,
@@ -26,9 +26,11 @@ use common_meta_types::Cmd;
 use common_meta_types::ConditionResult;
 use common_meta_types::Entry;
 use common_meta_types::EntryPayload;
+use common_meta_types::IncrementKV;
 use common_meta_types::KVMeta;
 use common_meta_types::MatchSeq;
 use common_meta_types::Node;
+use common_meta_types::Operation;
 use common_meta_types::SeqV;
 use common_meta_types::SeqValue;
 use common_meta_types::StoredMembership;
@@ -80,6 +82,14 @@ impl<'a> Applier<'a> {
     pub async fn apply(&mut self, entry: &Entry) -> Result<AppliedState, io::Error> {
         info!("apply: entry: {}", entry,);
 
+        #[cfg(feature = "testing")]
+        if self.sm.blocking_config().fail_apply {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "apply is set to fail for testing",
+            ));
+        }
+
         let log_id = &entry.log_id;
         let log_time_ms = Self::get_log_time(entry);
 
@@ -95,9 +105,30 @@ impl<'a> Applier<'a> {
             }
             EntryPayload::Normal(ref data) => {
                 info!("apply: normal: {}", data);
-                assert!(data.txid.is_none(), "txid is disabled");
 
-                self.apply_cmd(&data.cmd).await?
+                // A retried write carries the same txid: return the previous response
+                // instead of applying it again.
+                let last_resp = data
+                    .txid
+                    .as_ref()
+                    .and_then(|txid| self.sm.sys_data_ref().get_client_last_resp(txid))
+                    .cloned();
+
+                if let Some(last_resp) = last_resp {
+                    info!("apply: duplicated txid, return the last response: {}", data);
+                    last_resp
+                } else {
+                    let applied_state = self.apply_cmd(&data.cmd).await?;
+
+                    if let Some(txid) = &data.txid {
+                        self.sm.sys_data_mut().update_client_last_resp(
+                            txid,
+                            applied_state.clone(),
+                            log_id.index,
+                        );
+                    }
+                    applied_state
+                }
             }
             EntryPayload::Membership(ref mem) => {
                 info!("apply: membership: {:?}", mem);
@@ -139,6 +170,8 @@ impl<'a> Applier<'a> {
             Cmd::UpsertKV(ref upsert_kv) => self.apply_upsert_kv(upsert_kv).await?,
 
             Cmd::Transaction(txn) => self.apply_txn(txn).await?,
+
+            Cmd::IncrementKV(ref incr) => self.apply_increment_kv(incr).await?,
         };
 
         info!("apply_result: cmd: {}; res: {}", cmd, res);
@@ -198,6 +231,32 @@ impl<'a> Applier<'a> {
         Ok(st)
     }
 
+    /// Add a delta to the number stored in a key, or create the key with the delta if it is absent.
+    ///
+    /// The expiration of an existing key is kept.
+    /// If the value is not a number or the sum overflows, the key is left unchanged.
+    #[minitrace::trace]
+    async fn apply_increment_kv(&mut self, incr: &IncrementKV) -> Result<AppliedState, io::Error> {
+        debug!(incr = as_debug!(incr); "apply_increment_kv");
+
+        let prev = self.sm.get_maybe_expired_kv(&incr.key).await?;
+
+        let Some(value) = incr.incremented(prev.as_ref().map(|x| x.data.as_slice())) else {
+            info!("applied IncrementKV: not a number or overflow: {}", incr);
+            return Ok(Change::new(prev.clone(), prev).into());
+        };
+
+        let upsert_kv = UpsertKV::new(
+            &incr.key,
+            MatchSeq::GE(0),
+            Operation::Update(IncrementKV::encode_value(value)),
+            prev.and_then(|x| x.meta),
+        );
+        let (prev, result) = self.upsert_kv(&upsert_kv).await?;
+
+        Ok(Change::new(prev, result).into())
+    }
+
     // TODO(1): when get an applier, pass in a now_ms to ensure all expired are cleaned.
     /// Update or insert a kv entry.
     ///
@@ -228,6 +287,11 @@ impl<'a> Applier<'a> {
         Ok((prev, result))
     }
 
+    /// Apply a txn to the in-memory state machine `SMV002`.
+    ///
+    /// It is not a sled transaction and nothing is flushed here:
+    /// a txn is durable because its raft log entry is flushed before it is committed,
+    /// and the state machine is rebuilt from the last snapshot and the logs after it on restart.
     #[minitrace::trace]
     async fn apply_txn(&mut self, req: &TxnRequest) -> Result<AppliedState, io::Error> {
         debug!(txn = as_display!(req); "apply txn cmd");
@@ -79,6 +79,33 @@ pub struct RaftConfig {
     /// The maximum number of applied logs to keep before purging
     pub max_applied_log_to_keep: u64,
 
+    /// The max number of log entries a leader sends to a follower in one append-entries request.
+    ///
+    /// A lagging follower catches up in batches of this size,
+    /// so that applying a batch does not stall it for long and it stays responsive to heartbeats.
+    pub max_payload_entries: u64,
+
+    /// The max number of keys kept in the node-local cache for `get_kv` reads.
+    /// The cache is disabled if it is 0.
+    pub kv_read_cache_size: u64,
+
+    /// The max number of writes a leader proposes to raft at the same time.
+    ///
+    /// Writes beyond it wait and are proposed in the order of their priority.
+    pub max_inflight_proposals: u64,
+
+    /// Run this node as a read replica: it joins a cluster as a learner, serves reads from its
+    /// local state machine and rejects writes instead of forwarding them to the leader.
+    pub read_replica: bool,
+
+    /// The secret to sign and verify the tokens issued in gRPC handshake, shared by all nodes in a cluster.
+    ///
+    /// With it, a token issued by one node is accepted by the others,
+    /// e.g., by the raft service of the target of a leadership transfer.
+    /// It is also the issuer key a JWT handshake is verified against.
+    /// If it is empty, every node generates a random one and JWT handshake is disabled.
+    pub token_secret: String,
+
     /// Single node metasrv. It creates a single node cluster if meta data is not initialized.
     /// Otherwise it opens the previous one.
     /// This is mainly for testing purpose.
@@ -138,6 +165,11 @@ impl Default for RaftConfig {
             heartbeat_interval: 1000,
             install_snapshot_timeout: 4000,
             max_applied_log_to_keep: 1000,
+            max_payload_entries: 300,
+            kv_read_cache_size: 0,
+            max_inflight_proposals: 256,
+            read_replica: false,
+            token_secret: "".to_string(),
             single: false,
             join: vec![],
             leave_via: vec![],
@@ -226,6 +258,18 @@ impl RaftConfig {
                 "--join must not be set to itself",
             )));
         }
+
+        if self.max_payload_entries == 0 {
+            return Err(MetaStartupError::InvalidConfig(String::from(
+                "--max-payload-entries must be greater than 0",
+            )));
+        }
+
+        if self.max_inflight_proposals == 0 {
+            return Err(MetaStartupError::InvalidConfig(String::from(
+                "--max-inflight-proposals must be greater than 0",
+            )));
+        }
         Ok(())
     }
 
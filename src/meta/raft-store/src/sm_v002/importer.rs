@@ -55,8 +55,8 @@ impl Importer {
             RaftStoreEntry::RaftStateKV { .. } => {
                 // Not part of state machine
             }
-            RaftStoreEntry::ClientLastResps { .. } => {
-                unreachable!("client last resp is not supported")
+            RaftStoreEntry::ClientLastResps { key, value } => {
+                d.sys_data_mut().insert_client_last_resp(key, value);
             }
             RaftStoreEntry::Nodes { key, value } => {
                 d.sys_data_mut().nodes_mut().insert(key, value);
@@ -13,14 +13,21 @@
 // limitations under the License.
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
+use common_meta_types::AppliedState;
 use common_meta_types::LogId;
 use common_meta_types::Node;
 use common_meta_types::NodeId;
+use common_meta_types::RaftTxId;
 use common_meta_types::StoredMembership;
 use log::debug;
 
 use crate::sm_v002::leveled_store::sys_data_api::SysDataApiRO;
+use crate::state_machine::ClientLastRespValue;
+
+/// The max number of clients whose last response is kept for de-duplicating retried writes.
+pub const MAX_CLIENT_LAST_RESPS: usize = 1024;
 
 /// System data(non-user data).
 ///
@@ -40,6 +47,16 @@ pub struct SysData {
     ///
     /// A seq is globally unique and monotonically increasing.
     sequence: u64,
+
+    /// The last response to every client that writes with a [`RaftTxId`].
+    ///
+    /// A retried write with the same txid gets this response instead of being applied again.
+    /// At most [`MAX_CLIENT_LAST_RESPS`] clients are kept.
+    client_last_resps: BTreeMap<String, ClientLastRespValue>,
+
+    /// The clients in `client_last_resps` ordered by the log index of their last response,
+    /// to find the one to evict without scanning all of them.
+    client_last_resps_by_index: BTreeSet<(u64, String)>,
 }
 
 impl SysDataApiRO for SysData {
@@ -58,6 +75,10 @@ impl SysDataApiRO for SysData {
     fn nodes_ref(&self) -> &BTreeMap<NodeId, Node> {
         &self.nodes
     }
+
+    fn client_last_resps_ref(&self) -> &BTreeMap<String, ClientLastRespValue> {
+        &self.client_last_resps
+    }
 }
 
 impl<T> SysDataApiRO for T
@@ -78,6 +99,10 @@ where T: AsRef<SysData>
     fn nodes_ref(&self) -> &BTreeMap<NodeId, Node> {
         self.as_ref().nodes_ref()
     }
+
+    fn client_last_resps_ref(&self) -> &BTreeMap<String, ClientLastRespValue> {
+        self.as_ref().client_last_resps_ref()
+    }
 }
 
 impl SysData {
@@ -105,4 +130,44 @@ impl SysData {
     pub fn nodes_mut(&mut self) -> &mut BTreeMap<NodeId, Node> {
         &mut self.nodes
     }
+
+    /// Return the response of a previously applied write with the same txid, if any.
+    pub fn get_client_last_resp(&self, txid: &RaftTxId) -> Option<&AppliedState> {
+        let last = self.client_last_resps.get(&txid.client)?;
+        if last.req_serial_num == txid.serial {
+            Some(&last.res)
+        } else {
+            None
+        }
+    }
+
+    /// Record the response to the write identified by `txid`, which is applied at `log_index`.
+    ///
+    /// When there are too many clients, the one that has not written for the longest time is evicted.
+    pub fn update_client_last_resp(&mut self, txid: &RaftTxId, res: AppliedState, log_index: u64) {
+        self.insert_client_last_resp(txid.client.clone(), ClientLastRespValue {
+            req_serial_num: txid.serial,
+            res,
+            log_index,
+        });
+
+        while self.client_last_resps.len() > MAX_CLIENT_LAST_RESPS {
+            let Some((_, client)) = self.client_last_resps_by_index.pop_first() else {
+                break;
+            };
+            self.client_last_resps.remove(&client);
+        }
+    }
+
+    /// Insert the last response to a client, e.g., when importing a snapshot.
+    pub fn insert_client_last_resp(&mut self, client: String, value: ClientLastRespValue) {
+        let log_index = value.log_index;
+
+        if let Some(prev) = self.client_last_resps.insert(client.clone(), value) {
+            self.client_last_resps_by_index
+                .remove(&(prev.log_index, client.clone()));
+        }
+
+        self.client_last_resps_by_index.insert((log_index, client));
+    }
 }
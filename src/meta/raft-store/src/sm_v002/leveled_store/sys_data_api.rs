@@ -19,6 +19,8 @@ use common_meta_types::Node;
 use common_meta_types::NodeId;
 use common_meta_types::StoredMembership;
 
+use crate::state_machine::ClientLastRespValue;
+
 /// APIs to access the non-user-data of the state machine(leveled map).
 pub trait SysDataApiRO {
     fn curr_seq(&self) -> u64;
@@ -28,4 +30,6 @@ pub trait SysDataApiRO {
     fn last_membership_ref(&self) -> &StoredMembership;
 
     fn nodes_ref(&self) -> &BTreeMap<NodeId, Node>;
+
+    fn client_last_resps_ref(&self) -> &BTreeMap<String, ClientLastRespValue>;
 }
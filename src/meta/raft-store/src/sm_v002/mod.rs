@@ -28,6 +28,7 @@ mod sm_v002_test;
 mod snapshot_view_v002_test;
 
 pub use importer::Importer;
+pub use sm_v002::ApplyEntriesError;
 pub use sm_v002::SMV002;
 pub use snapshot_store::SnapshotStoreError;
 pub use snapshot_store::SnapshotStoreV002;
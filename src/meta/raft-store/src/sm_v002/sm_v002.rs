@@ -27,6 +27,7 @@ use common_meta_stoerr::MetaBytesError;
 use common_meta_types::protobuf::StreamItem;
 use common_meta_types::AppliedState;
 use common_meta_types::Entry;
+use common_meta_types::LogId;
 use common_meta_types::MatchSeqExt;
 use common_meta_types::Operation;
 use common_meta_types::SeqV;
@@ -66,6 +67,18 @@ use crate::state_machine::sm::BlockingConfig;
 use crate::state_machine::ExpireKey;
 use crate::state_machine::StateMachineSubscriber;
 
+/// An error that occurs when applying a batch of log entries.
+///
+/// It carries the log id of the entry that failed; the entries before it are applied.
+#[derive(Debug, thiserror::Error)]
+#[error("{source}")]
+pub struct ApplyEntriesError {
+    pub log_id: LogId,
+
+    #[source]
+    pub source: StorageIOError,
+}
+
 /// A wrapper that implements KVApi **readonly** methods for the state machine.
 pub struct SMV002KVApi<'a> {
     sm: &'a SMV002,
@@ -120,6 +133,22 @@ impl<'a> kvapi::KVApi for SMV002KVApi<'a> {
 }
 
 impl<'a> SMV002KVApi<'a> {
+    /// Count the non-expired keys that start with `prefix`, without building a reply for every key.
+    ///
+    /// An empty prefix counts all keys.
+    pub async fn count_prefix(&self, prefix: &str) -> Result<u64, io::Error> {
+        let local_now_ms = SeqV::<()>::now_ms();
+
+        self.sm
+            .list_kv(prefix)
+            .await?
+            .try_fold(0, move |n, (_k, v)| {
+                let n = if v.is_expired(local_now_ms) { n } else { n + 1 };
+                future::ready(Ok(n))
+            })
+            .await
+    }
+
     fn non_expired<V>(seq_value: Option<SeqV<V>>, now_ms: u64) -> Option<SeqV<V>> {
         if seq_value.is_expired(now_ms) {
             None
@@ -198,6 +227,10 @@ impl SMV002 {
             }
 
             sm.replace(LeveledMap::new(level_data));
+
+            if let Some(subscriber) = &sm.subscriber {
+                subscriber.snapshot_installed();
+            }
         }
 
         info!(
@@ -236,20 +269,21 @@ impl SMV002 {
         Applier::new(self)
     }
 
+    /// Apply log entries in order, and stop at the first one that fails.
     pub async fn apply_entries<'a>(
         &mut self,
         entries: impl IntoIterator<Item = &'a Entry>,
-    ) -> Result<Vec<AppliedState>, StorageIOError> {
+    ) -> Result<Vec<AppliedState>, ApplyEntriesError> {
         let mut applier = Applier::new(self);
 
         let mut res = vec![];
 
         for ent in entries.into_iter() {
             let log_id = *ent.get_log_id();
-            let r = applier
-                .apply(ent)
-                .await
-                .map_err(|e| StorageIOError::apply(log_id, &e))?;
+            let r = applier.apply(ent).await.map_err(|e| ApplyEntriesError {
+                log_id,
+                source: StorageIOError::apply(log_id, &e),
+            })?;
             res.push(r);
         }
         Ok(res)
@@ -290,6 +324,35 @@ impl SMV002 {
         Ok(strm.boxed())
     }
 
+    /// List kv entries in range `[start, end)`.
+    ///
+    /// It does not check expiration of the returned entries.
+    pub async fn range_kv(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> Result<ResultStream<(String, SeqV)>, io::Error> {
+        let strm = self
+            .levels
+            .str_map()
+            .range(start.to_string()..end.to_string())
+            .await?;
+
+        // Skip tombstone
+        let strm = strm.try_filter_map(|(k, marked)| {
+            let seqv = Into::<Option<SeqV>>::into(marked);
+            let res = seqv.map(|x| (k, x));
+            future::ready(Ok(res))
+        });
+
+        // Make it static
+
+        let vs = strm.collect::<Vec<_>>().await;
+        let strm = futures::stream::iter(vs);
+
+        Ok(strm.boxed())
+    }
+
     pub(crate) fn update_expire_cursor(&mut self, log_time_ms: u64) {
         if log_time_ms < self.expire_cursor.time_ms {
             warn!(
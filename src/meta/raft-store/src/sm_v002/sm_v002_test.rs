@@ -12,14 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_meta_types::AppliedState;
+use common_meta_types::Cmd;
+use common_meta_types::Entry;
+use common_meta_types::EntryPayload;
+use common_meta_types::LogEntry;
+use common_meta_types::RaftTxId;
 use common_meta_types::SeqV;
 use common_meta_types::SeqValue;
 use common_meta_types::UpsertKV;
 use futures_util::TryStreamExt;
+use openraft::testing::log_id;
 use pretty_assertions::assert_eq;
 
 use crate::sm_v002::leveled_store::map_api::AsMap;
 use crate::sm_v002::leveled_store::map_api::MapApiRO;
+use crate::sm_v002::leveled_store::sys_data::SysData;
+use crate::sm_v002::leveled_store::sys_data::MAX_CLIENT_LAST_RESPS;
+use crate::sm_v002::leveled_store::sys_data_api::SysDataApiRO;
 use crate::sm_v002::marked::Marked;
 use crate::sm_v002::SMV002;
 use crate::state_machine::ExpireKey;
@@ -295,6 +305,64 @@ async fn test_inserting_expired_becomes_deleting() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_apply_dedup_by_txid() -> anyhow::Result<()> {
+    let mut sm = SMV002::default();
+
+    let ent = |index: u64, txid: Option<RaftTxId>, value: &str| Entry {
+        log_id: log_id(1, 0, index),
+        payload: EntryPayload::Normal(
+            LogEntry::new(Cmd::UpsertKV(UpsertKV::update("a", value.as_bytes()))).with_txid(txid),
+        ),
+    };
+
+    let txid = || Some(RaftTxId::new("client-1", 1));
+
+    let res = sm
+        .apply_entries(&[ent(1, txid(), "a0"), ent(2, txid(), "a1")])
+        .await?;
+    assert_eq!(res[0], res[1], "a retried write gets the same reply");
+
+    let got = sm.get_maybe_expired_kv("a").await?;
+    assert_eq!(got, Some(SeqV::new(1, b("a0"))), "applied only once");
+
+    // Another serial, or no txid, is applied.
+    let res = sm
+        .apply_entries(&[
+            ent(3, Some(RaftTxId::new("client-1", 2)), "a2"),
+            ent(4, Some(RaftTxId::new("client-2", 1)), "a3"),
+            ent(5, None, "a4"),
+            ent(6, None, "a4"),
+        ])
+        .await?;
+    assert_eq!(res.len(), 4);
+
+    let got = sm.get_maybe_expired_kv("a").await?;
+    assert_eq!(got, Some(SeqV::new(5, b("a4"))));
+
+    Ok(())
+}
+
+#[test]
+fn test_client_last_resps_evict_the_oldest() {
+    let mut sd = SysData::default();
+    let txid = |client: &str, serial: u64| RaftTxId::new(client, serial);
+
+    for i in 0..MAX_CLIENT_LAST_RESPS as u64 {
+        sd.update_client_last_resp(&txid(&format!("c{}", i), 1), AppliedState::None, i);
+    }
+
+    // c0 writes again, thus c1 becomes the oldest one.
+    sd.update_client_last_resp(&txid("c0", 2), AppliedState::None, 10_000);
+    sd.update_client_last_resp(&txid("new", 1), AppliedState::None, 10_001);
+
+    assert_eq!(MAX_CLIENT_LAST_RESPS, sd.client_last_resps_ref().len());
+    assert!(sd.get_client_last_resp(&txid("c0", 2)).is_some());
+    assert!(sd.get_client_last_resp(&txid("c1", 1)).is_none());
+    assert!(sd.get_client_last_resp(&txid("c2", 1)).is_some());
+    assert!(sd.get_client_last_resp(&txid("new", 1)).is_some());
+}
+
 fn s(x: impl ToString) -> String {
     x.to_string()
 }
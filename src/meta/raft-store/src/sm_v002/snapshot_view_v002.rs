@@ -16,6 +16,9 @@ use std::future;
 use std::io;
 use std::sync::Arc;
 
+use common_meta_types::cluster_version::decode_cluster_version;
+use common_meta_types::cluster_version::CLUSTER_VERSION_KEY;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
 use common_meta_types::SeqNum;
 use common_meta_types::SeqV;
 use common_meta_types::SnapshotMeta;
@@ -114,6 +117,15 @@ impl SnapshotViewV002 {
         Ok(())
     }
 
+    /// Return the cluster version stored in the compacted data.
+    pub async fn cluster_version(&self) -> Result<u64, io::Error> {
+        let got = self.compacted.str_map().get(CLUSTER_VERSION_KEY).await?;
+        let seqv: Option<SeqV> = got.into();
+        Ok(decode_cluster_version(
+            seqv.as_ref().map(|x| x.data.as_slice()),
+        ))
+    }
+
     /// Export all its data in RaftStoreEntry format.
     // pub async fn export(&self) -> Result<impl Stream<Item = RaftStoreEntry> + '_, io::Error> {
     pub async fn export(&self) -> Result<ResultStream<RaftStoreEntry>, io::Error> {
@@ -165,6 +177,20 @@ impl SnapshotViewV002 {
             })
         }
 
+        // Client last responses
+        //
+        // A node of an older build can not import them,
+        // thus they are exported only when every node is known to support them.
+
+        if self.cluster_version().await? >= CLUSTER_VERSION_V1 {
+            for (client, resp) in d.client_last_resps_ref().iter() {
+                sm_meta.push(RaftStoreEntry::ClientLastResps {
+                    key: client.clone(),
+                    value: resp.clone(),
+                })
+            }
+        }
+
         // kv
 
         let strm = self.compacted.str_map().range::<String, _>(..).await?;
@@ -12,12 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
 use std::sync::Arc;
 
+use common_meta_types::cluster_version::CLUSTER_VERSION_KEY;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::AppliedState;
 use common_meta_types::Endpoint;
 use common_meta_types::KVMeta;
 use common_meta_types::Membership;
 use common_meta_types::Node;
+use common_meta_types::RaftTxId;
 use common_meta_types::StoredMembership;
 use common_meta_types::UpsertKV;
 use futures_util::TryStreamExt;
@@ -250,6 +255,39 @@ async fn test_import() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_export_client_last_resps_by_cluster_version() -> anyhow::Result<()> {
+    let mut sm = SMV002::default();
+    sm.sys_data_mut()
+        .update_client_last_resp(&RaftTxId::new("c1", 1), AppliedState::None, 1);
+
+    let n_client_last_resps = |snapshot: SnapshotViewV002| async move {
+        let entries = snapshot.export().await?.try_collect::<Vec<_>>().await?;
+        let n = entries
+            .iter()
+            .filter(|x| matches!(x, RaftStoreEntry::ClientLastResps { .. }))
+            .count();
+        Ok::<_, io::Error>(n)
+    };
+
+    assert_eq!(
+        0,
+        n_client_last_resps(sm.full_snapshot_view()).await?,
+        "an older build can not import them"
+    );
+
+    let mut a = sm.new_applier();
+    a.upsert_kv(&UpsertKV::update(
+        CLUSTER_VERSION_KEY,
+        CLUSTER_VERSION_V1.to_string().as_bytes(),
+    ))
+    .await?;
+
+    assert_eq!(1, n_client_last_resps(sm.full_snapshot_view()).await?);
+
+    Ok(())
+}
+
 /// Create multi levels store:
 ///
 /// l2 |         c(D) d
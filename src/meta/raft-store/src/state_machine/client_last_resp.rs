@@ -26,6 +26,13 @@ use serde::Serialize;
 pub struct ClientLastRespValue {
     pub req_serial_num: u64,
     pub res: AppliedState,
+
+    /// The index of the log that produced `res`.
+    ///
+    /// It is used to evict the least recently written clients.
+    /// Values written before this field was added are `0`.
+    #[serde(default)]
+    pub log_index: u64,
 }
 
 impl SledSerde for ClientLastRespValue {
@@ -38,6 +38,7 @@ use common_meta_types::Cmd;
 use common_meta_types::ConditionResult;
 use common_meta_types::Entry;
 use common_meta_types::EntryPayload;
+use common_meta_types::IncrementKV;
 use common_meta_types::KVMeta;
 use common_meta_types::LogId;
 use common_meta_types::MatchSeq;
@@ -97,6 +98,11 @@ const TREE_STATE_MACHINE: &str = "state_machine";
 /// StateMachine subscriber trait
 pub trait StateMachineSubscriber: Debug + Sync + Send {
     fn kv_changed(&self, change: Change<Vec<u8>, String>);
+
+    /// Called when the data is replaced by an installed snapshot, without emitting any change.
+    ///
+    /// It is called with the state machine write lock held.
+    fn snapshot_installed(&self) {}
 }
 
 /// The state machine of the `MemStore`.
@@ -137,11 +143,15 @@ impl SerializableSnapshot {
     }
 }
 
-/// Configuration of what operation to block for testing purpose.
+/// Configuration of what operation to block or to fail for testing purpose.
 #[derive(Debug, Clone, Default)]
 pub struct BlockingConfig {
     pub write_snapshot: Duration,
     pub compact_snapshot: Duration,
+
+    /// Make applying a log entry fail.
+    #[cfg(feature = "testing")]
+    pub fail_apply: bool,
 }
 
 impl StateMachine {
@@ -335,6 +345,7 @@ impl StateMachine {
                         self.txn_client_last_resp_update(
                             &txid.client,
                             (txid.serial, applied_state.clone()),
+                            log_id.index,
                             &txn_tree,
                         )?;
                     }
@@ -461,6 +472,34 @@ impl StateMachine {
         Ok(Change::new(prev, result).into())
     }
 
+    /// Add a delta to the number stored in a key, or create the key with the delta if it is absent.
+    ///
+    /// The expiration of an existing key is kept.
+    /// If the value is not a number or the sum overflows, the key is left unchanged.
+    fn apply_increment_kv_cmd(
+        &self,
+        incr: &IncrementKV,
+        txn_tree: &mut TransactionSledTree,
+        log_time_ms: u64,
+    ) -> Result<AppliedState, MetaStorageError> {
+        debug!(incr = as_debug!(incr); "apply_increment_kv_cmd");
+
+        let prev = txn_tree.key_space::<GenericKV>().get(&incr.key)?;
+        let (_expired, prev) = Self::expire_seq_v(prev, log_time_ms);
+
+        let Some(value) = incr.incremented(prev.as_ref().map(|x| x.data.as_slice())) else {
+            return Ok(Change::new(prev.clone(), prev).into());
+        };
+
+        let upsert_kv = UpsertKV::new(
+            &incr.key,
+            MatchSeq::GE(0),
+            Operation::Update(IncrementKV::encode_value(value)),
+            prev.and_then(|x| x.meta),
+        );
+        self.apply_update_kv_cmd(&upsert_kv, txn_tree, log_time_ms)
+    }
+
     fn return_value_condition_result(
         &self,
         expected: i32,
@@ -800,6 +839,8 @@ impl StateMachine {
             }
 
             Cmd::Transaction(txn) => self.apply_txn_cmd(txn, txn_tree, kv_pairs, log_time_ms),
+
+            Cmd::IncrementKV(ref incr) => self.apply_increment_kv_cmd(incr, txn_tree, log_time_ms),
         };
 
         let elapsed = now.elapsed().as_micros();
@@ -993,11 +1034,13 @@ impl StateMachine {
         &self,
         key: &str,
         value: (u64, AppliedState),
+        log_index: u64,
         txn_tree: &TransactionSledTree,
     ) -> Result<AppliedState, MetaStorageError> {
         let v = ClientLastRespValue {
             req_serial_num: value.0,
             res: value.1.clone(),
+            log_index,
         };
         let txn_ks = txn_tree.key_space::<ClientLastResps>();
         txn_ks.insert(&key.to_string(), &v)?;
@@ -53,5 +53,37 @@ fn test_raft_config() -> anyhow::Result<()> {
         )
     }
 
+    {
+        let raft_config = &RaftConfig {
+            single: true,
+            max_payload_entries: 0,
+            ..Default::default()
+        };
+        let r = raft_config.check();
+
+        assert_eq!(
+            r,
+            Err(MetaStartupError::InvalidConfig(String::from(
+                "--max-payload-entries must be greater than 0",
+            )))
+        )
+    }
+
+    {
+        let raft_config = &RaftConfig {
+            single: true,
+            max_inflight_proposals: 0,
+            ..Default::default()
+        };
+        let r = raft_config.check();
+
+        assert_eq!(
+            r,
+            Err(MetaStartupError::InvalidConfig(String::from(
+                "--max-inflight-proposals must be greater than 0",
+            )))
+        )
+    }
+
     Ok(())
 }
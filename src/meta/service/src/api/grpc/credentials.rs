@@ -0,0 +1,362 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Semaphore;
+use ring::constant_time;
+use ring::digest;
+use ring::digest::SHA256_OUTPUT_LEN;
+use ring::pbkdf2;
+use ring::rand::SecureRandom;
+use ring::rand::SystemRandom;
+use tonic::Status;
+
+static PBKDF2_ALGORITHM: pbkdf2::Algorithm = pbkdf2::PBKDF2_HMAC_SHA256;
+
+/// The scheme name at the beginning of an encoded [`PasswordHash`].
+const PBKDF2_SHA256: &str = "pbkdf2-sha256";
+
+/// The number of PBKDF2 iterations of a newly generated hash.
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 100_000;
+
+const SALT_LEN: usize = 16;
+
+/// How long a successfully verified password is accepted without deriving its hash again.
+pub const VERIFIED_PASSWORD_TTL: Duration = Duration::from_secs(60);
+
+/// The max number of PBKDF2 derivations that run at the same time.
+pub const MAX_CONCURRENT_PASSWORD_VERIFY: usize = 4;
+
+/// A salted PBKDF2-HMAC-SHA256 hash of a password.
+///
+/// It is encoded as `pbkdf2-sha256$<iterations>$<hex salt>$<hex hash>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordHash {
+    iterations: NonZeroU32,
+    salt: Vec<u8>,
+    hash: Vec<u8>,
+}
+
+impl PasswordHash {
+    /// Hash a password with a random salt and the default number of iterations.
+    pub fn generate(password: &str) -> Self {
+        let mut salt = vec![0; SALT_LEN];
+        // safe unwrap(): the system random source does not fail on supported platforms.
+        SystemRandom::new().fill(&mut salt).unwrap();
+
+        // safe unwrap(): it is not zero.
+        let iterations = NonZeroU32::new(DEFAULT_PBKDF2_ITERATIONS).unwrap();
+
+        Self::new(password, salt, iterations)
+    }
+
+    pub fn new(password: &str, salt: Vec<u8>, iterations: NonZeroU32) -> Self {
+        let mut hash = vec![0; SHA256_OUTPUT_LEN];
+        pbkdf2::derive(
+            PBKDF2_ALGORITHM,
+            iterations,
+            &salt,
+            password.as_bytes(),
+            &mut hash,
+        );
+
+        Self {
+            iterations,
+            salt,
+            hash,
+        }
+    }
+
+    /// Returns true if the password derives the same hash.
+    ///
+    /// `pbkdf2::verify()` compares the derived hash in constant time,
+    /// so that the time it takes does not tell how much of a wrong password matches.
+    pub fn verify(&self, password: &str) -> bool {
+        pbkdf2::verify(
+            PBKDF2_ALGORITHM,
+            self.iterations,
+            &self.salt,
+            password.as_bytes(),
+            &self.hash,
+        )
+        .is_ok()
+    }
+}
+
+impl fmt::Display for PasswordHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}${}${}${}",
+            PBKDF2_SHA256,
+            self.iterations,
+            hex::encode(&self.salt),
+            hex::encode(&self.hash)
+        )
+    }
+}
+
+impl FromStr for PasswordHash {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split('$').collect::<Vec<_>>();
+        let [scheme, iterations, salt, hash] = parts[..] else {
+            return Err(format!(
+                "invalid password hash, expect: {}$<iterations>$<salt>$<hash>",
+                PBKDF2_SHA256
+            ));
+        };
+
+        if scheme != PBKDF2_SHA256 {
+            return Err(format!(
+                "unsupported password hash scheme: {}, expect: {}",
+                scheme, PBKDF2_SHA256
+            ));
+        }
+
+        let iterations = iterations
+            .parse::<NonZeroU32>()
+            .map_err(|e| format!("invalid iterations in password hash: {}", e))?;
+        let salt =
+            hex::decode(salt).map_err(|e| format!("invalid salt in password hash: {}", e))?;
+        let hash =
+            hex::decode(hash).map_err(|e| format!("invalid hash in password hash: {}", e))?;
+
+        if hash.len() != SHA256_OUTPUT_LEN {
+            return Err(format!(
+                "invalid hash length in password hash: {} bytes, expect: {} bytes",
+                hash.len(),
+                SHA256_OUTPUT_LEN
+            ));
+        }
+
+        Ok(Self {
+            iterations,
+            salt,
+            hash,
+        })
+    }
+}
+
+/// A password that passed the PBKDF2 check, kept as a SHA-256 of the salt and the password.
+#[derive(Debug)]
+struct VerifiedPassword {
+    digest: Vec<u8>,
+    verified_at: Instant,
+}
+
+/// The password hash of each user, which a handshake password is verified against.
+///
+/// It is built from a string in form of `user1=<hash1>;user2=<hash2>`, see [`PasswordHash`].
+/// A user without a stored hash can not pass the check, unless the check is disabled.
+#[derive(Clone, Debug)]
+pub struct UserCredentials {
+    hashes: BTreeMap<String, PasswordHash>,
+    disabled: bool,
+
+    /// The last verified password of each user, accepted for [`VERIFIED_PASSWORD_TTL`].
+    ///
+    /// Only users with a stored hash are inserted, thus it is bounded by `hashes`.
+    verified: Arc<Mutex<BTreeMap<String, VerifiedPassword>>>,
+
+    /// Limits the PBKDF2 derivations, so that handshakes can not take up every blocking thread.
+    verifying: Arc<Semaphore>,
+}
+
+impl Default for UserCredentials {
+    fn default() -> Self {
+        Self {
+            hashes: BTreeMap::new(),
+            disabled: false,
+            verified: Arc::new(Mutex::new(BTreeMap::new())),
+            verifying: Arc::new(Semaphore::new(MAX_CONCURRENT_PASSWORD_VERIFY)),
+        }
+    }
+}
+
+impl UserCredentials {
+    /// Accept any password if `disabled` is true.
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Check the password a user presents in handshake.
+    ///
+    /// The PBKDF2 derivation is CPU-bound, thus it runs on a blocking thread,
+    /// in order not to stall other requests on the async runtime.
+    /// A password verified within [`VERIFIED_PASSWORD_TTL`] is accepted without the derivation,
+    /// since a client may handshake for every request.
+    pub async fn check(&self, username: &str, password: &str) -> Result<(), Status> {
+        if self.disabled {
+            return Ok(());
+        }
+
+        let invalid =
+            || Status::unauthenticated(format!("invalid credentials for user: {}", username));
+
+        let Some(hash) = self.hashes.get(username).cloned() else {
+            return Err(invalid());
+        };
+
+        let digest = Self::password_digest(&hash, password);
+        if self.is_recently_verified(username, &digest) {
+            return Ok(());
+        }
+
+        let _permit = self
+            .verifying
+            .acquire()
+            .await
+            .map_err(|e| Status::internal(format!("password verify is closed: {}", e)))?;
+
+        let password = password.to_string();
+        let verified = tokio::task::spawn_blocking(move || hash.verify(&password))
+            .await
+            .map_err(|e| Status::internal(format!("password verify task failed: {}", e)))?;
+
+        if !verified {
+            return Err(invalid());
+        }
+
+        let mut cache = self.verified.lock().unwrap();
+        cache.insert(username.to_string(), VerifiedPassword {
+            digest,
+            verified_at: Instant::now(),
+        });
+        Ok(())
+    }
+
+    fn password_digest(hash: &PasswordHash, password: &str) -> Vec<u8> {
+        let mut ctx = digest::Context::new(&digest::SHA256);
+        ctx.update(&hash.salt);
+        ctx.update(password.as_bytes());
+        ctx.finish().as_ref().to_vec()
+    }
+
+    /// Returns true if the password of the digest is verified within [`VERIFIED_PASSWORD_TTL`].
+    ///
+    /// The digest is compared in constant time, as `PasswordHash::verify()` does.
+    fn is_recently_verified(&self, username: &str, digest: &[u8]) -> bool {
+        let cache = self.verified.lock().unwrap();
+        let Some(verified) = cache.get(username) else {
+            return false;
+        };
+
+        verified.verified_at.elapsed() < VERIFIED_PASSWORD_TTL
+            && constant_time::verify_slices_are_equal(&verified.digest, digest).is_ok()
+    }
+}
+
+impl FromStr for UserCredentials {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut hashes = BTreeMap::new();
+
+        for entry in s.split(';').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let (username, hash) = entry.split_once('=').ok_or_else(|| {
+                "invalid credential entry, expect: <user>=<password hash>".to_string()
+            })?;
+
+            let username = username.trim();
+            if username.is_empty() {
+                return Err("empty user in credential entry".to_string());
+            }
+
+            let hash = hash
+                .trim()
+                .parse::<PasswordHash>()
+                .map_err(|e| format!("{} of user: {}", e, username))?;
+            hashes.insert(username.to_string(), hash);
+        }
+
+        Ok(Self {
+            hashes,
+            ..Self::default()
+        })
+    }
+}
+
+/// The user of each client certificate, which a handshake with mTLS is identified by.
+///
+/// It is built from a string in form of `user1=<fingerprint1>;user2=<fingerprint2>`,
+/// where a fingerprint is the hex SHA-256 of a DER encoded certificate.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CertUsers {
+    users: BTreeMap<Vec<u8>, String>,
+}
+
+impl CertUsers {
+    /// The SHA-256 fingerprint of a DER encoded certificate.
+    pub fn fingerprint(der: &[u8]) -> Vec<u8> {
+        digest::digest(&digest::SHA256, der).as_ref().to_vec()
+    }
+
+    /// Return the user of the first known certificate a client presents in TLS handshake.
+    pub fn user_of<'a>(&self, certs: impl IntoIterator<Item = &'a [u8]>) -> Option<&str> {
+        certs
+            .into_iter()
+            .find_map(|der| self.users.get(&Self::fingerprint(der)))
+            .map(|x| x.as_str())
+    }
+}
+
+impl FromStr for CertUsers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut users = BTreeMap::new();
+
+        for entry in s.split(';').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let (username, fingerprint) = entry.split_once('=').ok_or_else(|| {
+                "invalid cert user entry, expect: <user>=<certificate fingerprint>".to_string()
+            })?;
+
+            let username = username.trim();
+            if username.is_empty() {
+                return Err("empty user in cert user entry".to_string());
+            }
+
+            let fingerprint = hex::decode(fingerprint.trim()).map_err(|e| {
+                format!(
+                    "invalid certificate fingerprint of user: {}: {}",
+                    username, e
+                )
+            })?;
+            if fingerprint.len() != SHA256_OUTPUT_LEN {
+                return Err(format!(
+                    "invalid certificate fingerprint of user: {}: {} bytes, expect: {} bytes",
+                    username,
+                    fingerprint.len(),
+                    SHA256_OUTPUT_LEN
+                ));
+            }
+
+            users.insert(fingerprint, username.to_string());
+        }
+
+        Ok(Self { users })
+    }
+}
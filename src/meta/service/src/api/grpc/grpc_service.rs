@@ -12,32 +12,67 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::future::Future;
 use std::io;
 use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_arrow::arrow_format::flight::data::BasicAuth;
+use common_base::base::tokio;
 use common_base::base::tokio::sync::mpsc;
+use common_base::base::tokio::sync::mpsc::error::SendTimeoutError;
 use common_base::base::tokio::time::Instant;
 use common_grpc::GrpcClaim;
 use common_grpc::GrpcToken;
 use common_meta_client::MetaGrpcReadReq;
 use common_meta_client::MetaGrpcReq;
 use common_meta_kvapi::kvapi::KVApi;
+use common_meta_raft_store::key_spaces::RaftStoreEntry;
+use common_meta_sled_store::openraft::metrics::WaitError;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::cluster_version::MAX_CLUSTER_VERSION;
+use common_meta_types::protobuf::handshake_request::AuthMethod;
 use common_meta_types::protobuf::meta_service_server::MetaService;
+use common_meta_types::protobuf::CancelStreamReply;
+use common_meta_types::protobuf::CancelStreamRequest;
 use common_meta_types::protobuf::ClientInfo;
 use common_meta_types::protobuf::ClusterStatus;
+use common_meta_types::protobuf::ClusterVersionReply;
+use common_meta_types::protobuf::CountPrefixReply;
+use common_meta_types::protobuf::CountPrefixRequest;
 use common_meta_types::protobuf::Empty;
 use common_meta_types::protobuf::ExportedChunk;
+use common_meta_types::protobuf::FailedAppliesReply;
 use common_meta_types::protobuf::HandshakeRequest;
 use common_meta_types::protobuf::HandshakeResponse;
+use common_meta_types::protobuf::ImportReply;
+use common_meta_types::protobuf::IncrementReply;
+use common_meta_types::protobuf::IncrementRequest;
 use common_meta_types::protobuf::MemberListReply;
 use common_meta_types::protobuf::MemberListRequest;
 use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::protobuf::ReadLogReply;
+use common_meta_types::protobuf::ReadLogRequest;
+use common_meta_types::protobuf::SetClusterVersionRequest;
+use common_meta_types::protobuf::SetReadOnlyRequest;
+use common_meta_types::protobuf::SetSchemaVersionReply;
+use common_meta_types::protobuf::SetSchemaVersionRequest;
 use common_meta_types::protobuf::StreamItem;
+use common_meta_types::protobuf::TransferLeaderRequest;
+use common_meta_types::protobuf::WaitAppliedReply;
+use common_meta_types::protobuf::WaitAppliedRequest;
 use common_meta_types::protobuf::WatchRequest;
 use common_meta_types::protobuf::WatchResponse;
+use common_meta_types::txn_op;
+use common_meta_types::Features;
+use common_meta_types::IncrementKV;
+use common_meta_types::RaftTxId;
+use common_meta_types::TxnOp;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_metrics::count::Count;
@@ -54,15 +89,27 @@ use tokio_stream;
 use tokio_stream::Stream;
 use tonic::codegen::BoxStream;
 use tonic::metadata::MetadataMap;
+use tonic::metadata::MetadataValue;
+use tonic::transport::Certificate;
 use tonic::transport::NamedService;
 use tonic::Request;
 use tonic::Response;
 use tonic::Status;
 use tonic::Streaming;
 
+use crate::api::grpc::credentials::CertUsers;
+use crate::api::grpc::credentials::UserCredentials;
+use crate::api::grpc::key_acl::KeyAcl;
+use crate::api::grpc::reserved_key;
+use crate::api::grpc::schema_version::SchemaVersions;
+use crate::api::grpc::write_log_sampler::WriteLogSampler;
 use crate::grpc_helper::GrpcHelper;
+use crate::grpc_helper::AUTH_TOKEN_KEY;
+use crate::grpc_helper::DEFAULT_FORWARD_TIMEOUT;
+use crate::message::CountPrefixReq;
 use crate::message::ForwardRequest;
 use crate::meta_service::MetaNode;
+use crate::meta_service::WritePriority;
 use crate::metrics::network_metrics;
 use crate::metrics::RequestInFlight;
 use crate::version::from_digit_ver;
@@ -71,45 +118,570 @@ use crate::version::METASRV_SEMVER;
 use crate::version::MIN_METACLI_SEMVER;
 use crate::watcher::WatchStream;
 
+/// The request metadata key of an optional idempotency key for a write, in form of `txid-<client>-<serial>`.
+///
+/// A retried write with the same key is applied only once, and gets the reply of the first one.
+pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// The request metadata key to validate a write without committing it, if its value is `true`.
+///
+/// The reply is what the write would return if it were applied at once.
+pub const DRY_RUN_KEY: &str = "dry-run";
+
+/// The request metadata key of the schema version of the keys a write changes.
+///
+/// A write to a key whose namespace has a minimum schema version is rejected with `failed_precondition`
+/// if it does not carry a version, or carries an older one, see [`SchemaVersions`].
+pub const SCHEMA_VERSION_KEY: &str = "schema-version";
+
+/// The request metadata key of the [`ReadConsistency`] a read requires, `local` or `leader`.
+///
+/// Without it, a read replica serves a read locally and others forward it to the leader.
+pub const READ_CONSISTENCY_KEY: &str = "read-consistency";
+
+/// The request metadata key of the [`WritePriority`] of a write, `low`, `normal` or `high`.
+///
+/// When too many writes are being proposed, the waiting ones are proposed in the order of their priority.
+/// Without it, a write is `normal`.
+pub const WRITE_PRIORITY_KEY: &str = "write-priority";
+
+/// How consistent a read must be, i.e., whether it may be served by the local state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadConsistency {
+    /// Read the local state machine, which may be stale.
+    ///
+    /// It is rejected with `unavailable` if the local state machine is catching up.
+    Local,
+
+    /// Read through the leader.
+    Leader,
+}
+
+impl FromStr for ReadConsistency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Self::Local),
+            "leader" => Ok(Self::Leader),
+            _ => Err(format!("expect: local or leader, got: {}", s)),
+        }
+    }
+}
+
+/// The response metadata key of the index of the raft log a write is applied at.
+///
+/// Call `wait_applied` with it on another node before reading there, to read the write back.
+/// It is absent if the write is forwarded to the leader before the cluster version is raised to
+/// [`CLUSTER_VERSION_V1`], since a leader of an older build does not reply with it.
+pub const LOG_INDEX_KEY: &str = "log-index";
+
+/// The max number of raft log entries returned by one `read_log` call.
+pub const MAX_READ_LOG_ENTRIES: u64 = 1024;
+
+/// The max total size in bytes of the raft log entries returned by one `read_log` call.
+///
+/// It keeps a reply below the default 4MB message size limit of gRPC.
+/// At least one entry is returned, even if it is larger.
+pub const MAX_READ_LOG_BYTES: usize = 2 * 1024 * 1024;
+
+/// The max number of operations in one txn proposed by `import`.
+///
+/// A larger chunk is split into several txns, to keep a single raft log entry small.
+pub const MAX_IMPORT_TXN_OPS: usize = 256;
+
+/// The max number of items a streaming response buffers on the server side.
+pub const STREAM_BUFFER_SIZE: usize = 4;
+
+/// The max time a producer waits for a stalled client to consume an item,
+/// before the stream is considered broken and is dropped.
+pub const STREAM_SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The sending half of a bounded streaming response.
+///
+/// The producer is blocked by `send()` when the buffer is full,
+/// and it gets an error if the client does not make room within the timeout.
+#[derive(Debug)]
+pub struct StreamSender<T> {
+    tx: mpsc::Sender<Result<T, Status>>,
+    timeout: Duration,
+}
+
+impl<T> Clone for StreamSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+impl<T> StreamSender<T> {
+    pub async fn send(
+        &self,
+        item: Result<T, Status>,
+    ) -> Result<(), SendTimeoutError<Result<T, Status>>> {
+        self.tx.send_timeout(item, self.timeout).await
+    }
+}
+
+/// Create a bounded channel for building a streaming response.
+///
+/// At most `buffer` items are held by the server.
+/// The receiving half can be used as a gRPC stream with `tokio_stream::wrappers::ReceiverStream`,
+/// or be wrapped by a stream that cleans up when the client disconnects, such as [`WatchStream`].
+pub fn bounded_stream<T>(
+    buffer: usize,
+    timeout: Duration,
+) -> (StreamSender<T>, mpsc::Receiver<Result<T, Status>>) {
+    let (tx, rx) = mpsc::channel(buffer);
+    (StreamSender { tx, timeout }, rx)
+}
+
 pub struct MetaServiceImpl {
     token: GrpcToken,
+    /// Whether `token` is keyed by the configured `token_secret`, which a JWT handshake requires.
+    jwt_enabled: bool,
+    key_acl: KeyAcl,
+    /// The password hash a user's handshake password is verified against.
+    credentials: UserCredentials,
+    /// The user of each client certificate that handshakes with mTLS.
+    cert_users: CertUsers,
+    /// Reject handshake of the built-in root user.
+    root_disabled: bool,
+    /// The max time to handle a request that may be forwarded to the leader,
+    /// if the client does not specify a deadline.
+    forward_timeout: Duration,
+    /// Decides which writes are logged with their keys and results.
+    write_log_sampler: WriteLogSampler,
+    /// Reject writes through this node, set by the `set_read_only` admin API.
+    read_only: AtomicBool,
+    /// The max time a write waits for its log to be applied, in addition to the request timeout.
+    apply_timeout: Option<Duration>,
     pub(crate) meta_node: Arc<MetaNode>,
 }
 
 impl MetaServiceImpl {
     pub fn create(meta_node: Arc<MetaNode>) -> Self {
         Self {
-            token: GrpcToken::create(),
+            token: meta_node.grpc_token.clone(),
+            jwt_enabled: !meta_node.sto.config.token_secret.is_empty(),
+            key_acl: KeyAcl::default(),
+            credentials: UserCredentials::default(),
+            cert_users: CertUsers::default(),
+            root_disabled: false,
+            forward_timeout: DEFAULT_FORWARD_TIMEOUT,
+            write_log_sampler: WriteLogSampler::default(),
+            read_only: AtomicBool::new(false),
+            apply_timeout: None,
             meta_node,
         }
     }
 
+    pub fn with_forward_timeout(mut self, timeout: Duration) -> Self {
+        self.forward_timeout = timeout;
+        self
+    }
+
+    /// Limit the keys non-root users can access.
+    pub fn with_key_acl(mut self, key_acl: KeyAcl) -> Self {
+        self.key_acl = key_acl;
+        self
+    }
+
+    /// Verify the handshake password of the users that have a stored password hash.
+    pub fn with_credentials(mut self, credentials: UserCredentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Identify the clients that handshake with mTLS by their certificates.
+    pub fn with_cert_users(mut self, cert_users: CertUsers) -> Self {
+        self.cert_users = cert_users;
+        self
+    }
+
+    /// Reject handshake of root, so that only the users in the key acl can access.
+    pub fn with_root_disabled(mut self, disabled: bool) -> Self {
+        self.root_disabled = disabled;
+        self
+    }
+
+    /// Log 1 in every `rate` writes with the keys and the result, but not the values.
+    ///
+    /// 0 disables the sampled write log.
+    pub fn with_write_log_sample_rate(mut self, rate: u64) -> Self {
+        self.write_log_sampler = WriteLogSampler::new(rate);
+        self
+    }
+
+    /// Fail a write with `deadline_exceeded` if its log is not applied in `timeout`.
+    ///
+    /// The log may still be applied after the timeout.
+    pub fn with_apply_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.apply_timeout = timeout;
+        self
+    }
+
     fn check_token(&self, metadata: &MetadataMap) -> Result<GrpcClaim, Status> {
-        let token = metadata
-            .get_bin("auth-token-bin")
-            .and_then(|v| v.to_bytes().ok())
-            .and_then(|b| String::from_utf8(b.to_vec()).ok())
-            .ok_or_else(|| Status::unauthenticated("Error auth-token-bin is empty"))?;
+        GrpcHelper::verify_token(&self.token, metadata)
+    }
 
-        let claim = self.token.try_verify_token(token.clone()).map_err(|e| {
-            Status::unauthenticated(format!("token verify failed: {}, {}", token, e))
+    /// Return an error if a user is not allowed to handshake.
+    fn check_user(&self, username: &str) -> Result<(), Status> {
+        if self.root_disabled && username == KeyAcl::ROOT {
+            return Err(Status::unauthenticated("user root is disabled"));
+        }
+
+        if !self.key_acl.has_user(username) {
+            return Err(Status::unauthenticated(format!(
+                "Unknown user: {}",
+                username
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verify a handshake payload of `BasicAuth` and return the user.
+    async fn verify_basic_auth(&self, payload: &[u8]) -> Result<String, Status> {
+        let auth = BasicAuth::decode(payload).map_err(|e| Status::internal(e.to_string()))?;
+
+        self.check_user(&auth.username)?;
+        self.credentials
+            .check(&auth.username, &auth.password)
+            .await?;
+
+        Ok(auth.username)
+    }
+
+    /// Verify a handshake payload of a token signed with the configured `token_secret`
+    /// and return the token and the claim in it.
+    ///
+    /// It lets a client connect to another node without sending the password again.
+    /// The token is not re-issued: it keeps its expiration and the features it was issued with.
+    fn verify_jwt(&self, payload: &[u8]) -> Result<(String, GrpcClaim), Status> {
+        if !self.jwt_enabled {
+            return Err(Status::unauthenticated(
+                "jwt handshake requires raft token_secret to be configured",
+            ));
+        }
+
+        let token = std::str::from_utf8(payload)
+            .map_err(|e| Status::invalid_argument(format!("invalid jwt in handshake: {}", e)))?;
+
+        let claim = self
+            .token
+            .try_verify_token(token.to_string())
+            .map_err(|e| Status::unauthenticated(format!("jwt verify failed: {}", e)))?;
+
+        self.check_user(&claim.username)?;
+
+        Ok((token.to_string(), claim))
+    }
+
+    /// Issue a token for an authenticated user with the negotiated features.
+    fn issue_token(&self, username: String, features: Features) -> Result<String, Status> {
+        let claim = GrpcClaim {
+            username,
+            features: features.bits(),
+        };
+        self.token
+            .try_create_token(claim)
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+
+    /// Return the user of the client certificate of the TLS connection.
+    fn verify_mtls(&self, peer_certs: Option<&[Certificate]>) -> Result<String, Status> {
+        let certs = peer_certs.ok_or_else(|| {
+            Status::unauthenticated("mTLS handshake requires a client certificate")
         })?;
-        Ok(claim)
+
+        let username = self
+            .cert_users
+            .user_of(certs.iter().map(|c| c.get_ref()))
+            .ok_or_else(|| Status::unauthenticated("unknown client certificate"))?
+            .to_string();
+
+        self.check_user(&username)?;
+
+        Ok(username)
+    }
+
+    /// Wait for a write to be applied, at most `apply_timeout` if it is set.
+    async fn wait_write_applied<T>(&self, f: impl Future<Output = T>) -> Result<T, Status> {
+        let Some(timeout) = self.apply_timeout else {
+            return Ok(f.await);
+        };
+
+        tokio::time::timeout(timeout, f).await.map_err(|_elapsed| {
+            Status::deadline_exceeded(format!("write is not applied in {:?}", timeout))
+        })
+    }
+
+    /// Return an error if this node is in read-only mode or is a read replica.
+    fn check_writable(&self) -> Result<(), Status> {
+        if self.read_only.load(Ordering::Relaxed) {
+            return Err(Status::failed_precondition("cluster is read-only"));
+        }
+        if self.meta_node.is_read_replica() {
+            let leader = self.meta_node.raft.metrics().borrow().current_leader;
+            return Err(Status::failed_precondition(format!(
+                "not leader: node {} is a read replica, leader: {:?}",
+                self.meta_node.sto.id, leader
+            )));
+        }
+        Ok(())
+    }
+
+    /// Apply imported put operations in one txn and return the number of them.
+    async fn import_ops(&self, ops: Vec<TxnOp>) -> Result<u64, Status> {
+        if ops.is_empty() {
+            return Ok(0);
+        }
+
+        let n = ops.len() as u64;
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: ops,
+            else_then: vec![],
+        };
+
+        self.meta_node
+            .transaction(txn)
+            .await
+            .map_err(GrpcHelper::internal_err)?;
+
+        Ok(n)
+    }
+
+    /// Whether a txn may change any key, i.e., it has an operation other than get.
+    fn txn_writes(txn: &TxnRequest) -> bool {
+        txn.if_then
+            .iter()
+            .chain(txn.else_then.iter())
+            .any(|op| !matches!(op.request, Some(txn_op::Request::Get(_)) | None))
+    }
+
+    fn get_txid(metadata: &MetadataMap) -> Result<Option<RaftTxId>, Status> {
+        let Some(v) = metadata.get(IDEMPOTENCY_KEY) else {
+            return Ok(None);
+        };
+
+        let txid = v
+            .to_str()
+            .map_err(|e| Status::invalid_argument(format!("invalid {}: {}", IDEMPOTENCY_KEY, e)))?
+            .parse::<RaftTxId>()
+            .map_err(Status::invalid_argument)?;
+
+        Ok(Some(txid))
+    }
+
+    /// Return an error if a request using `feature` would be forwarded to a leader that may not understand it.
+    ///
+    /// A leader handles such a request locally, while forwarding it requires [`CLUSTER_VERSION_V1`].
+    async fn check_forwardable(&self, feature: &str) -> Result<(), Status> {
+        if self.meta_node.assume_leader().await.is_ok() {
+            return Ok(());
+        }
+
+        let ver = self.meta_node.cluster_version().await;
+        if ver < CLUSTER_VERSION_V1 {
+            return Err(Status::failed_precondition(format!(
+                "{} on a follower requires cluster version >= {}, current: {}",
+                feature, CLUSTER_VERSION_V1, ver
+            )));
+        }
+        Ok(())
+    }
+
+    /// Scope the txid sent by a client to the authenticated user,
+    /// so that a client can never get a response cached for a write of another user.
+    ///
+    /// De-duplicating writes by txid requires [`CLUSTER_VERSION_V1`]:
+    /// a node of an older build can not apply a raft log with a txid.
+    async fn user_txid(&self, txid: RaftTxId, username: &str) -> Result<RaftTxId, Status> {
+        let ver = self.meta_node.cluster_version().await;
+        if ver < CLUSTER_VERSION_V1 {
+            return Err(Status::failed_precondition(format!(
+                "{} requires cluster version >= {}, current: {}",
+                IDEMPOTENCY_KEY, CLUSTER_VERSION_V1, ver
+            )));
+        }
+
+        // The length prefix keeps `(user, client)` pairs distinct even if a name contains `:`.
+        let client = format!("{}:{}:{}", username.len(), username, txid.client);
+        Ok(RaftTxId::new(&client, txid.serial))
+    }
+
+    fn is_dry_run(metadata: &MetadataMap) -> Result<bool, Status> {
+        let Some(v) = metadata.get(DRY_RUN_KEY) else {
+            return Ok(false);
+        };
+
+        v.to_str()
+            .map_err(|e| Status::invalid_argument(format!("invalid {}: {}", DRY_RUN_KEY, e)))?
+            .parse::<bool>()
+            .map_err(|e| Status::invalid_argument(format!("invalid {}: {}", DRY_RUN_KEY, e)))
+    }
+
+    fn get_schema_version(metadata: &MetadataMap) -> Result<Option<u64>, Status> {
+        let Some(v) = metadata.get(SCHEMA_VERSION_KEY) else {
+            return Ok(None);
+        };
+
+        let ver = v
+            .to_str()
+            .map_err(|e| {
+                Status::invalid_argument(format!("invalid {}: {}", SCHEMA_VERSION_KEY, e))
+            })?
+            .parse::<u64>()
+            .map_err(|e| {
+                Status::invalid_argument(format!("invalid {}: {}", SCHEMA_VERSION_KEY, e))
+            })?;
+
+        Ok(Some(ver))
+    }
+
+    fn get_read_consistency(metadata: &MetadataMap) -> Result<Option<ReadConsistency>, Status> {
+        let Some(v) = metadata.get(READ_CONSISTENCY_KEY) else {
+            return Ok(None);
+        };
+
+        let consistency = v
+            .to_str()
+            .map_err(|e| {
+                Status::invalid_argument(format!("invalid {}: {}", READ_CONSISTENCY_KEY, e))
+            })?
+            .parse::<ReadConsistency>()
+            .map_err(|e| {
+                Status::invalid_argument(format!("invalid {}: {}", READ_CONSISTENCY_KEY, e))
+            })?;
+
+        Ok(Some(consistency))
+    }
+
+    fn get_write_priority(metadata: &MetadataMap) -> Result<WritePriority, Status> {
+        let Some(v) = metadata.get(WRITE_PRIORITY_KEY) else {
+            return Ok(WritePriority::default());
+        };
+
+        let priority = v
+            .to_str()
+            .map_err(|e| {
+                Status::invalid_argument(format!("invalid {}: {}", WRITE_PRIORITY_KEY, e))
+            })?
+            .parse::<WritePriority>()
+            .map_err(|e| {
+                Status::invalid_argument(format!("invalid {}: {}", WRITE_PRIORITY_KEY, e))
+            })?;
+
+        Ok(priority)
+    }
+
+    /// The minimum schema version of each key namespace, in the local state machine.
+    async fn schema_versions(&self) -> Result<SchemaVersions, Status> {
+        let versions = self
+            .meta_node
+            .schema_versions()
+            .await
+            .map_err(GrpcHelper::internal_err)?;
+        Ok(SchemaVersions::new(versions))
+    }
+
+    /// The keys a txn touches, in order of conditions and operations, for logging.
+    fn txn_keys(txn: &TxnRequest) -> Vec<String> {
+        let conds = txn.condition.iter().map(|c| c.key.as_str());
+
+        let ops = txn
+            .if_then
+            .iter()
+            .chain(txn.else_then.iter())
+            .filter_map(|op| match &op.request {
+                Some(txn_op::Request::Get(r)) => Some(r.key.as_str()),
+                Some(txn_op::Request::Put(r)) => Some(r.key.as_str()),
+                Some(txn_op::Request::Delete(r)) => Some(r.key.as_str()),
+                Some(txn_op::Request::DeleteByPrefix(r)) => Some(r.prefix.as_str()),
+                None => None,
+            });
+
+        conds.chain(ops).map(|k| k.to_string()).collect()
     }
 
     #[minitrace::trace]
-    async fn handle_kv_api(&self, request: Request<RaftRequest>) -> Result<RaftReply, Status> {
+    async fn handle_kv_api(
+        &self,
+        request: Request<RaftRequest>,
+        claim: &GrpcClaim,
+    ) -> Result<(RaftReply, Option<u64>), Status> {
+        let txid = match Self::get_txid(request.metadata())? {
+            None => None,
+            Some(txid) => Some(self.user_txid(txid, &claim.username).await?),
+        };
+        let dry_run = Self::is_dry_run(request.metadata())?;
+        let schema_version = Self::get_schema_version(request.metadata())?;
+        let priority = Self::get_write_priority(request.metadata())?;
+
         let req: MetaGrpcReq = request.try_into()?;
-        info!("{}: Received MetaGrpcReq: {:?}", func_name!(), req);
+
+        let acl = &self.key_acl;
+        match &req {
+            MetaGrpcReq::UpsertKV(a) => acl.check(&claim.username, &a.key)?,
+            MetaGrpcReq::GetKV(a) => acl.check(&claim.username, &a.key)?,
+            MetaGrpcReq::MGetKV(a) => acl.check_keys(&claim.username, &a.keys)?,
+            MetaGrpcReq::ListKV(a) => acl.check(&claim.username, &a.prefix)?,
+        }
+
+        if let MetaGrpcReq::UpsertKV(a) = &req {
+            reserved_key::check_key(&a.key)?;
+            self.schema_versions()
+                .await?
+                .check(schema_version, &a.key)?;
+        }
+
+        // A dry run is rejected the same way as a write, to predict the result faithfully.
+        if matches!(req, MetaGrpcReq::UpsertKV(_)) {
+            self.check_writable()?;
+        }
+
+        if dry_run {
+            self.check_forwardable("dry run").await?;
+        }
+
+        info!(
+            "{}: Received MetaGrpcReq: {:?}, txid: {:?}, dry_run: {}",
+            func_name!(),
+            req,
+            txid,
+            dry_run
+        );
 
         let t0 = Instant::now();
 
         let m = &self.meta_node;
+        let mut log_index = None;
         let reply = match &req {
-            MetaGrpcReq::UpsertKV(a) => {
-                let res = m.upsert_kv(a.clone()).await;
+            MetaGrpcReq::UpsertKV(a) if dry_run => {
+                let res = m.dry_run_upsert_kv(a.clone()).await;
                 RaftReply::from(res)
             }
+            MetaGrpcReq::UpsertKV(a) => {
+                let res = self
+                    .wait_write_applied(m.upsert_kv_with_txid(a.clone(), txid, priority))
+                    .await?;
+                log_index = res.as_ref().ok().and_then(|(_reply, index)| *index);
+                let reply = RaftReply::from(res.map(|(reply, _index)| reply));
+
+                if self.write_log_sampler.sample() {
+                    info!(
+                        "sampled write: upsert_kv key: {}, user: {}, ok: {}",
+                        a.key,
+                        claim.username,
+                        reply.error.is_empty()
+                    );
+                }
+
+                reply
+            }
             MetaGrpcReq::GetKV(a) => {
                 let res = m.get_kv(&a.key).await;
                 RaftReply::from(res)
@@ -128,16 +700,30 @@ impl MetaServiceImpl {
 
         network_metrics::incr_request_result(reply.error.is_empty());
 
-        Ok(reply)
+        if Features::from_bits(claim.features).contains(Features::COMPRESSION) {
+            let compressed = reply.compress().map_err(GrpcHelper::internal_err)?;
+            return Ok((compressed, log_index));
+        }
+
+        Ok((reply, log_index))
     }
 
     #[minitrace::trace]
     async fn handle_kv_read_v1(
         &self,
         request: Request<RaftRequest>,
+        claim: &GrpcClaim,
     ) -> Result<BoxStream<StreamItem>, Status> {
+        let consistency = Self::get_read_consistency(request.metadata())?;
         let req: MetaGrpcReadReq = GrpcHelper::parse_req(request)?;
 
+        let acl = &self.key_acl;
+        match &req {
+            MetaGrpcReadReq::GetKV(a) => acl.check(&claim.username, &a.key)?,
+            MetaGrpcReadReq::MGetKV(a) => acl.check_keys(&claim.username, &a.keys)?,
+            MetaGrpcReadReq::ListKV(a) => acl.check(&claim.username, &a.prefix)?,
+        }
+
         info!("{}: Received ReadRequest: {:?}", func_name!(), req);
 
         let req = ForwardRequest {
@@ -147,11 +733,33 @@ impl MetaServiceImpl {
 
         let t0 = Instant::now();
 
-        let res = self
-            .meta_node
-            .handle_forwardable_request::<MetaGrpcReadReq>(req.clone())
-            .await
-            .map_err(GrpcHelper::internal_err);
+        // A local read must not see the incomplete data of a catching-up state machine:
+        // it is rejected if the read requires to be local, or forwarded to the leader otherwise.
+        let local = match consistency {
+            Some(ReadConsistency::Leader) => false,
+            Some(ReadConsistency::Local) => {
+                if let Some(reason) = self.meta_node.catching_up() {
+                    return Err(Status::unavailable(format!(
+                        "node {} is catching up: {}",
+                        self.meta_node.sto.id, reason
+                    )));
+                }
+                true
+            }
+            None => self.meta_node.is_read_replica() && self.meta_node.catching_up().is_none(),
+        };
+
+        let res = if local {
+            self.meta_node
+                .local_read(req.body.clone())
+                .await
+                .map_err(GrpcHelper::internal_err)
+        } else {
+            self.meta_node
+                .handle_forwardable_request::<MetaGrpcReadReq>(req.clone())
+                .await
+                .map_err(GrpcHelper::internal_err)
+        };
 
         let elapsed = t0.elapsed();
         info!("Handled(elapsed: {:?}) ReadRequest: {:?}", elapsed, req);
@@ -161,15 +769,41 @@ impl MetaServiceImpl {
     }
 
     #[minitrace::trace]
-    async fn handle_txn(&self, request: Request<TxnRequest>) -> Result<TxnReply, Status> {
+    async fn handle_txn(
+        &self,
+        request: Request<TxnRequest>,
+        claim: &GrpcClaim,
+    ) -> Result<(TxnReply, Option<u64>), Status> {
+        let schema_version = Self::get_schema_version(request.metadata())?;
+        let priority = Self::get_write_priority(request.metadata())?;
         let request = request.into_inner();
 
+        self.key_acl.check_txn(&claim.username, &request)?;
+        reserved_key::check_txn(&request)?;
+        self.schema_versions()
+            .await?
+            .check_txn(schema_version, &request)?;
+
+        if Self::txn_writes(&request) {
+            self.check_writable()?;
+        }
+
         info!("{}: Receive txn_request: {}", func_name!(), request);
 
-        let ret = self.meta_node.transaction(request).await;
+        let sampled_keys = if self.write_log_sampler.sample() {
+            Some(Self::txn_keys(&request))
+        } else {
+            None
+        };
+
+        let ret = self
+            .wait_write_applied(self.meta_node.transaction_with_log_index(request, priority))
+            .await?;
+
+        let log_index = ret.as_ref().ok().and_then(|(_resp, index)| *index);
 
         let body = match ret {
-            Ok(resp) => TxnReply {
+            Ok((resp, _index)) => TxnReply {
                 success: resp.success,
                 error: "".to_string(),
                 responses: resp.responses,
@@ -181,9 +815,30 @@ impl MetaServiceImpl {
             },
         };
 
+        if let Some(keys) = sampled_keys {
+            info!(
+                "sampled write: txn keys: {:?}, user: {}, ok: {}, success: {}",
+                keys,
+                claim.username,
+                body.error.is_empty(),
+                body.success
+            );
+        }
+
         network_metrics::incr_request_result(body.error.is_empty());
 
-        Ok(body)
+        Ok((body, log_index))
+    }
+
+    /// Build a response, with the raft log index of the write in the metadata if there is one.
+    fn response_with_log_index<T>(message: T, log_index: Option<u64>) -> Response<T> {
+        let mut response = Response::new(message);
+        if let Some(log_index) = log_index {
+            response
+                .metadata_mut()
+                .insert(LOG_INDEX_KEY, MetadataValue::from(log_index));
+        }
+        response
     }
 }
 
@@ -201,18 +856,29 @@ impl MetaService for MetaServiceImpl {
         &self,
         request: Request<Streaming<HandshakeRequest>>,
     ) -> Result<Response<Self::HandshakeStream>, Status> {
+        let peer_certs = request.peer_certs();
+
         let req = request
             .into_inner()
             .next()
             .await
             .ok_or_else(|| Status::internal("Error request next is None"))??;
 
+        let auth_method = req.auth_method();
         let HandshakeRequest {
             protocol_version,
             payload,
+            features,
+            ..
         } = req;
 
-        debug!("handle handshake request, client ver: {}", protocol_version);
+        // A legacy client does not send features and gets none of them.
+        let features = Features::negotiate(features);
+
+        debug!(
+            "handle handshake request, client ver: {}, auth method: {:?}, negotiated features: {:?}",
+            protocol_version, auth_method, features
+        );
 
         let min_compatible = to_digit_ver(&MIN_METACLI_SEMVER);
 
@@ -225,46 +891,48 @@ impl MetaService for MetaServiceImpl {
             )));
         }
 
-        let auth = BasicAuth::decode(&*payload).map_err(|e| Status::internal(e.to_string()))?;
+        let (token, features) = match auth_method {
+            AuthMethod::BasicAuth => {
+                let username = self.verify_basic_auth(&payload).await?;
+                (self.issue_token(username, features)?, features.bits())
+            }
+            AuthMethod::Jwt => {
+                let (token, claim) = self.verify_jwt(&payload)?;
+                (token, claim.features)
+            }
+            AuthMethod::Mtls => {
+                let username = self.verify_mtls(peer_certs.as_ref().map(|x| x.as_slice()))?;
+                (self.issue_token(username, features)?, features.bits())
+            }
+        };
 
-        let user = "root";
-        if auth.username == user {
-            let claim = GrpcClaim {
-                username: user.to_string(),
-            };
-            let token = self
-                .token
-                .try_create_token(claim)
-                .map_err(|e| Status::internal(e.to_string()))?;
-
-            let resp = HandshakeResponse {
-                protocol_version: to_digit_ver(&METASRV_SEMVER),
-                payload: token.into_bytes(),
-            };
-            let output = futures::stream::once(async { Ok(resp) });
+        let resp = HandshakeResponse {
+            protocol_version: to_digit_ver(&METASRV_SEMVER),
+            payload: token.into_bytes(),
+            features,
+        };
+        let output = futures::stream::once(async { Ok(resp) });
 
-            debug!("handshake OK");
-            Ok(Response::new(Box::pin(output)))
-        } else {
-            Err(Status::unauthenticated(format!(
-                "Unknown user: {}",
-                auth.username
-            )))
-        }
+        debug!("handshake OK");
+        Ok(Response::new(Box::pin(output)))
     }
 
     async fn kv_api(&self, request: Request<RaftRequest>) -> Result<Response<RaftReply>, Status> {
-        self.check_token(request.metadata())?;
+        let claim = self.check_token(request.metadata())?;
 
         network_metrics::incr_recv_bytes(request.get_ref().encoded_len() as u64);
         let _guard = RequestInFlight::guard();
 
         let root = common_tracing::start_trace_for_remote_request(full_name!(), &request);
-        let reply = self.handle_kv_api(request).in_span(root).await?;
+        let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+        let (reply, log_index) =
+            GrpcHelper::with_timeout(timeout, self.handle_kv_api(request, &claim))
+                .in_span(root)
+                .await?;
 
         network_metrics::incr_sent_bytes(reply.encoded_len() as u64);
 
-        Ok(Response::new(reply))
+        Ok(Self::response_with_log_index(reply, log_index))
     }
 
     type KvReadV1Stream = BoxStream<StreamItem>;
@@ -273,12 +941,15 @@ impl MetaService for MetaServiceImpl {
         &self,
         request: Request<RaftRequest>,
     ) -> Result<Response<Self::KvReadV1Stream>, Status> {
-        self.check_token(request.metadata())?;
+        let claim = self.check_token(request.metadata())?;
 
         network_metrics::incr_recv_bytes(request.get_ref().encoded_len() as u64);
         let root = common_tracing::start_trace_for_remote_request(full_name!(), &request);
 
-        let strm = self.handle_kv_read_v1(request).in_span(root).await?;
+        let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+        let strm = GrpcHelper::with_timeout(timeout, self.handle_kv_read_v1(request, &claim))
+            .in_span(root)
+            .await?;
 
         Ok(Response::new(strm))
     }
@@ -287,17 +958,21 @@ impl MetaService for MetaServiceImpl {
         &self,
         request: Request<TxnRequest>,
     ) -> Result<Response<TxnReply>, Status> {
-        self.check_token(request.metadata())?;
+        let claim = self.check_token(request.metadata())?;
 
         network_metrics::incr_recv_bytes(request.get_ref().encoded_len() as u64);
         let _guard = RequestInFlight::guard();
 
         let root = common_tracing::start_trace_for_remote_request(full_name!(), &request);
-        let reply = self.handle_txn(request).in_span(root).await?;
+        let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+        let (reply, log_index) =
+            GrpcHelper::with_timeout(timeout, self.handle_txn(request, &claim))
+                .in_span(root)
+                .await?;
 
         network_metrics::incr_sent_bytes(reply.encoded_len() as u64);
 
-        Ok(Response::new(reply))
+        Ok(Self::response_with_log_index(reply, log_index))
     }
 
     type ExportStream = Pin<Box<dyn Stream<Item = Result<ExportedChunk, Status>> + Send + 'static>>;
@@ -308,8 +983,11 @@ impl MetaService for MetaServiceImpl {
     /// The exported data is a series of JSON encoded strings of `RaftStoreEntry`.
     async fn export(
         &self,
-        _request: Request<common_meta_types::protobuf::Empty>,
+        request: Request<common_meta_types::protobuf::Empty>,
     ) -> Result<Response<Self::ExportStream>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "export")?;
+
         let _guard = RequestInFlight::guard();
 
         let meta_node = &self.meta_node;
@@ -327,6 +1005,55 @@ impl MetaService for MetaServiceImpl {
         Ok(Response::new(Box::pin(s)))
     }
 
+    /// Import the `GenericKV` entries in the output of `export`.
+    ///
+    /// The seq of an imported key is assigned by this cluster and may differ from the exported one.
+    async fn import(
+        &self,
+        request: Request<Streaming<ExportedChunk>>,
+    ) -> Result<Response<ImportReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "import")?;
+        self.check_writable()?;
+
+        let _guard = RequestInFlight::guard();
+
+        let schema_version = Self::get_schema_version(request.metadata())?;
+        let schema_versions = self.schema_versions().await?;
+
+        let mut strm = request.into_inner();
+        let mut imported = 0;
+
+        while let Some(chunk) = strm.message().await? {
+            let mut ops = vec![];
+
+            for line in chunk.data.iter() {
+                let (_tree_name, entry): (String, RaftStoreEntry) = serde_json::from_str(line)
+                    .map_err(|e| {
+                        Status::invalid_argument(format!("invalid entry: {}: {}", line, e))
+                    })?;
+
+                if let RaftStoreEntry::GenericKV { key, value } = entry {
+                    reserved_key::check_key(&key)?;
+                    schema_versions.check(schema_version, &key)?;
+
+                    let expire_at = value.meta.and_then(|m| m.expire_at);
+                    ops.push(TxnOp::put_with_expire(key, value.data, expire_at));
+                }
+
+                if ops.len() >= MAX_IMPORT_TXN_OPS {
+                    imported += self.import_ops(std::mem::take(&mut ops)).await?;
+                }
+            }
+
+            imported += self.import_ops(ops).await?;
+        }
+
+        info!("{}: imported {} key-values", func_name!(), imported);
+
+        Ok(Response::new(ImportReply { imported }))
+    }
+
     type WatchStream = Pin<Box<dyn Stream<Item = Result<WatchResponse, Status>> + Send + 'static>>;
 
     #[minitrace::trace]
@@ -334,15 +1061,35 @@ impl MetaService for MetaServiceImpl {
         &self,
         request: Request<WatchRequest>,
     ) -> Result<Response<Self::WatchStream>, Status> {
-        let (tx, rx) = mpsc::channel(4);
+        let claim = self.check_token(request.metadata())?;
 
-        let mn = &self.meta_node;
+        let request = request.into_inner();
+
+        self.key_acl
+            .check_range(&claim.username, &request.key, request.key_end.as_deref())?;
+
+        let (tx, rx) = bounded_stream(STREAM_BUFFER_SIZE, STREAM_SEND_TIMEOUT);
 
-        let add_res = mn.add_watcher(request.into_inner(), tx).await;
+        let mn = &self.meta_node;
+        let add_res = mn.add_watcher(request, claim.username, tx).await;
 
         match add_res {
-            Ok(watcher) => {
+            Ok((watcher, initial)) => {
+                let stream_id = watcher.id;
                 let stream = WatchStream::new(rx, watcher, mn.dispatcher_handle.clone());
+
+                // The initial values are followed by a response without event,
+                // which marks the end of initialization.
+                // It is sent even without initial flush,
+                // so that the client receives the stream id at once.
+                let mut head = initial;
+                head.push(WatchResponse {
+                    event: None,
+                    is_initialization: false,
+                    stream_id,
+                });
+
+                let stream = futures::stream::iter(head.into_iter().map(Ok)).chain(stream);
                 Ok(Response::new(Box::pin(stream) as Self::WatchStream))
             }
             Err(e) => {
@@ -388,6 +1135,8 @@ impl MetaService for MetaServiceImpl {
             db_size: status.db_size,
             state: status.state,
             is_leader: status.is_leader,
+            is_learner: status.is_learner,
+            read_replica: status.read_replica,
             current_term: status.current_term,
             last_log_index: status.last_log_index,
             last_applied: status.last_applied.to_string(),
@@ -407,6 +1156,320 @@ impl MetaService for MetaServiceImpl {
         Ok(Response::new(resp))
     }
 
+    async fn transfer_leader(
+        &self,
+        request: Request<TransferLeaderRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "transfer leader")?;
+
+        let _guard = RequestInFlight::guard();
+
+        let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+
+        // The target verifies the same token before starting an election.
+        let auth_token = request
+            .metadata()
+            .get_bin(AUTH_TOKEN_KEY)
+            .cloned()
+            .ok_or_else(|| Status::unauthenticated("Error auth-token-bin is empty"))?;
+
+        let to = request.into_inner().to;
+
+        self.meta_node
+            .assume_leader()
+            .await
+            .map_err(|e| Status::failed_precondition(format!("not leader: {}", e)))?;
+
+        let is_voter = {
+            let metrics = self.meta_node.raft.metrics().borrow().clone();
+            let mut voters = metrics.membership_config.membership().voter_ids();
+            voters.any(|id| id == to)
+        };
+        if !is_voter {
+            return Err(Status::invalid_argument(format!(
+                "transfer leader target {} is not a voter",
+                to
+            )));
+        }
+
+        GrpcHelper::with_timeout(timeout, async {
+            self.meta_node
+                .transfer_leader(to, auth_token)
+                .await
+                .map_err(GrpcHelper::internal_err)
+        })
+        .await?;
+
+        Ok(Response::new(Empty {}))
+    }
+
+    /// Read the raft log entries in `[start, end)` from the local log, for diffing the logs of replicas.
+    ///
+    /// At most [`MAX_READ_LOG_ENTRIES`] entries or [`MAX_READ_LOG_BYTES`] bytes are returned.
+    /// The caller continues from the index after the last returned entry.
+    async fn read_log(
+        &self,
+        request: Request<ReadLogRequest>,
+    ) -> Result<Response<ReadLogReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "read raft log")?;
+
+        let _guard = RequestInFlight::guard();
+
+        let ReadLogRequest { start, end } = request.into_inner();
+        if start > end {
+            return Err(Status::invalid_argument(format!(
+                "invalid log index range: [{}, {})",
+                start, end
+            )));
+        }
+        let end = std::cmp::min(end, start.saturating_add(MAX_READ_LOG_ENTRIES));
+
+        let entries = self
+            .meta_node
+            .sto
+            .log
+            .read()
+            .await
+            .range_values(start..end)
+            .map_err(GrpcHelper::internal_err)?;
+
+        let mut res = Vec::with_capacity(entries.len());
+        let mut size = 0;
+
+        for entry in entries.iter() {
+            let s = serde_json::to_string(entry).map_err(GrpcHelper::internal_err)?;
+
+            size += s.len();
+            if size > MAX_READ_LOG_BYTES && !res.is_empty() {
+                break;
+            }
+            res.push(s);
+        }
+
+        Ok(Response::new(ReadLogReply { entries: res }))
+    }
+
+    async fn set_read_only(
+        &self,
+        request: Request<SetReadOnlyRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "set read-only mode")?;
+
+        let read_only = request.into_inner().read_only;
+        self.read_only.store(read_only, Ordering::Relaxed);
+        info!("set read-only mode: {}", read_only);
+
+        Ok(Response::new(Empty {}))
+    }
+
+    /// Raise the cluster version, after every node is upgraded to a build supporting it.
+    async fn set_cluster_version(
+        &self,
+        request: Request<SetClusterVersionRequest>,
+    ) -> Result<Response<ClusterVersionReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "set cluster version")?;
+        self.check_writable()?;
+
+        let version = request.into_inner().version;
+        let version = self
+            .meta_node
+            .set_cluster_version(version)
+            .await
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+
+        Ok(Response::new(ClusterVersionReply {
+            version,
+            max_version: MAX_CLUSTER_VERSION,
+        }))
+    }
+
+    /// Raise the minimum schema version of a key namespace, once no client uses an older layout.
+    async fn set_schema_version(
+        &self,
+        request: Request<SetSchemaVersionRequest>,
+    ) -> Result<Response<SetSchemaVersionReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "set schema version")?;
+        self.check_writable()?;
+
+        let SetSchemaVersionRequest { prefix, version } = request.into_inner();
+        if prefix.is_empty() {
+            return Err(Status::invalid_argument(
+                "schema version prefix can not be empty",
+            ));
+        }
+
+        let version = self
+            .meta_node
+            .set_schema_version(&prefix, version)
+            .await
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+
+        Ok(Response::new(SetSchemaVersionReply { version }))
+    }
+
+    /// Return the log entries that failed to apply on this node, which are kept until it restarts.
+    async fn get_failed_applies(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<FailedAppliesReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+        GrpcHelper::check_root(&claim, "get failed applies")?;
+
+        let failed_applies = self
+            .meta_node
+            .sto
+            .failed_applies()
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(GrpcHelper::internal_err)?;
+
+        Ok(Response::new(FailedAppliesReply { failed_applies }))
+    }
+
+    /// Count the keys under a prefix on the leader, without transferring them to the client.
+    async fn count_prefix(
+        &self,
+        request: Request<CountPrefixRequest>,
+    ) -> Result<Response<CountPrefixReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+
+        network_metrics::incr_recv_bytes(request.get_ref().encoded_len() as u64);
+        let _guard = RequestInFlight::guard();
+
+        let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+        let prefix = request.into_inner().prefix;
+        self.key_acl.check(&claim.username, &prefix)?;
+
+        // A follower forwards it as a `ForwardRequestBody::CountPrefix`.
+        self.check_forwardable("count prefix").await?;
+
+        let res = GrpcHelper::with_timeout(timeout, async {
+            self.meta_node
+                .consistent_read::<_, u64>(CountPrefixReq { prefix })
+                .await
+                .map_err(GrpcHelper::internal_err)
+        })
+        .await;
+
+        network_metrics::incr_request_result(res.is_ok());
+        let count = res?;
+
+        Ok(Response::new(CountPrefixReply { count }))
+    }
+
+    /// Atomically add a delta to a counter key and return the new value.
+    ///
+    /// It requires [`CLUSTER_VERSION_V1`]: a node of an older build can not apply `Cmd::IncrementKV`.
+    async fn increment(
+        &self,
+        request: Request<IncrementRequest>,
+    ) -> Result<Response<IncrementReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+
+        network_metrics::incr_recv_bytes(request.get_ref().encoded_len() as u64);
+        let _guard = RequestInFlight::guard();
+
+        let root = common_tracing::start_trace_for_remote_request(full_name!(), &request);
+        let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+        let schema_version = Self::get_schema_version(request.metadata())?;
+        let IncrementRequest { key, delta } = request.into_inner();
+        self.key_acl.check(&claim.username, &key)?;
+        reserved_key::check_key(&key)?;
+        self.schema_versions().await?.check(schema_version, &key)?;
+        self.check_writable()?;
+
+        let ver = self.meta_node.cluster_version().await;
+        if ver < CLUSTER_VERSION_V1 {
+            return Err(Status::failed_precondition(format!(
+                "increment requires cluster version >= {}, current: {}",
+                CLUSTER_VERSION_V1, ver
+            )));
+        }
+
+        let incr = IncrementKV::new(&key, delta);
+        let res = GrpcHelper::with_timeout(timeout, async {
+            self.wait_write_applied(self.meta_node.increment_kv_with_log_index(incr))
+                .await?
+                .map_err(GrpcHelper::internal_err)
+        })
+        .in_span(root)
+        .await;
+
+        network_metrics::incr_request_result(res.is_ok());
+        let (change, log_index) = res?;
+
+        // An unchanged key means the increment is not applied.
+        let value = change
+            .result
+            .as_ref()
+            .filter(|_| change.is_changed())
+            .and_then(|x| IncrementKV::decode_value(&x.data));
+        let Some(value) = value else {
+            return Err(Status::failed_precondition(format!(
+                "can not increment key: {}: the value is not an i64 or the sum overflows",
+                key
+            )));
+        };
+
+        Ok(Self::response_with_log_index(
+            IncrementReply { value },
+            log_index,
+        ))
+    }
+
+    /// Close a watch stream by the `stream_id` in its responses.
+    async fn cancel_stream(
+        &self,
+        request: Request<CancelStreamRequest>,
+    ) -> Result<Response<CancelStreamReply>, Status> {
+        let claim = self.check_token(request.metadata())?;
+
+        let _guard = RequestInFlight::guard();
+
+        let stream_id = request.into_inner().stream_id;
+        let cancelled = self
+            .meta_node
+            .cancel_watcher(stream_id, claim.username)
+            .await
+            .map_err(Status::permission_denied)?;
+        info!("cancel stream: {}, cancelled: {}", stream_id, cancelled);
+
+        Ok(Response::new(CancelStreamReply { cancelled }))
+    }
+
+    /// Wait for this node to apply up to `log_index`, e.g., to read back a write made through another node.
+    async fn wait_applied(
+        &self,
+        request: Request<WaitAppliedRequest>,
+    ) -> Result<Response<WaitAppliedReply>, Status> {
+        self.check_token(request.metadata())?;
+
+        let _guard = RequestInFlight::guard();
+
+        let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+        let log_index = request.into_inner().log_index;
+
+        let last_applied = self
+            .meta_node
+            .wait_applied(log_index, timeout)
+            .await
+            .map_err(|e| match e {
+                WaitError::Timeout(_, _) => Status::deadline_exceeded(format!(
+                    "log index {} is not applied in {:?}",
+                    log_index, timeout
+                )),
+                WaitError::ShuttingDown => Status::unavailable(e.to_string()),
+            })?;
+
+        Ok(Response::new(WaitAppliedReply { last_applied }))
+    }
+
     async fn get_client_info(
         &self,
         request: Request<Empty>,
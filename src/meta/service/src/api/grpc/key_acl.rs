@@ -0,0 +1,164 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use common_meta_types::txn_op::Request;
+use common_meta_types::TxnRequest;
+use tonic::Status;
+
+/// Key prefixes that each non-root user is allowed to access.
+///
+/// It is built from a string in form of `user1=prefix1,prefix2;user2=prefix3`.
+/// The root user is not limited by it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyAcl {
+    prefixes: BTreeMap<String, Vec<String>>,
+}
+
+impl KeyAcl {
+    pub const ROOT: &'static str = "root";
+
+    /// Returns true if the user is allowed to handshake, i.e., it is root or has an acl entry.
+    pub fn has_user(&self, username: &str) -> bool {
+        username == Self::ROOT || self.prefixes.contains_key(username)
+    }
+
+    /// Check if a user is allowed to access a key, or every key with a prefix.
+    pub fn check(&self, username: &str, key: &str) -> Result<(), Status> {
+        if username == Self::ROOT {
+            return Ok(());
+        }
+
+        let allowed = self
+            .prefixes
+            .get(username)
+            .map(|prefixes| prefixes.iter().any(|p| key.starts_with(p.as_str())))
+            .unwrap_or(false);
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!(
+                "user {} is not allowed to access key: {}",
+                username, key
+            )))
+        }
+    }
+
+    /// Check if a user is allowed to access every key in the range `[start, end)`, or just `start` if there is no `end`.
+    ///
+    /// Both ends must have the same allowed prefix, so that every key in between has it too.
+    pub fn check_range(
+        &self,
+        username: &str,
+        start: &str,
+        end: Option<&str>,
+    ) -> Result<(), Status> {
+        let Some(end) = end else {
+            return self.check(username, start);
+        };
+
+        if username == Self::ROOT {
+            return Ok(());
+        }
+
+        let allowed = self
+            .prefixes
+            .get(username)
+            .map(|prefixes| {
+                prefixes
+                    .iter()
+                    .any(|p| start.starts_with(p.as_str()) && end.starts_with(p.as_str()))
+            })
+            .unwrap_or(false);
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(format!(
+                "user {} is not allowed to access key range: [{}, {})",
+                username, start, end
+            )))
+        }
+    }
+
+    pub fn check_keys<'a>(
+        &self,
+        username: &str,
+        keys: impl IntoIterator<Item = &'a String>,
+    ) -> Result<(), Status> {
+        for key in keys {
+            self.check(username, key)?;
+        }
+        Ok(())
+    }
+
+    /// Check every key a transaction reads or writes.
+    pub fn check_txn(&self, username: &str, txn: &TxnRequest) -> Result<(), Status> {
+        for cond in txn.condition.iter() {
+            self.check(username, &cond.key)?;
+        }
+
+        for op in txn.if_then.iter().chain(txn.else_then.iter()) {
+            let key = match &op.request {
+                Some(Request::Get(r)) => &r.key,
+                Some(Request::Put(r)) => &r.key,
+                Some(Request::Delete(r)) => &r.key,
+                Some(Request::DeleteByPrefix(r)) => &r.prefix,
+                None => continue,
+            };
+            self.check(username, key)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for KeyAcl {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut prefixes = BTreeMap::new();
+
+        for entry in s.split(';').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let (user, list) = entry.split_once('=').ok_or_else(|| {
+                format!(
+                    "invalid key acl entry: {}, expect: <user>=<prefix>,...",
+                    entry
+                )
+            })?;
+
+            let user = user.trim();
+            if user.is_empty() {
+                return Err(format!("empty user in key acl entry: {}", entry));
+            }
+            if user == Self::ROOT {
+                return Err(format!("root can not be limited by key acl: {}", entry));
+            }
+
+            let list = list
+                .split(',')
+                .map(|x| x.trim())
+                .filter(|x| !x.is_empty())
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>();
+
+            prefixes.insert(user.to_string(), list);
+        }
+
+        Ok(Self { prefixes })
+    }
+}
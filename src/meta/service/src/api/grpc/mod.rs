@@ -12,4 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod credentials;
 pub mod grpc_service;
+pub mod key_acl;
+pub mod reserved_key;
+pub mod schema_version;
+pub mod write_log_sampler;
@@ -0,0 +1,61 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_meta_types::txn_op::Request;
+use common_meta_types::TxnRequest;
+use tonic::Status;
+
+/// The prefix of the keys that store the versions a cluster enforces,
+/// such as [`common_meta_types::cluster_version::CLUSTER_VERSION_KEY`]
+/// and the keys under [`common_meta_types::schema_version::SCHEMA_VERSION_KEY_PREFIX`].
+///
+/// These keys are written only by the admin APIs, which never lower a version.
+/// A client can read them, but can not write or delete them.
+pub const RESERVED_KEY_PREFIX: &str = "__fd_meta/";
+
+/// Return an error if a client write to `key` would change a reserved key.
+pub fn check_key(key: &str) -> Result<(), Status> {
+    if key.starts_with(RESERVED_KEY_PREFIX) {
+        return Err(Status::permission_denied(format!(
+            "key is reserved: {}, keys under {} can not be written by a client",
+            key, RESERVED_KEY_PREFIX
+        )));
+    }
+    Ok(())
+}
+
+/// Return an error if a client deleting every key with `prefix` would delete a reserved key,
+/// i.e., the prefix is under the reserved prefix or covers it.
+pub fn check_prefix(prefix: &str) -> Result<(), Status> {
+    if prefix.starts_with(RESERVED_KEY_PREFIX) || RESERVED_KEY_PREFIX.starts_with(prefix) {
+        return Err(Status::permission_denied(format!(
+            "prefix covers reserved keys: {:?}, keys under {} can not be deleted by a client",
+            prefix, RESERVED_KEY_PREFIX
+        )));
+    }
+    Ok(())
+}
+
+/// Check every key a transaction may write or delete; reading a reserved key is allowed.
+pub fn check_txn(txn: &TxnRequest) -> Result<(), Status> {
+    for op in txn.if_then.iter().chain(txn.else_then.iter()) {
+        match &op.request {
+            Some(Request::Put(r)) => check_key(&r.key)?,
+            Some(Request::Delete(r)) => check_key(&r.key)?,
+            Some(Request::DeleteByPrefix(r)) => check_prefix(&r.prefix)?,
+            Some(Request::Get(_)) | None => {}
+        }
+    }
+    Ok(())
+}
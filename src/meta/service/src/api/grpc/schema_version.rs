@@ -0,0 +1,88 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_meta_types::txn_op::Request;
+use common_meta_types::TxnRequest;
+use tonic::Status;
+
+/// The minimum schema version of the keys under each prefix, which a write must carry.
+///
+/// The versions are stored in the state machine, see [`common_meta_types::schema_version`].
+/// A key belongs to the namespace of the longest prefix it starts with.
+/// A write to a key in a namespace must carry a version no less than the minimum of the namespace.
+/// A key in no namespace is not checked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemaVersions {
+    versions: BTreeMap<String, u64>,
+}
+
+impl SchemaVersions {
+    pub fn new(versions: BTreeMap<String, u64>) -> Self {
+        Self { versions }
+    }
+
+    /// Returns the namespace prefix of a key and the minimum schema version of it.
+    pub fn min_version(&self, key: &str) -> Option<(&str, u64)> {
+        self.versions
+            .iter()
+            .filter(|(prefix, _)| key.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, ver)| (prefix.as_str(), *ver))
+    }
+
+    /// Check the schema version a write carries against the minimum of the key.
+    pub fn check(&self, version: Option<u64>, key: &str) -> Result<(), Status> {
+        let Some((prefix, min)) = self.min_version(key) else {
+            return Ok(());
+        };
+
+        match version {
+            None => Err(Status::failed_precondition(format!(
+                "schema version is required for key: {}, min: {} for prefix: {}",
+                key, min, prefix
+            ))),
+            Some(version) if version < min => Err(Status::failed_precondition(format!(
+                "schema version is too old for key: {}, request: {}, min: {} for prefix: {}",
+                key, version, min, prefix
+            ))),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Check every key removed by a delete-by-prefix,
+    /// i.e., the namespace of the prefix and every namespace under the prefix.
+    pub fn check_prefix(&self, version: Option<u64>, prefix: &str) -> Result<(), Status> {
+        self.check(version, prefix)?;
+
+        for ns in self.versions.keys().filter(|ns| ns.starts_with(prefix)) {
+            self.check(version, ns)?;
+        }
+        Ok(())
+    }
+
+    /// Check every key a transaction writes.
+    pub fn check_txn(&self, version: Option<u64>, txn: &TxnRequest) -> Result<(), Status> {
+        for op in txn.if_then.iter().chain(txn.else_then.iter()) {
+            match &op.request {
+                Some(Request::Put(r)) => self.check(version, &r.key)?,
+                Some(Request::Delete(r)) => self.check(version, &r.key)?,
+                Some(Request::DeleteByPrefix(r)) => self.check_prefix(version, &r.prefix)?,
+                Some(Request::Get(_)) | None => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Decides which write requests are logged, so that the write path can be traced
+/// in production without a log line for every write.
+///
+/// Only the keys and the result of a sampled write are logged, never the values.
+#[derive(Debug, Default)]
+pub struct WriteLogSampler {
+    /// Log 1 in every `rate` writes. 0 disables logging.
+    rate: u64,
+    /// Number of writes seen so far.
+    seen: AtomicU64,
+}
+
+impl WriteLogSampler {
+    pub fn new(rate: u64) -> Self {
+        Self {
+            rate,
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    pub fn rate(&self) -> u64 {
+        self.rate
+    }
+
+    /// Count a write and return whether it should be logged.
+    ///
+    /// The first write is always sampled if logging is enabled.
+    pub fn sample(&self) -> bool {
+        if self.rate == 0 {
+            return false;
+        }
+
+        let n = self.seen.fetch_add(1, Ordering::Relaxed);
+        n % self.rate == 0
+    }
+}
@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyerror::AnyError;
 use common_base::base::tokio;
+use common_base::base::tokio::net::TcpSocket;
 use common_base::base::tokio::sync::oneshot;
 use common_base::base::tokio::sync::oneshot::Receiver;
 use common_base::base::tokio::sync::oneshot::Sender;
@@ -24,15 +27,21 @@ use common_base::base::Stoppable;
 use common_meta_types::protobuf::meta_service_server::MetaServiceServer;
 use common_meta_types::protobuf::FILE_DESCRIPTOR_SET;
 use common_meta_types::GrpcConfig;
+use common_meta_types::InvalidArgument;
 use common_meta_types::MetaNetworkError;
 use futures::future::Either;
 use log::info;
 use minitrace::prelude::*;
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::Certificate;
 use tonic::transport::Identity;
 use tonic::transport::Server;
 use tonic::transport::ServerTlsConfig;
 
+use crate::api::grpc::credentials::CertUsers;
+use crate::api::grpc::credentials::UserCredentials;
 use crate::api::grpc::grpc_service::MetaServiceImpl;
+use crate::api::grpc::key_acl::KeyAcl;
 use crate::configs::Config;
 use crate::meta_service::MetaNode;
 
@@ -69,12 +78,18 @@ impl GrpcServer {
         // For sending the signal when server finished shutting down.
         let (fin_tx, fin_rx) = oneshot::channel::<()>();
 
-        let reflect_srv = tonic_reflection::server::Builder::configure()
-            .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
-            .build()
-            .unwrap();
+        // Reflection exposes the API definition to anyone who can connect, thus it is opt-in.
+        let reflect_srv = if conf.grpc_enable_reflection {
+            let srv = tonic_reflection::server::Builder::configure()
+                .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+                .build()
+                .unwrap();
+            Some(srv)
+        } else {
+            None
+        };
 
-        let builder = Server::builder();
+        let builder = Self::server_builder(&conf);
 
         let tls_conf = Self::tls_config(&self.conf)
             .await
@@ -89,11 +104,43 @@ impl GrpcServer {
             builder
         };
 
-        let addr = conf.grpc_api_address.parse::<std::net::SocketAddr>()?;
+        let addr = conf.grpc_api_address.parse::<SocketAddr>()?;
 
         info!("gRPC addr: {}", addr);
 
-        let grpc_impl = MetaServiceImpl::create(meta_node.clone());
+        let incoming = Self::bind(addr, conf.grpc_accept_backlog)?;
+
+        let key_acl: KeyAcl = conf.grpc_key_acl.parse().map_err(|e: String| {
+            MetaNetworkError::InvalidArgument(InvalidArgument::new(
+                AnyError::error(e),
+                "parse grpc_key_acl",
+            ))
+        })?;
+
+        let credentials: UserCredentials =
+            conf.grpc_user_credentials.parse().map_err(|e: String| {
+                MetaNetworkError::InvalidArgument(InvalidArgument::new(
+                    AnyError::error(e),
+                    "parse grpc_user_credentials",
+                ))
+            })?;
+
+        let cert_users: CertUsers = conf.grpc_cert_users.parse().map_err(|e: String| {
+            MetaNetworkError::InvalidArgument(InvalidArgument::new(
+                AnyError::error(e),
+                "parse grpc_cert_users",
+            ))
+        })?;
+
+        let grpc_impl = MetaServiceImpl::create(meta_node.clone())
+            .with_key_acl(key_acl)
+            .with_credentials(credentials.with_disabled(conf.grpc_disable_password_auth))
+            .with_cert_users(cert_users)
+            .with_root_disabled(conf.grpc_disable_root)
+            .with_write_log_sample_rate(conf.grpc_write_log_sample_rate)
+            .with_apply_timeout(
+                Some(Duration::from_millis(conf.grpc_apply_timeout_ms)).filter(|t| !t.is_zero()),
+            );
         let grpc_srv = MetaServiceServer::new(grpc_impl)
             .max_decoding_message_size(GrpcConfig::MAX_DECODING_SIZE)
             .max_encoding_message_size(GrpcConfig::MAX_ENCODING_SIZE);
@@ -101,9 +148,9 @@ impl GrpcServer {
         let j = tokio::spawn(
             async move {
                 let res = builder
-                    .add_service(reflect_srv)
+                    .add_optional_service(reflect_srv)
                     .add_service(grpc_srv)
-                    .serve_with_shutdown(addr, async move {
+                    .serve_with_incoming_shutdown(incoming, async move {
                         let _ = started_tx.send(());
                         info!("metasrv starts to wait for stop signal: {}", addr);
                         let _ = stop_rx.await;
@@ -169,13 +216,53 @@ impl GrpcServer {
         }
     }
 
+    /// Create a server builder with the HTTP/2 settings in the config applied.
+    fn server_builder(conf: &Config) -> Server {
+        Server::builder()
+            .max_concurrent_streams(conf.grpc_max_concurrent_streams)
+            .initial_connection_window_size(conf.grpc_initial_connection_window_size)
+            .initial_stream_window_size(conf.grpc_initial_stream_window_size)
+    }
+
+    /// Listen on `addr` with an accept queue of at most `backlog` pending connections.
+    fn bind(addr: SocketAddr, backlog: u32) -> Result<TcpIncoming, MetaNetworkError> {
+        let io_err = |e: std::io::Error| {
+            MetaNetworkError::BadAddressFormat(AnyError::new(&e).add_context(|| addr))
+        };
+
+        let socket = if addr.is_ipv4() {
+            TcpSocket::new_v4()
+        } else {
+            TcpSocket::new_v6()
+        }
+        .map_err(io_err)?;
+
+        socket.set_reuseaddr(true).map_err(io_err)?;
+        socket.bind(addr).map_err(io_err)?;
+        let listener = socket.listen(backlog).map_err(io_err)?;
+
+        let incoming = TcpIncoming::from_listener(listener, true, None).map_err(|e| {
+            MetaNetworkError::BadAddressFormat(AnyError::error(e).add_context(|| addr))
+        })?;
+
+        Ok(incoming)
+    }
+
     async fn tls_config(conf: &Config) -> Result<Option<ServerTlsConfig>, std::io::Error> {
         if conf.tls_rpc_server_enabled() {
             let cert = tokio::fs::read(conf.grpc_tls_server_cert.as_str()).await?;
             let key = tokio::fs::read(conf.grpc_tls_server_key.as_str()).await?;
             let server_identity = Identity::from_pem(cert, key);
 
-            let tls = ServerTlsConfig::new().identity(server_identity);
+            let mut tls = ServerTlsConfig::new().identity(server_identity);
+
+            // A client without a certificate can still handshake with a password or a token.
+            if !conf.grpc_tls_client_ca.is_empty() {
+                let ca = tokio::fs::read(conf.grpc_tls_client_ca.as_str()).await?;
+                tls = tls
+                    .client_ca_root(Certificate::from_pem(ca))
+                    .client_auth_optional(true);
+            }
             Ok(Some(tls))
         } else {
             Ok(None)
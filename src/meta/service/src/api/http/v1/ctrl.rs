@@ -15,6 +15,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use common_meta_types::cluster_version::MAX_CLUSTER_VERSION;
 use poem::http::StatusCode;
 use poem::web::Data;
 use poem::web::IntoResponse;
@@ -22,18 +23,17 @@ use poem::web::Json;
 
 use crate::meta_service::MetaNode;
 
-/// Let raft leader send snapshot to followers/learners.
+/// Build a snapshot on this node and return the last log id included in it.
 ///
-/// If this node is not a leader this request will be just ignored.
+/// The request is not forwarded to the leader.
+/// If a previously triggered snapshot is still being built, it returns with `in_progress: true`.
 #[poem::handler]
 pub async fn trigger_snapshot(meta_node: Data<&Arc<MetaNode>>) -> poem::Result<impl IntoResponse> {
-    meta_node
-        .raft
-        .trigger()
-        .snapshot()
+    let status = meta_node
+        .trigger_snapshot()
         .await
         .map_err(|e| poem::Error::from_string(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
-    Ok(Json(()))
+    Ok(Json(status))
 }
 
 #[poem::handler]
@@ -53,3 +53,37 @@ pub async fn block_compact_snapshot(
     sm.blocking_config_mut().compact_snapshot = Duration::from_millis(1_000_000);
     Ok(Json(()))
 }
+
+#[derive(serde::Serialize, Debug)]
+pub struct ClusterVersionResponse {
+    /// The cluster version in the state machine of this node.
+    pub version: u64,
+
+    /// The greatest cluster version the build of this node supports.
+    pub max_version: u64,
+}
+
+/// Return the cluster version on this node.
+///
+/// The request is not forwarded to the leader.
+/// The version is raised with the root-only `SetClusterVersion` gRPC API.
+#[poem::handler]
+pub async fn cluster_version(meta_node: Data<&Arc<MetaNode>>) -> poem::Result<impl IntoResponse> {
+    Ok(Json(ClusterVersionResponse {
+        version: meta_node.cluster_version().await,
+        max_version: MAX_CLUSTER_VERSION,
+    }))
+}
+
+/// Return the minimum schema version of each key namespace, by namespace prefix, on this node.
+///
+/// The request is not forwarded to the leader.
+/// A version is raised with the root-only `SetSchemaVersion` gRPC API.
+#[poem::handler]
+pub async fn schema_versions(meta_node: Data<&Arc<MetaNode>>) -> poem::Result<impl IntoResponse> {
+    let versions = meta_node
+        .schema_versions()
+        .await
+        .map_err(|e| poem::Error::from_string(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(Json(versions))
+}
@@ -70,6 +70,14 @@ impl HttpService {
                 "/v1/ctrl/block_compact_snapshot",
                 get(super::http::v1::ctrl::block_compact_snapshot),
             )
+            .at(
+                "/v1/ctrl/cluster_version",
+                get(super::http::v1::ctrl::cluster_version),
+            )
+            .at(
+                "/v1/ctrl/schema_versions",
+                get(super::http::v1::ctrl::schema_versions),
+            )
             .at(
                 "/v1/cluster/nodes",
                 get(super::http::v1::cluster_state::nodes_handler),
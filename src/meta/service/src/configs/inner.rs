@@ -20,6 +20,9 @@ use common_meta_types::Node;
 use common_tracing::Config as LogConfig;
 
 use super::outer_v0::Config as OuterV0Config;
+use crate::api::grpc::credentials::CertUsers;
+use crate::api::grpc::credentials::UserCredentials;
+use crate::api::grpc::key_acl::KeyAcl;
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
 pub struct Config {
@@ -40,6 +43,32 @@ pub struct Config {
     /// Certificate for server to identify itself
     pub grpc_tls_server_cert: String,
     pub grpc_tls_server_key: String,
+    /// The CA certificate to verify client certificates with, which enables mTLS authentication.
+    pub grpc_tls_client_ca: String,
+    /// Key prefixes each non-root user is allowed to access, see [`KeyAcl`].
+    pub grpc_key_acl: String,
+    /// Reject handshake of the built-in root user.
+    pub grpc_disable_root: bool,
+    /// HTTP/2 max concurrent streams per connection; `None` to use the default.
+    pub grpc_max_concurrent_streams: Option<u32>,
+    /// HTTP/2 initial connection window size in bytes; `None` to use the default.
+    pub grpc_initial_connection_window_size: Option<u32>,
+    /// HTTP/2 initial stream window size in bytes; `None` to use the default.
+    pub grpc_initial_stream_window_size: Option<u32>,
+    /// Max number of pending connections in the accept queue of the listening socket.
+    pub grpc_accept_backlog: u32,
+    /// Log 1 in every N write requests; 0 to disable.
+    pub grpc_write_log_sample_rate: u64,
+    /// The max time in milliseconds a write waits to be applied; 0 to use only the request timeout.
+    pub grpc_apply_timeout_ms: u64,
+    /// Serve gRPC server reflection of the meta service API.
+    pub grpc_enable_reflection: bool,
+    /// The password hash of each user checked in handshake, see [`UserCredentials`].
+    pub grpc_user_credentials: String,
+    /// Skip the password check in handshake, for a deployment without `grpc_user_credentials`.
+    pub grpc_disable_password_auth: bool,
+    /// The user of each client certificate for mTLS authentication, see [`CertUsers`].
+    pub grpc_cert_users: String,
     pub raft_config: RaftConfig,
 }
 
@@ -62,6 +91,19 @@ impl Default for Config {
             grpc_api_advertise_host: None,
             grpc_tls_server_cert: "".to_string(),
             grpc_tls_server_key: "".to_string(),
+            grpc_tls_client_ca: "".to_string(),
+            grpc_key_acl: "".to_string(),
+            grpc_disable_root: false,
+            grpc_max_concurrent_streams: None,
+            grpc_initial_connection_window_size: None,
+            grpc_initial_stream_window_size: None,
+            grpc_accept_backlog: 1024,
+            grpc_write_log_sample_rate: 0,
+            grpc_apply_timeout_ms: 0,
+            grpc_enable_reflection: false,
+            grpc_user_credentials: "".to_string(),
+            grpc_disable_password_auth: false,
+            grpc_cert_users: "".to_string(),
             raft_config: Default::default(),
         }
     }
@@ -84,6 +126,21 @@ impl Config {
                 e, self.grpc_api_address
             ))
         })?;
+        let _acl: KeyAcl = self.grpc_key_acl.parse().map_err(|e| {
+            MetaStartupError::InvalidConfig(format!(
+                "{} while parsing grpc_key_acl: {}",
+                e, self.grpc_key_acl
+            ))
+        })?;
+        let _credentials: UserCredentials = self.grpc_user_credentials.parse().map_err(|e| {
+            MetaStartupError::InvalidConfig(format!("{} while parsing grpc_user_credentials", e))
+        })?;
+        let _cert_users: CertUsers = self.grpc_cert_users.parse().map_err(|e| {
+            MetaStartupError::InvalidConfig(format!(
+                "{} while parsing grpc_cert_users: {}",
+                e, self.grpc_cert_users
+            ))
+        })?;
         Ok(())
     }
 
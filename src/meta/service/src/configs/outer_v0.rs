@@ -24,7 +24,7 @@ use common_tracing::Config as InnerLogConfig;
 use common_tracing::FileConfig as InnerFileLogConfig;
 use common_tracing::QueryLogConfig;
 use common_tracing::StderrConfig as InnerStderrLogConfig;
-use common_tracing::TracingConfig;
+use common_tracing::TracingConfig as InnerTracingConfig;
 use serde::Deserialize;
 use serde::Serialize;
 use serfig::collectors::from_env;
@@ -125,6 +125,80 @@ pub struct Config {
     #[clap(long, default_value = "")]
     pub grpc_tls_server_key: String,
 
+    /// CA certificate to verify the client certificates with.
+    ///
+    /// When it is set along with the server certificate, a client may handshake with mTLS,
+    /// i.e., be identified by its certificate, see `grpc_cert_users`.
+    #[clap(long, default_value = "")]
+    pub grpc_tls_client_ca: String,
+
+    /// Key prefixes each non-root user is allowed to access through gRPC API,
+    /// in form of `user1=prefix1,prefix2;user2=prefix3`.
+    ///
+    /// Only root and users listed here can handshake. Root can access every key.
+    #[clap(long, default_value = "")]
+    pub grpc_key_acl: String,
+
+    /// Reject handshake of the built-in `root` user.
+    ///
+    /// Users in `grpc_key_acl` can still handshake.
+    #[clap(long)]
+    pub grpc_disable_root: bool,
+
+    /// HTTP/2 max concurrent streams per gRPC connection.
+    ///
+    /// The default of the underlying HTTP/2 implementation is used if it is absent.
+    #[clap(long)]
+    pub grpc_max_concurrent_streams: Option<u32>,
+
+    /// HTTP/2 initial connection window size in bytes for gRPC API.
+    #[clap(long)]
+    pub grpc_initial_connection_window_size: Option<u32>,
+
+    /// HTTP/2 initial stream window size in bytes for gRPC API.
+    #[clap(long)]
+    pub grpc_initial_stream_window_size: Option<u32>,
+
+    /// Max number of pending connections in the accept queue of the gRPC listening socket.
+    #[clap(long, default_value = "1024")]
+    pub grpc_accept_backlog: u32,
+
+    /// Log 1 in every N write requests with the keys and the result, without values.
+    ///
+    /// 0 disables the sampled write log.
+    #[clap(long, default_value = "0")]
+    pub grpc_write_log_sample_rate: u64,
+
+    /// The max time in milliseconds a write request waits for its log to be applied.
+    ///
+    /// 0 means a write is only bounded by the request timeout.
+    #[clap(long, default_value = "0")]
+    pub grpc_apply_timeout_ms: u64,
+
+    /// Serve gRPC server reflection, so that tools like grpcurl can discover the meta service API at runtime.
+    #[clap(long)]
+    pub grpc_enable_reflection: bool,
+
+    /// The salted password hash of each user, in form of `user1=<hash1>;user2=<hash2>`,
+    /// where a hash is `pbkdf2-sha256$<iterations>$<hex salt>$<hex hash>`.
+    ///
+    /// A user must handshake with a password matching the hash listed here,
+    /// unless `grpc_disable_password_auth` is set.
+    #[clap(long, default_value = "")]
+    pub grpc_user_credentials: String,
+
+    /// Accept any password in handshake, instead of checking it against `grpc_user_credentials`.
+    ///
+    /// Without it, a user without a stored hash can not handshake with a password.
+    #[clap(long)]
+    pub grpc_disable_password_auth: bool,
+
+    /// The user of each client certificate that handshakes with mTLS,
+    /// in form of `user1=<fingerprint1>;user2=<fingerprint2>`,
+    /// where a fingerprint is the hex SHA-256 of the DER encoded certificate.
+    #[clap(long, default_value = "")]
+    pub grpc_cert_users: String,
+
     #[clap(flatten)]
     pub raft_config: RaftConfig,
 }
@@ -162,6 +236,19 @@ impl From<Config> for InnerConfig {
             grpc_api_advertise_host: outer.grpc_api_advertise_host,
             grpc_tls_server_cert: outer.grpc_tls_server_cert,
             grpc_tls_server_key: outer.grpc_tls_server_key,
+            grpc_tls_client_ca: outer.grpc_tls_client_ca,
+            grpc_key_acl: outer.grpc_key_acl,
+            grpc_disable_root: outer.grpc_disable_root,
+            grpc_max_concurrent_streams: outer.grpc_max_concurrent_streams,
+            grpc_initial_connection_window_size: outer.grpc_initial_connection_window_size,
+            grpc_initial_stream_window_size: outer.grpc_initial_stream_window_size,
+            grpc_accept_backlog: outer.grpc_accept_backlog,
+            grpc_write_log_sample_rate: outer.grpc_write_log_sample_rate,
+            grpc_apply_timeout_ms: outer.grpc_apply_timeout_ms,
+            grpc_enable_reflection: outer.grpc_enable_reflection,
+            grpc_user_credentials: outer.grpc_user_credentials,
+            grpc_disable_password_auth: outer.grpc_disable_password_auth,
+            grpc_cert_users: outer.grpc_cert_users,
             raft_config: outer.raft_config.into(),
         }
     }
@@ -188,6 +275,19 @@ impl From<InnerConfig> for Config {
             grpc_api_advertise_host: inner.grpc_api_advertise_host,
             grpc_tls_server_cert: inner.grpc_tls_server_cert,
             grpc_tls_server_key: inner.grpc_tls_server_key,
+            grpc_tls_client_ca: inner.grpc_tls_client_ca,
+            grpc_key_acl: inner.grpc_key_acl,
+            grpc_disable_root: inner.grpc_disable_root,
+            grpc_max_concurrent_streams: inner.grpc_max_concurrent_streams,
+            grpc_initial_connection_window_size: inner.grpc_initial_connection_window_size,
+            grpc_initial_stream_window_size: inner.grpc_initial_stream_window_size,
+            grpc_accept_backlog: inner.grpc_accept_backlog,
+            grpc_write_log_sample_rate: inner.grpc_write_log_sample_rate,
+            grpc_apply_timeout_ms: inner.grpc_apply_timeout_ms,
+            grpc_enable_reflection: inner.grpc_enable_reflection,
+            grpc_user_credentials: inner.grpc_user_credentials,
+            grpc_disable_password_auth: inner.grpc_disable_password_auth,
+            grpc_cert_users: inner.grpc_cert_users,
             raft_config: inner.raft_config.into(),
         }
     }
@@ -267,6 +367,9 @@ pub struct ConfigViaEnv {
     pub metasrv_log_stderr_on: bool,
     pub metasrv_log_stderr_level: String,
     pub metasrv_log_stderr_format: String,
+    pub metasrv_log_tracing_on: bool,
+    pub metasrv_log_tracing_capture_log_level: String,
+    pub metasrv_log_tracing_otlp_endpoint: String,
     pub admin_api_address: String,
     pub admin_tls_server_cert: String,
     pub admin_tls_server_key: String,
@@ -274,6 +377,19 @@ pub struct ConfigViaEnv {
     pub metasrv_grpc_api_advertise_host: Option<String>,
     pub grpc_tls_server_cert: String,
     pub grpc_tls_server_key: String,
+    pub grpc_tls_client_ca: String,
+    pub metasrv_grpc_key_acl: String,
+    pub metasrv_grpc_disable_root: bool,
+    pub metasrv_grpc_max_concurrent_streams: Option<u32>,
+    pub metasrv_grpc_initial_connection_window_size: Option<u32>,
+    pub metasrv_grpc_initial_stream_window_size: Option<u32>,
+    pub metasrv_grpc_accept_backlog: u32,
+    pub metasrv_grpc_write_log_sample_rate: u64,
+    pub metasrv_grpc_apply_timeout_ms: u64,
+    pub metasrv_grpc_enable_reflection: bool,
+    pub metasrv_grpc_user_credentials: String,
+    pub metasrv_grpc_disable_password_auth: bool,
+    pub metasrv_grpc_cert_users: String,
 
     pub config_id: String,
     pub kvsrv_listen_host: String,
@@ -286,6 +402,11 @@ pub struct ConfigViaEnv {
     pub kvsrv_install_snapshot_timeout: u64,
     pub kvsrv_wait_leader_timeout: u64,
     pub raft_max_applied_log_to_keep: u64,
+    pub raft_max_payload_entries: u64,
+    pub raft_kv_read_cache_size: u64,
+    pub raft_max_inflight_proposals: u64,
+    pub raft_read_replica: bool,
+    pub raft_token_secret: String,
     pub kvsrv_single: bool,
     pub metasrv_join: Vec<String>,
     pub kvsrv_id: u64,
@@ -312,6 +433,9 @@ impl From<Config> for ConfigViaEnv {
             metasrv_log_stderr_on: cfg.log.stderr.stderr_on,
             metasrv_log_stderr_level: cfg.log.stderr.stderr_level,
             metasrv_log_stderr_format: cfg.log.stderr.stderr_format,
+            metasrv_log_tracing_on: cfg.log.tracing.tracing_on,
+            metasrv_log_tracing_capture_log_level: cfg.log.tracing.tracing_capture_log_level,
+            metasrv_log_tracing_otlp_endpoint: cfg.log.tracing.tracing_otlp_endpoint,
             admin_api_address: cfg.admin_api_address,
             admin_tls_server_cert: cfg.admin_tls_server_cert,
             admin_tls_server_key: cfg.admin_tls_server_key,
@@ -319,6 +443,19 @@ impl From<Config> for ConfigViaEnv {
             metasrv_grpc_api_advertise_host: cfg.grpc_api_advertise_host,
             grpc_tls_server_cert: cfg.grpc_tls_server_cert,
             grpc_tls_server_key: cfg.grpc_tls_server_key,
+            grpc_tls_client_ca: cfg.grpc_tls_client_ca,
+            metasrv_grpc_key_acl: cfg.grpc_key_acl,
+            metasrv_grpc_disable_root: cfg.grpc_disable_root,
+            metasrv_grpc_max_concurrent_streams: cfg.grpc_max_concurrent_streams,
+            metasrv_grpc_initial_connection_window_size: cfg.grpc_initial_connection_window_size,
+            metasrv_grpc_initial_stream_window_size: cfg.grpc_initial_stream_window_size,
+            metasrv_grpc_accept_backlog: cfg.grpc_accept_backlog,
+            metasrv_grpc_write_log_sample_rate: cfg.grpc_write_log_sample_rate,
+            metasrv_grpc_apply_timeout_ms: cfg.grpc_apply_timeout_ms,
+            metasrv_grpc_enable_reflection: cfg.grpc_enable_reflection,
+            metasrv_grpc_user_credentials: cfg.grpc_user_credentials,
+            metasrv_grpc_disable_password_auth: cfg.grpc_disable_password_auth,
+            metasrv_grpc_cert_users: cfg.grpc_cert_users,
             config_id: cfg.raft_config.config_id,
             kvsrv_listen_host: cfg.raft_config.raft_listen_host,
             kvsrv_advertise_host: cfg.raft_config.raft_advertise_host,
@@ -330,6 +467,11 @@ impl From<Config> for ConfigViaEnv {
             kvsrv_install_snapshot_timeout: cfg.raft_config.install_snapshot_timeout,
             kvsrv_wait_leader_timeout: cfg.raft_config.wait_leader_timeout,
             raft_max_applied_log_to_keep: cfg.raft_config.max_applied_log_to_keep,
+            raft_max_payload_entries: cfg.raft_config.max_payload_entries,
+            raft_kv_read_cache_size: cfg.raft_config.kv_read_cache_size,
+            raft_max_inflight_proposals: cfg.raft_config.max_inflight_proposals,
+            raft_read_replica: cfg.raft_config.read_replica,
+            raft_token_secret: cfg.raft_config.token_secret,
             kvsrv_single: cfg.raft_config.single,
             metasrv_join: cfg.raft_config.join,
             kvsrv_id: cfg.raft_config.id,
@@ -355,6 +497,11 @@ impl Into<Config> for ConfigViaEnv {
             install_snapshot_timeout: self.kvsrv_install_snapshot_timeout,
             wait_leader_timeout: self.kvsrv_wait_leader_timeout,
             max_applied_log_to_keep: self.raft_max_applied_log_to_keep,
+            max_payload_entries: self.raft_max_payload_entries,
+            kv_read_cache_size: self.raft_kv_read_cache_size,
+            max_inflight_proposals: self.raft_max_inflight_proposals,
+            read_replica: self.raft_read_replica,
+            token_secret: self.raft_token_secret,
             single: self.kvsrv_single,
             join: self.metasrv_join,
             // Do not allow to leave via environment variable
@@ -377,6 +524,11 @@ impl Into<Config> for ConfigViaEnv {
                 stderr_level: self.metasrv_log_stderr_level,
                 stderr_format: self.metasrv_log_stderr_format,
             },
+            tracing: TracingConfig {
+                tracing_on: self.metasrv_log_tracing_on,
+                tracing_capture_log_level: self.metasrv_log_tracing_capture_log_level,
+                tracing_otlp_endpoint: self.metasrv_log_tracing_otlp_endpoint,
+            },
         };
 
         Config {
@@ -399,6 +551,19 @@ impl Into<Config> for ConfigViaEnv {
             grpc_api_advertise_host: self.metasrv_grpc_api_advertise_host,
             grpc_tls_server_cert: self.grpc_tls_server_cert,
             grpc_tls_server_key: self.grpc_tls_server_key,
+            grpc_tls_client_ca: self.grpc_tls_client_ca,
+            grpc_key_acl: self.metasrv_grpc_key_acl,
+            grpc_disable_root: self.metasrv_grpc_disable_root,
+            grpc_max_concurrent_streams: self.metasrv_grpc_max_concurrent_streams,
+            grpc_initial_connection_window_size: self.metasrv_grpc_initial_connection_window_size,
+            grpc_initial_stream_window_size: self.metasrv_grpc_initial_stream_window_size,
+            grpc_accept_backlog: self.metasrv_grpc_accept_backlog,
+            grpc_write_log_sample_rate: self.metasrv_grpc_write_log_sample_rate,
+            grpc_apply_timeout_ms: self.metasrv_grpc_apply_timeout_ms,
+            grpc_enable_reflection: self.metasrv_grpc_enable_reflection,
+            grpc_user_credentials: self.metasrv_grpc_user_credentials,
+            grpc_disable_password_auth: self.metasrv_grpc_disable_password_auth,
+            grpc_cert_users: self.metasrv_grpc_cert_users,
             raft_config,
         }
     }
@@ -457,6 +622,33 @@ pub struct RaftConfig {
     #[clap(long, default_value = "1000")]
     pub max_applied_log_to_keep: u64,
 
+    /// The max number of log entries a leader sends to a follower in one append-entries request.
+    /// A lagging follower catches up in batches of this size, thus it stays responsive to heartbeats.
+    #[clap(long, default_value = "300")]
+    pub max_payload_entries: u64,
+
+    /// The max number of keys kept in the node-local cache for `get_kv` reads.
+    /// The cache is disabled if it is 0.
+    #[clap(long, default_value = "0")]
+    pub kv_read_cache_size: u64,
+
+    /// The max number of writes a leader proposes to raft at the same time.
+    /// Writes beyond it wait and are proposed in the order of their priority.
+    #[clap(long, default_value = "256")]
+    pub max_inflight_proposals: u64,
+
+    /// Run databend-meta as a read replica.
+    /// It joins a cluster as a learner that never votes, serves reads from its local, possibly stale, state machine,
+    /// and rejects writes.
+    #[clap(long)]
+    pub read_replica: bool,
+
+    /// The secret to sign and verify the tokens issued in gRPC handshake, shared by all nodes in a cluster.
+    /// A leadership transfer and a JWT handshake require every node to be configured with the same secret.
+    /// If it is empty, every node generates a random one and JWT handshake is disabled.
+    #[clap(long, default_value = "")]
+    pub token_secret: String,
+
     /// Start databend-meta in single node mode.
     /// It initialize a single node cluster, if meta data is not initialized.
     /// If on-disk data is already initialized, this argument has no effect.
@@ -521,6 +713,11 @@ impl From<RaftConfig> for InnerRaftConfig {
             heartbeat_interval: x.heartbeat_interval,
             install_snapshot_timeout: x.install_snapshot_timeout,
             max_applied_log_to_keep: x.max_applied_log_to_keep,
+            max_payload_entries: x.max_payload_entries,
+            kv_read_cache_size: x.kv_read_cache_size,
+            max_inflight_proposals: x.max_inflight_proposals,
+            read_replica: x.read_replica,
+            token_secret: x.token_secret,
             single: x.single,
             join: x.join,
             leave_via: x.leave_via,
@@ -546,6 +743,11 @@ impl From<InnerRaftConfig> for RaftConfig {
             heartbeat_interval: inner.heartbeat_interval,
             install_snapshot_timeout: inner.install_snapshot_timeout,
             max_applied_log_to_keep: inner.max_applied_log_to_keep,
+            max_payload_entries: inner.max_payload_entries,
+            kv_read_cache_size: inner.kv_read_cache_size,
+            max_inflight_proposals: inner.max_inflight_proposals,
+            read_replica: inner.read_replica,
+            token_secret: inner.token_secret,
             single: inner.single,
             join: inner.join,
             leave_via: inner.leave_via,
@@ -566,6 +768,9 @@ pub struct LogConfig {
 
     #[clap(flatten)]
     pub stderr: StderrLogConfig,
+
+    #[clap(flatten)]
+    pub tracing: TracingConfig,
 }
 
 impl Default for LogConfig {
@@ -584,7 +789,7 @@ impl Into<InnerLogConfig> for LogConfig {
                 on: false,
                 dir: "".to_string(),
             },
-            tracing: TracingConfig::default(),
+            tracing: self.tracing.into(),
         }
     }
 }
@@ -594,6 +799,7 @@ impl From<InnerLogConfig> for LogConfig {
         Self {
             file: inner.file.into(),
             stderr: inner.stderr.into(),
+            tracing: inner.tracing.into(),
         }
     }
 }
@@ -695,3 +901,53 @@ impl From<InnerStderrLogConfig> for StderrLogConfig {
         }
     }
 }
+
+/// Export the spans of meta-service RPC handlers to an OTLP collector,
+/// so that a request can be traced across databend-query and databend-meta.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Args)]
+#[serde(default)]
+pub struct TracingConfig {
+    #[clap(long = "log-tracing-on", default_value = "false", action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    #[serde(rename = "on")]
+    pub tracing_on: bool,
+
+    /// Tracing log level <DEBUG|TRACE|INFO|WARN|ERROR>
+    #[clap(long = "log-tracing-level", default_value = "INFO")]
+    #[serde(rename = "capture_log_level")]
+    pub tracing_capture_log_level: String,
+
+    /// Tracing otlp endpoint
+    #[clap(
+        long = "log-tracing-otlp-endpoint",
+        default_value = "http://localhost:4317"
+    )]
+    #[serde(rename = "otlp_endpoint")]
+    pub tracing_otlp_endpoint: String,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        InnerTracingConfig::default().into()
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<InnerTracingConfig> for TracingConfig {
+    fn into(self) -> InnerTracingConfig {
+        InnerTracingConfig {
+            on: self.tracing_on,
+            capture_log_level: self.tracing_capture_log_level,
+            otlp_endpoint: self.tracing_otlp_endpoint,
+        }
+    }
+}
+
+impl From<InnerTracingConfig> for TracingConfig {
+    fn from(inner: InnerTracingConfig) -> Self {
+        Self {
+            tracing_on: inner.on,
+            tracing_capture_log_level: inner.capture_log_level,
+            tracing_otlp_endpoint: inner.otlp_endpoint,
+        }
+    }
+}
@@ -15,9 +15,30 @@
 //! Helper functions for handling grpc.
 
 use std::error::Error;
+use std::future::Future;
+use std::time::Duration;
 
+use common_base::base::tokio;
+use common_grpc::GrpcClaim;
+use common_grpc::GrpcToken;
 use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::GrpcConfig;
+use tonic::metadata::MetadataMap;
+
+use crate::api::grpc::key_acl::KeyAcl;
+
+/// The max time to handle a request that may be forwarded to the leader,
+/// if the client does not specify a deadline.
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The max size in bytes of the json payload of a forwarded request.
+///
+/// A forwarded request wraps a client request, which is already bounded by the grpc message size.
+pub const DEFAULT_MAX_FORWARD_REQUEST_SIZE: usize = GrpcConfig::MAX_DECODING_SIZE;
+
+/// The request metadata key of the token a client gets in handshake.
+pub const AUTH_TOKEN_KEY: &str = "auth-token-bin";
 
 pub struct GrpcHelper;
 
@@ -30,6 +51,27 @@ impl GrpcHelper {
         Ok(req)
     }
 
+    /// Same as [`Self::parse_req`], except that a payload larger than `max_size` is rejected before parsing.
+    ///
+    /// A too deeply nested payload is rejected by the recursion limit of `serde_json`.
+    pub fn parse_req_with_limit<T>(
+        request: tonic::Request<RaftRequest>,
+        max_size: usize,
+    ) -> Result<T, tonic::Status>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let size = request.get_ref().data.len();
+        if size > max_size {
+            return Err(tonic::Status::invalid_argument(format!(
+                "request payload too large: {} bytes, max: {} bytes",
+                size, max_size
+            )));
+        }
+
+        Self::parse_req(request)
+    }
+
     /// Create an Ok response for raft API.
     pub fn ok_response<D>(d: D) -> Result<tonic::Response<RaftReply>, tonic::Status>
     where D: serde::Serialize {
@@ -37,6 +79,8 @@ impl GrpcHelper {
         let reply = RaftReply {
             data,
             error: "".to_string(),
+            compressed_data: vec![],
+            binary_data: vec![],
         };
         Ok(tonic::Response::new(reply))
     }
@@ -50,4 +94,75 @@ impl GrpcHelper {
     pub fn internal_err(e: impl Error) -> tonic::Status {
         tonic::Status::internal(e.to_string())
     }
+
+    /// Verify the token in the request metadata and return the claim in it.
+    pub fn verify_token(
+        token: &GrpcToken,
+        metadata: &MetadataMap,
+    ) -> Result<GrpcClaim, tonic::Status> {
+        let t = metadata
+            .get_bin(AUTH_TOKEN_KEY)
+            .and_then(|v| v.to_bytes().ok())
+            .and_then(|b| String::from_utf8(b.to_vec()).ok())
+            .ok_or_else(|| tonic::Status::unauthenticated("Error auth-token-bin is empty"))?;
+
+        let claim = token.try_verify_token(t.clone()).map_err(|e| {
+            tonic::Status::unauthenticated(format!("token verify failed: {}, {}", t, e))
+        })?;
+        Ok(claim)
+    }
+
+    /// Return an error if the request is not sent by root, for an admin operation `action`.
+    pub fn check_root(claim: &GrpcClaim, action: &str) -> Result<(), tonic::Status> {
+        if claim.username != KeyAcl::ROOT {
+            return Err(tonic::Status::permission_denied(format!(
+                "user {} is not allowed to {}",
+                claim.username, action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the time budget of a request from the `grpc-timeout` header set by the client,
+    /// or use the `default` if it is absent or malformed.
+    pub fn request_timeout(metadata: &MetadataMap, default: Duration) -> Duration {
+        metadata
+            .get("grpc-timeout")
+            .and_then(|v| v.to_str().ok())
+            .and_then(Self::parse_grpc_timeout)
+            .unwrap_or(default)
+    }
+
+    /// Parse a `grpc-timeout` header value, such as `100m` or `5S`.
+    ///
+    /// See: https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md
+    pub fn parse_grpc_timeout(v: &str) -> Option<Duration> {
+        if v.len() < 2 || v.len() > 9 {
+            return None;
+        }
+
+        let (digits, unit) = v.split_at(v.len() - 1);
+        let n: u64 = digits.parse().ok()?;
+
+        let d = match unit {
+            "H" => Duration::from_secs(n * 60 * 60),
+            "M" => Duration::from_secs(n * 60),
+            "S" => Duration::from_secs(n),
+            "m" => Duration::from_millis(n),
+            "u" => Duration::from_micros(n),
+            "n" => Duration::from_nanos(n),
+            _ => return None,
+        };
+        Some(d)
+    }
+
+    /// Run a request handler with a deadline, and return a deadline-exceeded error if it expires.
+    pub async fn with_timeout<T>(
+        timeout: Duration,
+        f: impl Future<Output = Result<T, tonic::Status>>,
+    ) -> Result<T, tonic::Status> {
+        tokio::time::timeout(timeout, f).await.map_err(|_elapsed| {
+            tonic::Status::deadline_exceeded(format!("request timeout after {:?}", timeout))
+        })?
+    }
 }
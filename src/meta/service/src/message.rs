@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use anyerror::AnyError;
+use common_meta_api::reply::reply_to_api_result;
 use common_meta_client::MetaGrpcReadReq;
 use common_meta_kvapi::kvapi::GetKVReply;
 use common_meta_kvapi::kvapi::GetKVReq;
@@ -20,12 +21,20 @@ use common_meta_kvapi::kvapi::ListKVReply;
 use common_meta_kvapi::kvapi::ListKVReq;
 use common_meta_kvapi::kvapi::MGetKVReply;
 use common_meta_kvapi::kvapi::MGetKVReq;
+use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::AppliedState;
 use common_meta_types::Endpoint;
+use common_meta_types::InvalidReply;
 use common_meta_types::LogEntry;
 use common_meta_types::MetaAPIError;
 use common_meta_types::NodeId;
+use common_meta_types::UpsertKV;
+use tonic::metadata::MetadataMap;
+use tonic::metadata::MetadataValue;
+
+use crate::grpc_helper::GrpcHelper;
+use crate::meta_service::WritePriority;
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, PartialEq, Eq)]
 pub struct JoinRequest {
@@ -37,6 +46,10 @@ pub struct JoinRequest {
     pub grpc_api_addr: String,
 
     pub grpc_api_advertise_address: Option<String>,
+
+    /// Join as a learner that never becomes a voter, e.g., a read replica.
+    #[serde(default)]
+    pub learner: bool,
 }
 
 impl JoinRequest {
@@ -52,6 +65,11 @@ impl JoinRequest {
             ..Default::default()
         }
     }
+
+    pub fn with_learner(mut self, learner: bool) -> Self {
+        self.learner = learner;
+        self
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -77,9 +95,30 @@ pub enum ForwardRequestBody {
 
     Write(LogEntry),
 
+    /// Write a log entry and reply with the index of its raft log, as `ForwardResponse::AppliedStateAt`.
+    ///
+    /// The entry is proposed before the writes of a lower `priority` that are waiting on the leader.
+    #[from(ignore)]
+    #[try_into(ignore)]
+    WriteWithLogIndex {
+        entry: LogEntry,
+        priority: WritePriority,
+    },
+
+    /// Evaluate an upsert on the leader without proposing it to raft.
+    DryRunUpsertKV(UpsertKV),
+
     GetKV(GetKVReq),
     MGetKV(MGetKVReq),
     ListKV(ListKVReq),
+
+    CountPrefix(CountPrefixReq),
+}
+
+/// Count the keys that start with `prefix`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CountPrefixReq {
+    pub prefix: String,
 }
 
 /// A request that is forwarded from one raft node to another
@@ -136,15 +175,154 @@ pub enum ForwardResponse {
     Leave(()),
     AppliedState(AppliedState),
 
+    /// The applied state of a write and the index of the raft log it is applied at.
+    #[try_into(ignore)]
+    AppliedStateAt {
+        log_index: u64,
+        state: AppliedState,
+    },
+
     GetKV(GetKVReply),
     MGetKV(MGetKVReply),
     ListKV(ListKVReply),
+
+    CountPrefix(u64),
+}
+
+/// The metadata key to specify the encoding of a forwarded request and its reply.
+///
+/// The server echoes the encoding it used in the reply metadata with the same key.
+pub const FORWARD_CONTENT_TYPE: &str = "forward-content-type";
+
+/// The encoding of the payload of a forwarded request and its reply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ForwardContentType {
+    /// The payload is json in `RaftRequest.data` and `RaftReply.data`.
+    #[default]
+    Json,
+
+    /// The payload is MessagePack in `RaftRequest.binary_data` and `RaftReply.binary_data`.
+    ///
+    /// An error in the reply is still json in `RaftReply.error`.
+    ///
+    /// Like json, it is not compressed: reply compression is negotiated by the handshake of a
+    /// client on `MetaService`, while a forwarded request is sent between meta nodes by
+    /// `RaftService::forward`, which has no handshake.
+    MsgPack,
+}
+
+impl ForwardContentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForwardContentType::Json => "json",
+            ForwardContentType::MsgPack => "msgpack",
+        }
+    }
+
+    /// Get the content type from request metadata, `Json` if it is absent.
+    pub fn from_metadata(metadata: &MetadataMap) -> Result<Self, tonic::Status> {
+        let Some(v) = metadata.get(FORWARD_CONTENT_TYPE) else {
+            return Ok(Self::default());
+        };
+
+        match v.to_str() {
+            Ok("json") => Ok(ForwardContentType::Json),
+            Ok("msgpack") => Ok(ForwardContentType::MsgPack),
+            _ => Err(tonic::Status::invalid_argument(format!(
+                "unsupported {}: {:?}",
+                FORWARD_CONTENT_TYPE, v
+            ))),
+        }
+    }
+
+    /// Set this content type in the metadata of a request or a reply.
+    pub fn set_metadata(&self, metadata: &mut MetadataMap) {
+        metadata.insert(
+            FORWARD_CONTENT_TYPE,
+            MetadataValue::from_static(self.as_str()),
+        );
+    }
+
+    /// Build a request with the payload encoded in this content type.
+    pub fn encode_req<T>(&self, req: &T) -> tonic::Request<RaftRequest>
+    where T: serde::Serialize {
+        let raft_req = match self {
+            ForwardContentType::Json => RaftRequest {
+                data: serde_json::to_string(req).expect("fail to serialize req"),
+                binary_data: vec![],
+            },
+            ForwardContentType::MsgPack => RaftRequest {
+                data: "".to_string(),
+                binary_data: rmp_serde::to_vec_named(req).expect("fail to serialize req"),
+            },
+        };
+
+        let mut request = tonic::Request::new(raft_req);
+        self.set_metadata(request.metadata_mut());
+        request
+    }
+
+    /// Decode a request payload in this content type.
+    ///
+    /// A payload larger than `max_size` is rejected before parsing.
+    pub fn decode_req<T>(
+        &self,
+        request: tonic::Request<RaftRequest>,
+        max_size: usize,
+    ) -> Result<T, tonic::Status>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        match self {
+            ForwardContentType::Json => GrpcHelper::parse_req_with_limit(request, max_size),
+            ForwardContentType::MsgPack => {
+                let raft_req = request.into_inner();
+
+                let size = raft_req.binary_data.len();
+                if size > max_size {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "request payload too large: {} bytes, max: {} bytes",
+                        size, max_size
+                    )));
+                }
+
+                rmp_serde::from_slice(&raft_req.binary_data).map_err(GrpcHelper::invalid_arg)
+            }
+        }
+    }
+
+    /// Build a reply with the successful result encoded in this content type.
+    pub fn encode_reply<T>(&self, res: Result<T, MetaAPIError>) -> RaftReply
+    where T: serde::Serialize {
+        match (self, res) {
+            (ForwardContentType::MsgPack, Ok(x)) => RaftReply {
+                data: "".to_string(),
+                error: "".to_string(),
+                compressed_data: vec![],
+                binary_data: rmp_serde::to_vec_named(&x).expect("fail to serialize resp"),
+            },
+            (_, res) => res.into(),
+        }
+    }
+
+    /// Decode a reply that is built by [`Self::encode_reply`].
+    pub fn decode_reply<T>(&self, reply: RaftReply) -> Result<T, MetaAPIError>
+    where T: serde::de::DeserializeOwned {
+        if reply.binary_data.is_empty() {
+            return reply_to_api_result(reply);
+        }
+
+        let res: T = rmp_serde::from_slice(&reply.binary_data)
+            .map_err(|e| InvalidReply::new("can not decode RaftReply.binary_data", &e))?;
+        Ok(res)
+    }
 }
 
 impl tonic::IntoRequest<RaftRequest> for ForwardRequest<ForwardRequestBody> {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(&self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -154,6 +332,7 @@ impl tonic::IntoRequest<RaftRequest> for ForwardRequest<MetaGrpcReadReq> {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(&self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
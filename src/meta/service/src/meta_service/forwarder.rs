@@ -16,6 +16,7 @@
 
 use common_meta_api::reply::reply_to_api_result;
 use common_meta_client::MetaGrpcReadReq;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
 use common_meta_types::protobuf::raft_service_client::RaftServiceClient;
 use common_meta_types::protobuf::StreamItem;
 use common_meta_types::ConnectionError;
@@ -39,6 +40,7 @@ use crate::store::RaftStore;
 
 /// Handle a request locally if it is leader. Otherwise, forward it to the leader.
 pub struct MetaForwarder<'a> {
+    meta_node: &'a MetaNode,
     sto: &'a RaftStore,
     #[allow(dead_code)]
     raft: &'a MetaRaft,
@@ -47,12 +49,33 @@ pub struct MetaForwarder<'a> {
 impl<'a> MetaForwarder<'a> {
     pub fn new(meta_node: &'a MetaNode) -> Self {
         Self {
+            meta_node,
             sto: &meta_node.sto,
             raft: &meta_node.raft,
         }
     }
 
-    async fn new_raft_client(
+    /// Replace a request body that a leader of an older build can not decode with a plain `Write`,
+    /// until every node is upgraded, i.e., the cluster version is at least [`CLUSTER_VERSION_V1`].
+    ///
+    /// The leader then replies with `ForwardResponse::AppliedState`, which has no log index,
+    /// and the write is proposed with the default priority.
+    async fn compatible_body(&self, body: ForwardRequestBody) -> ForwardRequestBody {
+        if !matches!(body, ForwardRequestBody::WriteWithLogIndex { .. }) {
+            return body;
+        }
+
+        if self.meta_node.cluster_version().await >= CLUSTER_VERSION_V1 {
+            return body;
+        }
+
+        match body {
+            ForwardRequestBody::WriteWithLogIndex { entry, .. } => ForwardRequestBody::Write(entry),
+            _ => body,
+        }
+    }
+
+    pub(crate) async fn new_raft_client(
         &self,
         target: &NodeId,
     ) -> Result<(Endpoint, RaftServiceClient<Channel>), MetaNetworkError> {
@@ -89,6 +112,11 @@ impl<'a> Forwarder<ForwardRequestBody> for MetaForwarder<'a> {
     ) -> Result<ForwardResponse, ForwardRPCError> {
         debug!("forward ForwardRequest to: {} {:?}", target, req);
 
+        let req = ForwardRequest {
+            forward_to_leader: req.forward_to_leader,
+            body: self.compatible_body(req.body).await,
+        };
+
         let (endpoint, mut client) = self.new_raft_client(&target).await?;
 
         let resp = client.forward(req).await.map_err(|e| {
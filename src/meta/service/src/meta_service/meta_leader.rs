@@ -13,27 +13,36 @@
 // limitations under the License.
 
 use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
 
+use common_base::base::tokio;
 use common_base::base::tokio::sync::RwLockReadGuard;
 use common_meta_client::MetaGrpcReadReq;
 use common_meta_kvapi::kvapi::KVApi;
 use common_meta_raft_store::sm_v002::leveled_store::sys_data_api::SysDataApiRO;
 use common_meta_raft_store::sm_v002::SMV002;
+use common_meta_sled_store::openraft::error::CheckIsLeaderError;
 use common_meta_sled_store::openraft::ChangeMembers;
 use common_meta_stoerr::MetaStorageError;
 use common_meta_types::protobuf::StreamItem;
 use common_meta_types::AppliedState;
+use common_meta_types::Change;
 use common_meta_types::ClientWriteError;
 use common_meta_types::Cmd;
+use common_meta_types::ForwardToLeader;
 use common_meta_types::LogEntry;
+use common_meta_types::MatchSeqExt;
 use common_meta_types::MembershipNode;
 use common_meta_types::MetaDataError;
 use common_meta_types::MetaDataReadError;
 use common_meta_types::MetaOperationError;
 use common_meta_types::Node;
 use common_meta_types::NodeId;
+use common_meta_types::Operation;
 use common_meta_types::RaftError;
 use common_meta_types::SeqV;
+use common_meta_types::UpsertKV;
 use common_metrics::count::Count;
 use futures::StreamExt;
 use log::as_debug;
@@ -49,7 +58,9 @@ use crate::message::ForwardResponse;
 use crate::message::JoinRequest;
 use crate::message::LeaveRequest;
 use crate::meta_service::meta_node::MetaRaft;
+use crate::meta_service::submit_queue::SubmitQueue;
 use crate::meta_service::MetaNode;
+use crate::meta_service::WritePriority;
 use crate::metrics::server_metrics;
 use crate::metrics::ProposalPending;
 use crate::request_handling::Handler;
@@ -62,6 +73,7 @@ use crate::store::RaftStore;
 pub struct MetaLeader<'a> {
     sto: &'a RaftStore,
     raft: &'a MetaRaft,
+    submit_queue: &'a Arc<SubmitQueue>,
 }
 
 #[async_trait::async_trait]
@@ -88,10 +100,18 @@ impl<'a> Handler<ForwardRequestBody> for MetaLeader<'a> {
                 let res = self.write(entry.clone()).await?;
                 Ok(ForwardResponse::AppliedState(res))
             }
+            ForwardRequestBody::WriteWithLogIndex { entry, priority } => {
+                let (state, log_index) = self.write_with_priority(entry, priority).await?;
+                Ok(ForwardResponse::AppliedStateAt { log_index, state })
+            }
+            ForwardRequestBody::DryRunUpsertKV(upsert_kv) => {
+                let res = self.dry_run_upsert_kv(upsert_kv).await;
+                Ok(ForwardResponse::AppliedState(res))
+            }
 
             ForwardRequestBody::GetKV(req) => {
                 let sm = self.get_state_machine().await;
-                let res = sm.kv_api().get_kv(&req.key).await.unwrap();
+                let res = self.sto.kv_read_cache.get_kv(&sm, &req.key).await;
                 Ok(ForwardResponse::GetKV(res))
             }
             ForwardRequestBody::MGetKV(req) => {
@@ -104,6 +124,17 @@ impl<'a> Handler<ForwardRequestBody> for MetaLeader<'a> {
                 let res = sm.kv_api().prefix_list_kv(&req.prefix).await.unwrap();
                 Ok(ForwardResponse::ListKV(res))
             }
+            ForwardRequestBody::CountPrefix(req) => {
+                let sm = self.get_state_machine().await;
+                let count = sm.kv_api().count_prefix(&req.prefix).await.map_err(|e| {
+                    MetaDataError::ReadError(MetaDataReadError::new(
+                        "count_prefix",
+                        &req.prefix,
+                        &e,
+                    ))
+                })?;
+                Ok(ForwardResponse::CountPrefix(count))
+            }
         }
     }
 }
@@ -122,8 +153,7 @@ impl<'a> Handler<MetaGrpcReadReq> for MetaLeader<'a> {
 
         match req.body {
             MetaGrpcReadReq::GetKV(req) => {
-                // safe unwrap(): Infallible
-                let got = kv_api.get_kv(&req.key).await.unwrap();
+                let got = self.sto.kv_read_cache.get_kv(&sm, &req.key).await;
 
                 let item = StreamItem::from((req.key.clone(), got));
                 let strm = futures::stream::iter([Ok(item)]);
@@ -166,13 +196,14 @@ impl<'a> MetaLeader<'a> {
         MetaLeader {
             sto: &meta_node.sto,
             raft: &meta_node.raft,
+            submit_queue: &meta_node.submit_queue,
         }
     }
 
     /// Join a new node to the cluster.
     ///
     /// - Adds the node to cluster as a non-voter persistently and starts replication.
-    /// - Adds the node to membership to let it become a voter.
+    /// - Adds the node to membership to let it become a voter, unless it joins as a learner.
     ///
     /// If the node is already in cluster membership, it still returns Ok.
     #[minitrace::trace]
@@ -188,6 +219,10 @@ impl<'a> MetaLeader<'a> {
             return Ok(());
         }
 
+        if req.learner && membership.learner_ids().any(|id| id == node_id) {
+            return Ok(());
+        }
+
         let ent = LogEntry {
             txid: None,
             time_ms: None,
@@ -198,14 +233,15 @@ impl<'a> MetaLeader<'a> {
                 overriding: false,
             },
         };
-        self.write(ent).await?;
+        self.write_with_priority(ent, WritePriority::High).await?;
 
-        self.raft
-            .change_membership(
-                ChangeMembers::AddVoters(btreemap! {node_id=>MembershipNode{}}),
-                false,
-            )
-            .await?;
+        let change = if req.learner {
+            ChangeMembers::AddNodes(btreemap! {node_id=>MembershipNode{}})
+        } else {
+            ChangeMembers::AddVoters(btreemap! {node_id=>MembershipNode{}})
+        };
+
+        self.raft.change_membership(change, false).await?;
         Ok(())
     }
 
@@ -241,19 +277,121 @@ impl<'a> MetaLeader<'a> {
             time_ms: None,
             cmd: Cmd::RemoveNode { node_id },
         };
-        self.write(ent).await?;
+        self.write_with_priority(ent, WritePriority::High).await?;
 
         Ok(())
     }
 
+    /// Evaluate an upsert against the current state machine, without proposing it to raft.
+    ///
+    /// It returns what `write()` would return if no other write is applied in between:
+    /// a failed `MatchSeq` leaves the record unchanged,
+    /// and the seq of the new record is predicted as the next seq.
+    #[minitrace::trace]
+    pub async fn dry_run_upsert_kv(&self, upsert_kv: UpsertKV) -> AppliedState {
+        let sm = self.get_state_machine().await;
+
+        // safe unwrap(): Infallible
+        let prev = sm.kv_api().get_kv(&upsert_kv.key).await.unwrap();
+
+        if upsert_kv.seq.match_seq(&prev).is_err() {
+            return AppliedState::KV(Change::new(prev.clone(), prev));
+        }
+
+        let next_seq = sm.sys_data_ref().curr_seq() + 1;
+        let meta = upsert_kv.value_meta;
+
+        let result = match upsert_kv.value {
+            Operation::Update(v) => Some(SeqV::with_meta(next_seq, meta, v)),
+            Operation::Delete => None,
+            Operation::AsIs => prev.clone().map(|x| x.set_seq(next_seq).set_meta(meta)),
+        };
+
+        // An upsert with an expiration time in the past deletes the record at once.
+        let result = result.filter(|x| x.get_expire_at() >= SeqV::<()>::now_ms());
+
+        debug!(
+            "dry run upsert: {}; prev: {:?}; res: {:?}",
+            upsert_kv.key, prev, result
+        );
+
+        AppliedState::KV(Change::new(prev, result))
+    }
+
+    /// Confirm with a quorum that this node is still the leader.
+    ///
+    /// A leader that is partitioned away believes it is the leader until it sees a greater vote.
+    /// If the leadership can not be confirmed within the min election timeout,
+    /// another leader may have been elected, and this node returns a ForwardToLeader error without a known leader.
+    #[minitrace::trace]
+    pub async fn ensure_leader(&self) -> Result<(), RaftError<ClientWriteError>> {
+        let unknown_leader = || {
+            RaftError::APIError(ClientWriteError::ForwardToLeader(ForwardToLeader {
+                leader_id: None,
+                leader_node: None,
+            }))
+        };
+
+        let timeout = Duration::from_millis(self.sto.config.election_timeout().0);
+
+        let res = tokio::time::timeout(timeout, self.raft.is_leader()).await;
+
+        match res {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(RaftError::APIError(CheckIsLeaderError::ForwardToLeader(to_leader)))) => Err(
+                RaftError::APIError(ClientWriteError::ForwardToLeader(to_leader)),
+            ),
+            Ok(Err(RaftError::APIError(CheckIsLeaderError::QuorumNotEnough(e)))) => {
+                info!("leadership is not confirmed: {}", e);
+                Err(unknown_leader())
+            }
+            Ok(Err(RaftError::Fatal(f))) => Err(RaftError::Fatal(f)),
+            Err(elapsed) => {
+                info!("leadership is not confirmed in {:?}: {}", timeout, elapsed);
+                Err(unknown_leader())
+            }
+        }
+    }
+
     /// Write a log through local raft node and return the states before and after applying the log.
     ///
+    /// The leadership is confirmed before proposing the log,
+    /// so that a stale leader fails fast instead of waiting for a log that will never be committed.
+    ///
     /// If the raft node is not a leader, it returns MetaRaftError::ForwardToLeader.
     #[minitrace::trace]
     pub async fn write(
         &self,
-        mut entry: LogEntry,
+        entry: LogEntry,
     ) -> Result<AppliedState, RaftError<ClientWriteError>> {
+        let (state, _log_index) = self.write_with_log_index(entry).await?;
+        Ok(state)
+    }
+
+    /// Write a log entry and return the applied state and the index of the raft log.
+    ///
+    /// A node that has applied up to the returned index sees the result of this write.
+    #[minitrace::trace]
+    pub async fn write_with_log_index(
+        &self,
+        entry: LogEntry,
+    ) -> Result<(AppliedState, u64), RaftError<ClientWriteError>> {
+        self.write_with_priority(entry, WritePriority::Normal).await
+    }
+
+    /// Write a log entry after the waiting writes of a higher priority are proposed,
+    /// and return the applied state and the index of the raft log.
+    #[minitrace::trace]
+    pub async fn write_with_priority(
+        &self,
+        mut entry: LogEntry,
+        priority: WritePriority,
+    ) -> Result<(AppliedState, u64), RaftError<ClientWriteError>> {
+        self.ensure_leader().await?;
+
+        // Wait in the submit queue if there are too many proposals in flight.
+        let _permit = self.submit_queue.acquire(priority).await;
+
         // Add consistent clock time to log entry.
         entry.time_ms = Some(SeqV::<()>::now_ms());
 
@@ -269,7 +407,7 @@ impl<'a> MetaLeader<'a> {
                     "raft.client_write res ok: log_id: {}, data: {}, membership: {:?}",
                     resp.log_id, resp.data, resp.membership
                 );
-                Ok(resp.data)
+                Ok((resp.data, resp.log_id.index))
             }
             Err(raft_err) => {
                 server_metrics::incr_proposals_failed();
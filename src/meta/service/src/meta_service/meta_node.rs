@@ -14,9 +14,14 @@
 
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::io;
 use std::net::Ipv4Addr;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -30,19 +35,35 @@ use common_base::base::tokio::time::sleep;
 use common_base::base::tokio::time::Instant;
 use common_grpc::ConnectionFactory;
 use common_grpc::DNSResolver;
+use common_grpc::GrpcToken;
 use common_meta_client::reply_to_api_result;
+use common_meta_client::MetaGrpcReadReq;
 use common_meta_client::RequestFor;
 use common_meta_raft_store::config::RaftConfig;
 use common_meta_raft_store::ondisk::DataVersion;
 use common_meta_raft_store::ondisk::DATA_VERSION;
 use common_meta_raft_store::sm_v002::leveled_store::sys_data_api::SysDataApiRO;
 use common_meta_sled_store::openraft;
+use common_meta_sled_store::openraft::metrics::WaitError;
 use common_meta_sled_store::openraft::storage::Adaptor;
 use common_meta_sled_store::openraft::ChangeMembers;
 use common_meta_stoerr::MetaStorageError;
+use common_meta_types::cluster_version::decode_cluster_version;
+use common_meta_types::cluster_version::CLUSTER_VERSION_KEY;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V0;
+use common_meta_types::cluster_version::MAX_CLUSTER_VERSION;
+use common_meta_types::protobuf as pb;
 use common_meta_types::protobuf::raft_service_client::RaftServiceClient;
 use common_meta_types::protobuf::raft_service_server::RaftServiceServer;
+use common_meta_types::protobuf::watch_request::FilterType;
+use common_meta_types::protobuf::Empty;
+use common_meta_types::protobuf::Event;
+use common_meta_types::protobuf::StreamItem;
 use common_meta_types::protobuf::WatchRequest;
+use common_meta_types::protobuf::WatchResponse;
+use common_meta_types::schema_version::decode_schema_version;
+use common_meta_types::schema_version::schema_version_key;
+use common_meta_types::schema_version::SCHEMA_VERSION_KEY_PREFIX;
 use common_meta_types::AppliedState;
 use common_meta_types::Cmd;
 use common_meta_types::CommittedLeaderId;
@@ -63,8 +84,11 @@ use common_meta_types::MetaStartupError;
 use common_meta_types::Node;
 use common_meta_types::NodeId;
 use common_meta_types::RaftMetrics;
+use common_meta_types::SeqV;
 use common_meta_types::TypeConfig;
+use common_meta_types::UpsertKV;
 use futures::channel::oneshot;
+use futures::TryStreamExt;
 use itertools::Itertools;
 use log::as_debug;
 use log::as_display;
@@ -79,8 +103,11 @@ use openraft::Config;
 use openraft::Raft;
 use openraft::ServerState;
 use openraft::SnapshotPolicy;
+use tonic::codegen::BoxStream;
+use tonic::metadata::BinaryMetadataValue;
 
 use crate::configs::Config as MetaConfig;
+use crate::grpc_helper::AUTH_TOKEN_KEY;
 use crate::message::ForwardRequest;
 use crate::message::ForwardRequestBody;
 use crate::message::ForwardResponse;
@@ -89,17 +116,21 @@ use crate::message::LeaveRequest;
 use crate::meta_service::errors::grpc_error_to_network_err;
 use crate::meta_service::forwarder::MetaForwarder;
 use crate::meta_service::meta_leader::MetaLeader;
+use crate::meta_service::submit_queue::SubmitQueue;
 use crate::meta_service::RaftServiceImpl;
+use crate::meta_service::WritePriority;
 use crate::metrics::server_metrics;
 use crate::network::Network;
 use crate::request_handling::Forwarder;
 use crate::request_handling::Handler;
+use crate::store::KVReadCacheInvalidator;
 use crate::store::RaftStore;
 use crate::version::METASRV_COMMIT_VERSION;
 use crate::watcher::DispatcherSender;
 use crate::watcher::EventDispatcher;
 use crate::watcher::EventDispatcherHandle;
 use crate::watcher::Watcher;
+use crate::watcher::WatcherId;
 use crate::watcher::WatcherSender;
 use crate::Opened;
 
@@ -125,6 +156,12 @@ pub struct MetaNodeStatus {
     /// Is this node a leader.
     pub is_leader: bool,
 
+    /// Is this node a learner, which does not vote and is not part of a quorum.
+    pub is_learner: bool,
+
+    /// Is this node configured as a read replica, see [`MetaNode::is_read_replica`].
+    pub read_replica: bool,
+
     /// Current term.
     pub current_term: u64,
 
@@ -160,6 +197,27 @@ pub struct MetaNodeStatus {
     pub last_seq: u64,
 }
 
+/// The max time to wait for a snapshot triggered by [`MetaNode::trigger_snapshot`] to be built.
+const TRIGGER_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The interval to re-check the progress of a leadership transfer.
+const TRANSFER_LEADER_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A node is catching up if it has applied fewer logs than the leader committed by more than this.
+pub const CATCHING_UP_APPLY_LAG: u64 = 1024;
+
+/// The max number of recent append-entries batches kept in [`MetaNode::append_entries_batches`].
+pub const MAX_APPEND_ENTRIES_BATCHES: usize = 1024;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TriggerSnapshotStatus {
+    /// A snapshot triggered earlier is still being built, thus no new snapshot is triggered.
+    pub in_progress: bool,
+
+    /// The last log id contained in the last built snapshot.
+    pub snapshot_last_log_id: Option<LogId>,
+}
+
 pub type LogStore = Adaptor<TypeConfig, RaftStore>;
 pub type SMStore = Adaptor<TypeConfig, RaftStore>;
 
@@ -175,6 +233,26 @@ pub struct MetaNode {
     pub running_rx: watch::Receiver<()>,
     pub join_handles: Mutex<Vec<JoinHandle<Result<(), AnyError>>>>,
     pub joined_tasks: AtomicI32,
+
+    /// Whether a snapshot triggered by [`MetaNode::trigger_snapshot`] is being built.
+    pub building_snapshot: AtomicBool,
+
+    /// The writes waiting to be proposed when this node is the leader.
+    pub submit_queue: Arc<SubmitQueue>,
+
+    /// The greatest committed log index a leader replicated to this node with append-entries.
+    ///
+    /// It is the leader's progress even if this node has not yet received the logs up to it.
+    pub leader_committed: AtomicU64,
+
+    /// The `(first log index, number of entries)` of the recent non-empty append-entries requests
+    /// this node received, the oldest first.
+    ///
+    /// At most [`MAX_APPEND_ENTRIES_BATCHES`] batches are kept.
+    pub append_entries_batches: std::sync::Mutex<VecDeque<(u64, u64)>>,
+
+    /// Issues and verifies the tokens of gRPC clients, shared by the meta service and the raft service.
+    pub grpc_token: GrpcToken,
 }
 
 impl Opened for MetaNode {
@@ -189,6 +267,8 @@ pub struct MetaNodeBuilder {
     sto: Option<RaftStore>,
     monitor_metrics: bool,
     endpoint: Option<Endpoint>,
+    grpc_token: Option<GrpcToken>,
+    max_inflight_proposals: usize,
 }
 
 impl MetaNodeBuilder {
@@ -207,6 +287,8 @@ impl MetaNodeBuilder {
             .take()
             .ok_or_else(|| MetaStartupError::InvalidConfig(String::from("sto is not set")))?;
 
+        let grpc_token = self.grpc_token.take().unwrap_or_else(GrpcToken::create);
+
         let net = Network::new(sto.clone());
 
         let (log_store, sm_store) = Adaptor::new(sto.clone());
@@ -222,7 +304,10 @@ impl MetaNodeBuilder {
 
         sto.get_state_machine()
             .await
-            .set_subscriber(Box::new(DispatcherSender(dispatcher_tx.clone())));
+            .set_subscriber(Box::new(KVReadCacheInvalidator {
+                cache: sto.kv_read_cache.clone(),
+                next: DispatcherSender(dispatcher_tx.clone()),
+            }));
 
         let mn = Arc::new(MetaNode {
             sto: sto.clone(),
@@ -232,6 +317,11 @@ impl MetaNodeBuilder {
             running_rx: rx,
             join_handles: Mutex::new(Vec::new()),
             joined_tasks: AtomicI32::new(1),
+            building_snapshot: AtomicBool::new(false),
+            submit_queue: Arc::new(SubmitQueue::new(self.max_inflight_proposals)),
+            leader_committed: AtomicU64::new(0),
+            append_entries_batches: std::sync::Mutex::new(VecDeque::new()),
+            grpc_token,
         });
 
         if self.monitor_metrics {
@@ -292,6 +382,17 @@ impl MetaNode {
             sto: None,
             monitor_metrics: true,
             endpoint: None,
+            grpc_token: Some(Self::new_grpc_token(config)),
+            max_inflight_proposals: config.max_inflight_proposals as usize,
+        }
+    }
+
+    /// Build the token issuer from the shared secret, or a random key if there is none.
+    pub fn new_grpc_token(config: &RaftConfig) -> GrpcToken {
+        if config.token_secret.is_empty() {
+            GrpcToken::create()
+        } else {
+            GrpcToken::create_with_secret(config.token_secret.as_bytes())
         }
     }
 
@@ -308,6 +409,7 @@ impl MetaNode {
             install_snapshot_timeout: config.install_snapshot_timeout,
             snapshot_policy: SnapshotPolicy::LogsSinceLast(config.snapshot_logs_since_last),
             max_in_snapshot_log_to_keep: config.max_applied_log_to_keep,
+            max_payload_entries: config.max_payload_entries,
             ..Default::default()
         }
         .validate()
@@ -727,11 +829,14 @@ impl MetaNode {
 
         let req = ForwardRequest {
             forward_to_leader: 1,
-            body: ForwardRequestBody::Join(JoinRequest::new(
-                conf.id,
-                advertise_endpoint.clone(),
-                grpc_api_advertise_address.clone(),
-            )),
+            body: ForwardRequestBody::Join(
+                JoinRequest::new(
+                    conf.id,
+                    advertise_endpoint.clone(),
+                    grpc_api_advertise_address.clone(),
+                )
+                .with_learner(conf.read_replica),
+            ),
         };
 
         let join_res = raft_client.forward(req.clone()).await;
@@ -889,6 +994,128 @@ impl MetaNode {
         nodes
     }
 
+    /// Build a snapshot on this node, and wait for it to include all logs applied so far.
+    ///
+    /// It only runs on the local node and is never forwarded.
+    /// If a snapshot triggered by a previous call is still being built, it returns at once with `in_progress` set.
+    #[minitrace::trace]
+    pub async fn trigger_snapshot(&self) -> Result<TriggerSnapshotStatus, AnyError> {
+        if self
+            .building_snapshot
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            let metrics = self.raft.metrics().borrow().clone();
+            return Ok(TriggerSnapshotStatus {
+                in_progress: true,
+                snapshot_last_log_id: metrics.snapshot,
+            });
+        }
+
+        let res = self.build_snapshot().await;
+        self.building_snapshot.store(false, Ordering::Release);
+
+        let snapshot_last_log_id = res?;
+        Ok(TriggerSnapshotStatus {
+            in_progress: false,
+            snapshot_last_log_id,
+        })
+    }
+
+    async fn build_snapshot(&self) -> Result<Option<LogId>, AnyError> {
+        let last_applied = self.raft.metrics().borrow().last_applied;
+
+        info!("trigger snapshot, last_applied: {:?}", last_applied);
+
+        self.raft
+            .trigger()
+            .snapshot()
+            .await
+            .map_err(|e| AnyError::new(&e))?;
+
+        let metrics = self
+            .raft
+            .wait(Some(TRIGGER_SNAPSHOT_TIMEOUT))
+            .metrics(
+                |m| m.snapshot >= last_applied,
+                format!("snapshot includes {:?}", last_applied),
+            )
+            .await
+            .map_err(|e| AnyError::new(&e))?;
+
+        Ok(metrics.snapshot)
+    }
+
+    /// Move leadership to the voter `to`, and return when `to` becomes the leader.
+    ///
+    /// It waits for `to` to replicate all the logs of the current leader,
+    /// then asks `to` to start an election at once.
+    /// `auth_token` is the token of a root client, which `to` verifies before starting an election,
+    /// thus every node must share the same [`RaftConfig::token_secret`].
+    /// If a round of election does not elect `to`, it retries.
+    /// The caller is responsible to bound the time it takes.
+    #[minitrace::trace]
+    pub async fn transfer_leader(
+        &self,
+        to: NodeId,
+        auth_token: BinaryMetadataValue,
+    ) -> Result<(), AnyError> {
+        info!("transfer leader to: {}", to);
+
+        let election_timeout = Duration::from_millis(self.sto.config.election_timeout().1);
+
+        loop {
+            let metrics = self.raft.metrics().borrow().clone();
+
+            if metrics.current_leader == Some(to) {
+                info!(
+                    "transfer leader to {} done, term: {}",
+                    to, metrics.current_term
+                );
+                return Ok(());
+            }
+
+            // The target can only be elected if its log is as up to date as the leader's.
+            if let Some(replication) = &metrics.replication {
+                let matched = replication.get(&to).copied().flatten();
+                if matched.map(|x| x.index) < metrics.last_log_index {
+                    debug!(
+                        "transfer leader: {} has not yet caught up: {:?}",
+                        to, matched
+                    );
+                    sleep(TRANSFER_LEADER_CHECK_INTERVAL).await;
+                    continue;
+                }
+            }
+
+            let (endpoint, mut client) = MetaForwarder::new(self)
+                .new_raft_client(&to)
+                .await
+                .map_err(|e| AnyError::new(&e))?;
+
+            let mut req = tonic::Request::new(Empty {});
+            req.metadata_mut()
+                .insert_bin(AUTH_TOKEN_KEY, auth_token.clone());
+
+            client.elect(req).await.map_err(|e| {
+                AnyError::new(&e).add_context(|| format!("elect on {}, endpoint: {}", to, endpoint))
+            })?;
+
+            let res = self
+                .raft
+                .wait(Some(election_timeout))
+                .metrics(
+                    |m| m.current_leader == Some(to),
+                    format!("{} becomes leader", to),
+                )
+                .await;
+
+            if let Err(e) = res {
+                warn!("transfer leader to {} not yet done: {}, retry", to, e);
+            }
+        }
+    }
+
     pub async fn get_status(&self) -> Result<MetaNodeStatus, MetaError> {
         let voters = self
             .sto
@@ -925,6 +1152,8 @@ impl MetaNode {
             db_size,
             state: format!("{:?}", metrics.state),
             is_leader: metrics.state == openraft::ServerState::Leader,
+            is_learner: metrics.state == openraft::ServerState::Learner,
+            read_replica: self.sto.config.read_replica,
             current_term: metrics.current_term,
             last_log_index: metrics.last_log_index.unwrap_or(0),
             last_applied: metrics
@@ -940,6 +1169,187 @@ impl MetaNode {
         })
     }
 
+    /// Whether this node serves as a read replica: it is configured with `read_replica` and is a learner.
+    ///
+    /// A read replica serves reads from its local state machine and rejects writes.
+    pub fn is_read_replica(&self) -> bool {
+        self.sto.config.read_replica
+            && self.raft.metrics().borrow().state == openraft::ServerState::Learner
+    }
+
+    /// Returns why the local state machine is known to be behind, or None if it is not.
+    ///
+    /// It is behind when it is installing a snapshot, or when its applied log index is behind
+    /// the leader's by more than [`CATCHING_UP_APPLY_LAG`].
+    /// The leader's index is the greater one of the committed index the leader replicates,
+    /// and the last log index this node received.
+    /// A local read should not be served then, because the data may be incomplete.
+    pub fn catching_up(&self) -> Option<String> {
+        if self.sto.installing_snapshot.load(Ordering::Relaxed) {
+            return Some("installing snapshot".to_string());
+        }
+
+        let (last_log_index, last_applied) = {
+            let metrics = self.raft.metrics();
+            let m = metrics.borrow();
+            (
+                m.last_log_index.unwrap_or_default(),
+                m.last_applied.unwrap_or_default().index,
+            )
+        };
+        let leader_index = std::cmp::max(
+            self.leader_committed.load(Ordering::Relaxed),
+            last_log_index,
+        );
+        let lag = leader_index.saturating_sub(last_applied);
+
+        if lag > CATCHING_UP_APPLY_LAG {
+            return Some(format!(
+                "applied: {}, leader: {}, lag: {} > {}",
+                last_applied, leader_index, lag, CATCHING_UP_APPLY_LAG
+            ));
+        }
+
+        None
+    }
+
+    /// Record a non-empty append-entries batch received, evicting the oldest one if the buffer is full.
+    pub(crate) fn record_append_entries_batch(&self, first_index: u64, n_entries: u64) {
+        let mut batches = self.append_entries_batches.lock().unwrap();
+        if batches.len() >= MAX_APPEND_ENTRIES_BATCHES {
+            batches.pop_front();
+        }
+        batches.push_back((first_index, n_entries));
+    }
+
+    /// Return the `(first log index, number of entries)` of the recent append-entries batches received,
+    /// the oldest first.
+    pub fn append_entries_batches(&self) -> Vec<(u64, u64)> {
+        self.append_entries_batches
+            .lock()
+            .unwrap()
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Serve a read from the local state machine, without forwarding it to the leader.
+    ///
+    /// The result reflects only the logs this node has applied, thus it may be stale.
+    #[minitrace::trace]
+    pub async fn local_read(
+        &self,
+        req: MetaGrpcReadReq,
+    ) -> Result<BoxStream<StreamItem>, MetaOperationError> {
+        // The read handler of `MetaLeader` only reads the local state machine.
+        MetaLeader::new(self)
+            .handle(ForwardRequest {
+                forward_to_leader: 0,
+                body: req,
+            })
+            .await
+    }
+
+    /// Returns the cluster version stored in the local state machine.
+    ///
+    /// A state machine that is behind may return an older version,
+    /// which only keeps the features gated on the version disabled for a while.
+    /// An error reading it is treated as [`CLUSTER_VERSION_V0`] for the same reason.
+    pub async fn cluster_version(&self) -> u64 {
+        let sm = self.sto.state_machine.read().await;
+        match sm.get_maybe_expired_kv(CLUSTER_VERSION_KEY).await {
+            Ok(seqv) => decode_cluster_version(seqv.as_ref().map(|x| x.data.as_slice())),
+            Err(e) => {
+                warn!("fail to read cluster version: {}", e);
+                CLUSTER_VERSION_V0
+            }
+        }
+    }
+
+    /// Raise the cluster version to `version`, with a raft log.
+    ///
+    /// It must be called only after every node is upgraded to a build supporting `version`.
+    /// A version can not be lowered, because the data written with a feature it enables
+    /// may not be understood by an older build.
+    #[minitrace::trace]
+    pub async fn set_cluster_version(&self, version: u64) -> Result<u64, AnyError> {
+        if version > MAX_CLUSTER_VERSION {
+            return Err(AnyError::error(format!(
+                "cluster version {} is not supported, max: {}",
+                version, MAX_CLUSTER_VERSION
+            )));
+        }
+
+        let curr = self.cluster_version().await;
+        if version < curr {
+            return Err(AnyError::error(format!(
+                "cluster version can not be lowered from {} to {}",
+                curr, version
+            )));
+        }
+
+        info!("set cluster version from {} to {}", curr, version);
+
+        let cmd = Cmd::UpsertKV(UpsertKV::update(
+            CLUSTER_VERSION_KEY,
+            version.to_string().as_bytes(),
+        ));
+        self.write(LogEntry::new(cmd))
+            .await
+            .map_err(|e| AnyError::new(&e))?;
+
+        Ok(version)
+    }
+
+    /// Returns the minimum schema version of each key namespace, by namespace prefix,
+    /// stored in the local state machine.
+    pub async fn schema_versions(&self) -> Result<BTreeMap<String, u64>, io::Error> {
+        let sm = self.sto.state_machine.read().await;
+        let strm = sm.list_kv(SCHEMA_VERSION_KEY_PREFIX).await?;
+        let kvs = strm.try_collect::<Vec<_>>().await?;
+
+        let versions = kvs
+            .iter()
+            .filter_map(|(k, v)| decode_schema_version(k, &v.data))
+            .collect();
+        Ok(versions)
+    }
+
+    /// Raise the minimum schema version of the keys starting with `prefix`, with a raft log.
+    ///
+    /// A version can not be lowered, otherwise clients of an older key layout could write again.
+    #[minitrace::trace]
+    pub async fn set_schema_version(&self, prefix: &str, version: u64) -> Result<u64, AnyError> {
+        if prefix.is_empty() {
+            return Err(AnyError::error("schema version prefix can not be empty"));
+        }
+
+        let versions = self
+            .schema_versions()
+            .await
+            .map_err(|e| AnyError::new(&e))?;
+        if let Some(curr) = versions.get(prefix) {
+            if version < *curr {
+                return Err(AnyError::error(format!(
+                    "schema version of {} can not be lowered from {} to {}",
+                    prefix, curr, version
+                )));
+            }
+        }
+
+        info!("set schema version of {} to {}", prefix, version);
+
+        let cmd = Cmd::UpsertKV(UpsertKV::update(
+            schema_version_key(prefix),
+            version.to_string().as_bytes(),
+        ));
+        self.write(LogEntry::new(cmd))
+            .await
+            .map_err(|e| AnyError::new(&e))?;
+
+        Ok(version)
+    }
+
     pub(crate) async fn get_last_seq(&self) -> u64 {
         let sm = self.sto.state_machine.read().await;
         sm.sys_data_ref().curr_seq()
@@ -1011,6 +1421,22 @@ impl MetaNode {
         &self,
         req: ForwardRequest<Req>,
     ) -> Result<Req::Reply, MetaAPIError>
+    where
+        Req: RequestFor,
+        for<'a> MetaLeader<'a>: Handler<Req>,
+        for<'a> MetaForwarder<'a>: Forwarder<Req>,
+    {
+        let (reply, _forwarded) = self.handle_or_forward(req).await?;
+        Ok(reply)
+    }
+
+    /// Handle a request locally if this node is the leader, otherwise forward it to the leader.
+    ///
+    /// Returns the reply and whether the request is forwarded.
+    async fn handle_or_forward<Req>(
+        &self,
+        req: ForwardRequest<Req>,
+    ) -> Result<(Req::Reply, bool), MetaAPIError>
     where
         Req: RequestFor,
         for<'a> MetaLeader<'a>: Handler<Req>,
@@ -1032,7 +1458,7 @@ impl MetaNode {
                 Ok(leader) => {
                     let res = leader.handle(req.clone()).await;
                     match res {
-                        Ok(x) => return Ok(x),
+                        Ok(x) => return Ok((x, false)),
                         Err(e) => e,
                     }
                 }
@@ -1058,7 +1484,7 @@ impl MetaNode {
 
             let forward_err = match res {
                 Ok(x) => {
-                    return Ok(x);
+                    return Ok((x, true));
                 }
                 Err(forward_err) => forward_err,
             };
@@ -1125,7 +1551,9 @@ impl MetaNode {
             node,
             overriding: false,
         };
-        let resp = self.write(LogEntry::new(cmd)).await?;
+        let resp = self
+            .write_with_priority(LogEntry::new(cmd), WritePriority::High)
+            .await?;
 
         self.raft
             .change_membership(
@@ -1142,18 +1570,97 @@ impl MetaNode {
     pub async fn write(&self, req: LogEntry) -> Result<AppliedState, MetaAPIError> {
         debug!("{} req: {:?}", func_name!(), req);
 
-        let res = self
-            .handle_forwardable_request(ForwardRequest {
+        let (res, forwarded) = self
+            .handle_or_forward(ForwardRequest {
                 forward_to_leader: 1,
                 body: ForwardRequestBody::Write(req.clone()),
             })
             .await?;
 
-        let res: AppliedState = res.try_into().expect("expect AppliedState");
+        server_metrics::incr_write_handled(forwarded);
+
+        let res: AppliedState = res.try_into().map_err(|e| {
+            let invalid_reply =
+                InvalidReply::new("expect reply type to be AppliedState", &AnyError::error(e));
+            MetaNetworkError::from(invalid_reply)
+        })?;
 
         Ok(res)
     }
 
+    /// Submit a write request to the known leader, to be proposed before the waiting writes of a lower priority.
+    /// Returns the response after applying the request.
+    ///
+    /// Only the writes not yet proposed are reordered, the order of the proposed raft logs is kept.
+    #[minitrace::trace]
+    pub async fn write_with_priority(
+        &self,
+        req: LogEntry,
+        priority: WritePriority,
+    ) -> Result<AppliedState, MetaAPIError> {
+        let (res, _log_index) = self.write_with_log_index(req, priority).await?;
+        Ok(res)
+    }
+
+    /// Submit a write request to the known leader.
+    /// Returns the response and the index of the raft log of the write.
+    ///
+    /// Waiting for another node to apply up to the index, with [`Self::wait_applied`],
+    /// makes the write visible to reads on that node.
+    ///
+    /// The index is `None` if the write is forwarded before the cluster version is raised to
+    /// [`CLUSTER_VERSION_V1`](common_meta_types::cluster_version::CLUSTER_VERSION_V1):
+    /// a leader of an older build does not reply with it, nor does it know the `priority`.
+    #[minitrace::trace]
+    pub async fn write_with_log_index(
+        &self,
+        req: LogEntry,
+        priority: WritePriority,
+    ) -> Result<(AppliedState, Option<u64>), MetaAPIError> {
+        debug!("{} req: {:?}, priority: {:?}", func_name!(), req, priority);
+
+        let (res, forwarded) = self
+            .handle_or_forward(ForwardRequest {
+                forward_to_leader: 1,
+                body: ForwardRequestBody::WriteWithLogIndex {
+                    entry: req,
+                    priority,
+                },
+            })
+            .await?;
+
+        server_metrics::incr_write_handled(forwarded);
+
+        match res {
+            ForwardResponse::AppliedStateAt { log_index, state } => Ok((state, Some(log_index))),
+            ForwardResponse::AppliedState(state) => Ok((state, None)),
+            _ => {
+                let invalid_reply = InvalidReply::new(
+                    "expect reply type to be AppliedStateAt",
+                    &AnyError::error(res),
+                );
+                Err(MetaNetworkError::from(invalid_reply).into())
+            }
+        }
+    }
+
+    /// Wait until the local state machine applies up to the raft log `log_index`, at most `timeout`.
+    ///
+    /// Returns the index of the last applied log.
+    #[minitrace::trace]
+    pub async fn wait_applied(&self, log_index: u64, timeout: Duration) -> Result<u64, WaitError> {
+        let metrics = self
+            .raft
+            .wait(Some(timeout))
+            .metrics(
+                |m| m.last_applied.map_or(false, |x| x.index >= log_index),
+                format!("applied log index >= {}", log_index),
+            )
+            .await?;
+
+        Ok(metrics.last_applied.map(|x| x.index).unwrap_or_default())
+    }
+
     /// Try to get the leader from the latest metrics of the local raft node.
     /// If leader is absent, wait for an metrics update in which a leader is set.
     #[minitrace::trace]
@@ -1183,22 +1690,99 @@ impl MetaNode {
         }
     }
 
+    /// Register a watcher, and read the initial values in its range if `request.initial_flush` is set.
+    ///
+    /// The watcher is registered while holding the state machine read lock.
+    /// Thus a change applied before it is included in the initial values,
+    /// and a change applied after it is sent to the watcher:
+    /// the dispatcher receives the registration and the change events through the same channel.
     pub(crate) async fn add_watcher(
         &self,
         request: WatchRequest,
+        owner: String,
         tx: WatcherSender,
-    ) -> Result<Watcher, &'static str> {
+    ) -> Result<(Watcher, Vec<WatchResponse>), &'static str> {
         let (resp_tx, resp_rx) = oneshot::channel();
 
+        let initial_flush = request.initial_flush && request.filter_type() != FilterType::Delete;
+        let key = request.key.clone();
+        let key_end = request.key_end.clone();
+
+        let sm = self.sto.state_machine.read().await;
+
+        let handle = self.dispatcher_handle.clone();
         self.dispatcher_handle.request(|d: &mut EventDispatcher| {
-            let add_res = d.add_watcher(request, tx);
+            let add_res = d.add_watcher(request, owner, tx, handle);
             let _ = resp_tx.send(add_res);
         });
 
+        let initial = if initial_flush {
+            let kvs = match key_end {
+                Some(key_end) => {
+                    let strm = sm.range_kv(&key, &key_end).await;
+                    let strm = strm.map_err(|_e| "read initial values")?;
+                    strm.try_collect::<Vec<_>>()
+                        .await
+                        .map_err(|_e| "read initial values")?
+                }
+                None => {
+                    let got = sm.get_maybe_expired_kv(&key).await;
+                    let got = got.map_err(|_e| "read initial values")?;
+                    got.map(|v| (key.clone(), v)).into_iter().collect()
+                }
+            };
+
+            let now_ms = SeqV::<()>::now_ms();
+            kvs.into_iter()
+                .filter(|(_k, v)| v.get_expire_at() >= now_ms)
+                .map(|(k, v)| WatchResponse {
+                    event: Some(Event {
+                        key: k,
+                        current: Some(pb::SeqV::from(v)),
+                        prev: None,
+                    }),
+                    is_initialization: true,
+                    stream_id: 0,
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        drop(sm);
+
         let recv_res = resp_rx.await;
-        match recv_res {
-            Ok(add_res) => add_res,
-            Err(_e) => Err("dispatcher closed"),
-        }
+        let watcher = match recv_res {
+            Ok(add_res) => add_res?,
+            Err(_e) => return Err("dispatcher closed"),
+        };
+
+        let initial = initial
+            .into_iter()
+            .map(|resp| WatchResponse {
+                stream_id: watcher.id,
+                ..resp
+            })
+            .collect();
+
+        Ok((watcher, initial))
+    }
+
+    /// Close a watch stream of `user` and release its subscription in the dispatcher.
+    ///
+    /// Returns false if there is no watcher with this id,
+    /// or an error if the watcher is created by another user.
+    pub(crate) async fn cancel_watcher(
+        &self,
+        watcher_id: WatcherId,
+        user: String,
+    ) -> Result<bool, String> {
+        let (tx, rx) = oneshot::channel();
+
+        self.dispatcher_handle.request(move |d| {
+            let _ = tx.send(d.cancel_watcher(watcher_id, &user));
+        });
+
+        rx.await.unwrap_or(Ok(false))
     }
 }
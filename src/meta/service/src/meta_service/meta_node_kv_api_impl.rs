@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use anyerror::AnyError;
 use async_trait::async_trait;
 use common_meta_client::MetaGrpcReadReq;
 use common_meta_kvapi::kvapi;
@@ -25,9 +26,12 @@ use common_meta_kvapi::kvapi::UpsertKVReply;
 use common_meta_kvapi::kvapi::UpsertKVReq;
 use common_meta_types::AppliedState;
 use common_meta_types::Cmd;
+use common_meta_types::IncrementKV;
+use common_meta_types::InvalidReply;
 use common_meta_types::LogEntry;
 use common_meta_types::MetaAPIError;
 use common_meta_types::MetaNetworkError;
+use common_meta_types::RaftTxId;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_meta_types::UpsertKV;
@@ -36,25 +40,104 @@ use futures::TryStreamExt;
 use log::info;
 
 use crate::message::ForwardRequest;
+use crate::message::ForwardRequestBody;
 use crate::meta_service::MetaNode;
+use crate::meta_service::WritePriority;
 
-/// Impl kvapi::KVApi for MetaNode.
-///
-/// Write through raft-log.
-/// Read through local state machine, which may not be consistent.
-/// E.g. Read is not guaranteed to see a write.
-#[async_trait]
-impl kvapi::KVApi for MetaNode {
-    type Error = MetaAPIError;
-
-    async fn upsert_kv(&self, act: UpsertKVReq) -> Result<UpsertKVReply, Self::Error> {
+impl MetaNode {
+    /// Upsert a kv through raft-log.
+    ///
+    /// If `txid` is provided, a retry with the same txid is applied only once
+    /// and returns the same reply as the first one.
+    ///
+    /// It also returns the index of the raft log of the write.
+    #[minitrace::trace]
+    pub async fn upsert_kv_with_txid(
+        &self,
+        act: UpsertKVReq,
+        txid: Option<RaftTxId>,
+        priority: WritePriority,
+    ) -> Result<(UpsertKVReply, Option<u64>), MetaAPIError> {
         let ent = LogEntry::new(Cmd::UpsertKV(UpsertKV {
             key: act.key,
             seq: act.seq,
             value: act.value,
             value_meta: act.value_meta,
-        }));
-        let rst = self.write(ent).await?;
+        }))
+        .with_txid(txid);
+        let (rst, log_index) = self.write_with_log_index(ent, priority).await?;
+
+        match rst {
+            AppliedState::KV(x) => Ok((x, log_index)),
+            _ => Err(Self::unexpected_applied_state("KV", rst)),
+        }
+    }
+
+    /// Run a transaction through raft-log and return the reply and the index of the raft log.
+    #[minitrace::trace]
+    pub async fn transaction_with_log_index(
+        &self,
+        txn: TxnRequest,
+        priority: WritePriority,
+    ) -> Result<(TxnReply, Option<u64>), MetaAPIError> {
+        info!(
+            "MetaNode::transaction_with_log_index(): {}, priority: {:?}",
+            txn, priority
+        );
+        let ent = LogEntry::new(Cmd::Transaction(txn));
+        let (rst, log_index) = self.write_with_log_index(ent, priority).await?;
+
+        match rst {
+            AppliedState::TxnReply(x) => Ok((x, log_index)),
+            _ => Err(Self::unexpected_applied_state("TxnReply", rst)),
+        }
+    }
+
+    /// Atomically add a delta to a counter key through raft-log.
+    ///
+    /// It returns the change of the key and the index of the raft log.
+    /// The key is left unchanged if its value is not a number or the sum overflows.
+    #[minitrace::trace]
+    pub async fn increment_kv_with_log_index(
+        &self,
+        incr: IncrementKV,
+    ) -> Result<(UpsertKVReply, Option<u64>), MetaAPIError> {
+        info!("MetaNode::increment_kv_with_log_index(): {}", incr);
+        let ent = LogEntry::new(Cmd::IncrementKV(incr));
+        let (rst, log_index) = self
+            .write_with_log_index(ent, WritePriority::Normal)
+            .await?;
+
+        match rst {
+            AppliedState::KV(x) => Ok((x, log_index)),
+            _ => Err(Self::unexpected_applied_state("KV", rst)),
+        }
+    }
+
+    /// Build an error for a write that is applied with a state of an unexpected type,
+    /// instead of panicking the server.
+    fn unexpected_applied_state(expect: &str, got: AppliedState) -> MetaAPIError {
+        let invalid_reply = InvalidReply::new(
+            format!("expect AppliedState::{}", expect),
+            &AnyError::error(got),
+        );
+        MetaNetworkError::from(invalid_reply).into()
+    }
+
+    /// Evaluate an upsert on the leader and return the would-be reply, without committing it.
+    ///
+    /// The `MatchSeq` precondition is checked against the current state:
+    /// if it fails, the returned `prev` and `result` are the same, just like a real upsert.
+    #[minitrace::trace]
+    pub async fn dry_run_upsert_kv(&self, act: UpsertKVReq) -> Result<UpsertKVReply, MetaAPIError> {
+        let res = self
+            .handle_forwardable_request(ForwardRequest {
+                forward_to_leader: 1,
+                body: ForwardRequestBody::DryRunUpsertKV(act),
+            })
+            .await?;
+
+        let rst: AppliedState = res.try_into().expect("expect AppliedState");
 
         match rst {
             AppliedState::KV(x) => Ok(x),
@@ -63,6 +146,23 @@ impl kvapi::KVApi for MetaNode {
             }
         }
     }
+}
+
+/// Impl kvapi::KVApi for MetaNode.
+///
+/// Write through raft-log.
+/// Read through local state machine, which may not be consistent.
+/// E.g. Read is not guaranteed to see a write.
+#[async_trait]
+impl kvapi::KVApi for MetaNode {
+    type Error = MetaAPIError;
+
+    async fn upsert_kv(&self, act: UpsertKVReq) -> Result<UpsertKVReply, Self::Error> {
+        let (reply, _log_index) = self
+            .upsert_kv_with_txid(act, None, WritePriority::Normal)
+            .await?;
+        Ok(reply)
+    }
 
     #[minitrace::trace]
     async fn get_kv(&self, key: &str) -> Result<GetKVReply, Self::Error> {
@@ -112,9 +212,7 @@ impl kvapi::KVApi for MetaNode {
 
         match rst {
             AppliedState::TxnReply(x) => Ok(x),
-            _ => {
-                unreachable!("expect type {}", "AppliedState::transaction",)
-            }
+            _ => Err(Self::unexpected_applied_state("TxnReply", rst)),
         }
     }
 }
@@ -15,7 +15,9 @@
 pub use forwarder::MetaForwarder;
 pub use meta_node::MetaNode;
 pub use raft_service_impl::RaftServiceImpl;
+pub use submit_queue::WritePriority;
 
+pub use crate::message::ForwardContentType;
 pub use crate::message::ForwardRequest;
 pub use crate::message::ForwardRequestBody;
 pub use crate::message::JoinRequest;
@@ -27,3 +29,4 @@ pub mod meta_leader;
 pub mod meta_node;
 mod meta_node_kv_api_impl;
 pub mod raft_service_impl;
+pub mod submit_queue;
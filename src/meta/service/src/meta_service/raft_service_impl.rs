@@ -15,14 +15,18 @@
 //! Meta service impl a grpc server that serves both raft protocol: append_entries, vote and install_snapshot.
 //! It also serves RPC for user-data access.
 
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::Instant;
 
 use common_meta_client::MetaGrpcReadReq;
 use common_meta_types::protobuf::raft_service_server::RaftService;
+use common_meta_types::protobuf::Empty;
 use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::protobuf::StreamItem;
+use common_meta_types::AppendEntriesRequest;
 use minitrace::full_name;
 use minitrace::prelude::*;
 use tonic::codegen::BoxStream;
@@ -31,6 +35,9 @@ use tonic::Response;
 use tonic::Status;
 
 use crate::grpc_helper::GrpcHelper;
+use crate::grpc_helper::DEFAULT_FORWARD_TIMEOUT;
+use crate::grpc_helper::DEFAULT_MAX_FORWARD_REQUEST_SIZE;
+use crate::message::ForwardContentType;
 use crate::message::ForwardRequest;
 use crate::message::ForwardRequestBody;
 use crate::meta_service::MetaNode;
@@ -38,11 +45,31 @@ use crate::metrics::raft_metrics;
 
 pub struct RaftServiceImpl {
     pub meta_node: Arc<MetaNode>,
+
+    /// The max time to handle a forwarded request, if the request does not specify a deadline.
+    forward_timeout: Duration,
+
+    /// The max size of the payload of a forwarded request; a larger one is rejected before parsing.
+    max_forward_request_size: usize,
 }
 
 impl RaftServiceImpl {
     pub fn create(meta_node: Arc<MetaNode>) -> Self {
-        Self { meta_node }
+        Self {
+            meta_node,
+            forward_timeout: DEFAULT_FORWARD_TIMEOUT,
+            max_forward_request_size: DEFAULT_MAX_FORWARD_REQUEST_SIZE,
+        }
+    }
+
+    pub fn with_forward_timeout(mut self, timeout: Duration) -> Self {
+        self.forward_timeout = timeout;
+        self
+    }
+
+    pub fn with_max_forward_request_size(mut self, size: usize) -> Self {
+        self.max_forward_request_size = size;
+        self
     }
 
     fn incr_meta_metrics_recv_bytes_from_peer(&self, request: &tonic::Request<RaftRequest>) {
@@ -63,13 +90,21 @@ impl RaftService for RaftServiceImpl {
         let root = common_tracing::start_trace_for_remote_request(full_name!(), &request);
 
         async {
-            let forward_req: ForwardRequest<ForwardRequestBody> = GrpcHelper::parse_req(request)?;
+            let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+            let content_type = ForwardContentType::from_metadata(request.metadata())?;
+            let forward_req: ForwardRequest<ForwardRequestBody> =
+                content_type.decode_req(request, self.max_forward_request_size)?;
 
-            let res = self.meta_node.handle_forwardable_request(forward_req).await;
+            let res = GrpcHelper::with_timeout(timeout, async {
+                Ok(self.meta_node.handle_forwardable_request(forward_req).await)
+            })
+            .await?;
 
-            let raft_reply: RaftReply = res.into();
+            let raft_reply = content_type.encode_reply(res);
 
-            Ok(tonic::Response::new(raft_reply))
+            let mut resp = tonic::Response::new(raft_reply);
+            content_type.set_metadata(resp.metadata_mut());
+            Ok(resp)
         }
         .in_span(root)
         .await
@@ -84,13 +119,17 @@ impl RaftService for RaftServiceImpl {
         let root = common_tracing::start_trace_for_remote_request(full_name!(), &request);
 
         async {
-            let forward_req: ForwardRequest<MetaGrpcReadReq> = GrpcHelper::parse_req(request)?;
-
-            let strm = self
-                .meta_node
-                .handle_forwardable_request(forward_req)
-                .await
-                .map_err(GrpcHelper::internal_err)?;
+            let timeout = GrpcHelper::request_timeout(request.metadata(), self.forward_timeout);
+            let forward_req: ForwardRequest<MetaGrpcReadReq> =
+                GrpcHelper::parse_req_with_limit(request, self.max_forward_request_size)?;
+
+            let strm = GrpcHelper::with_timeout(timeout, async {
+                self.meta_node
+                    .handle_forwardable_request(forward_req)
+                    .await
+                    .map_err(GrpcHelper::internal_err)
+            })
+            .await?;
 
             Ok(tonic::Response::new(strm))
         }
@@ -107,9 +146,20 @@ impl RaftService for RaftServiceImpl {
         async {
             self.incr_meta_metrics_recv_bytes_from_peer(&request);
 
-            let ae_req = GrpcHelper::parse_req(request)?;
+            let ae_req: AppendEntriesRequest = GrpcHelper::parse_req(request)?;
             let raft = &self.meta_node.raft;
 
+            if let Some(committed) = ae_req.leader_commit {
+                self.meta_node
+                    .leader_committed
+                    .fetch_max(committed.index, Ordering::Relaxed);
+            }
+
+            if let Some(first) = ae_req.entries.first() {
+                self.meta_node
+                    .record_append_entries_batch(first.log_id.index, ae_req.entries.len() as u64);
+            }
+
             let resp = raft
                 .append_entries(ae_req)
                 .await
@@ -181,4 +231,25 @@ impl RaftService for RaftServiceImpl {
         .in_span(root)
         .await
     }
+
+    async fn elect(&self, request: Request<Empty>) -> Result<Response<Empty>, Status> {
+        let root = common_tracing::start_trace_for_remote_request(full_name!(), &request);
+
+        async {
+            // An election disrupts the cluster, only root is allowed to start one.
+            let claim = GrpcHelper::verify_token(&self.meta_node.grpc_token, request.metadata())?;
+            GrpcHelper::check_root(&claim, "start an election")?;
+
+            self.meta_node
+                .raft
+                .trigger()
+                .elect()
+                .await
+                .map_err(GrpcHelper::internal_err)?;
+
+            Ok(Response::new(Empty {}))
+        }
+        .in_span(root)
+        .await
+    }
 }
@@ -0,0 +1,185 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The queue of writes waiting to be proposed to raft, ordered by priority.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_base::base::tokio::sync::oneshot;
+
+/// The priority of a write that waits to be proposed.
+///
+/// A control-plane write, such as adding or removing a node, is `High`,
+/// so that it does not wait behind a backlog of bulk writes, which can be `Low`.
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum WritePriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl FromStr for WritePriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "high" => Ok(Self::High),
+            _ => Err(format!("expect: low, normal or high, got: {}", s)),
+        }
+    }
+}
+
+/// Limits the number of in-flight raft proposals, and admits the waiting writes by priority.
+///
+/// The limit is `RaftConfig::max_inflight_proposals`: writes beyond it wait until a proposal completes.
+///
+/// Writes of the same priority are admitted in the order they arrive.
+/// Only the writes that are not proposed yet are reordered:
+/// the order of the proposed logs is still decided by raft.
+pub struct SubmitQueue {
+    max_inflight: usize,
+    state: Mutex<QueueState>,
+}
+
+struct QueueState {
+    inflight: usize,
+    next_seq: u64,
+    waiting: BinaryHeap<Waiter>,
+}
+
+struct Waiter {
+    priority: WritePriority,
+    /// The arrival order, to keep writes of the same priority FIFO.
+    seq: u64,
+    tx: oneshot::Sender<SubmitPermit>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    /// A higher priority is greater, and an earlier arrival in the same priority is greater.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// Permission to propose a write. The slot is passed to the next waiting write when it is dropped.
+pub struct SubmitPermit {
+    queue: Arc<SubmitQueue>,
+}
+
+impl Drop for SubmitPermit {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+impl SubmitQueue {
+    pub fn new(max_inflight: usize) -> Self {
+        Self {
+            max_inflight,
+            state: Mutex::new(QueueState {
+                inflight: 0,
+                next_seq: 0,
+                waiting: BinaryHeap::new(),
+            }),
+        }
+    }
+
+    /// Wait until a write of `priority` is allowed to be proposed.
+    ///
+    /// The returned permit should be held until the proposal completes.
+    pub async fn acquire(self: &Arc<Self>, priority: WritePriority) -> SubmitPermit {
+        let rx = {
+            let mut st = self.state.lock().unwrap();
+
+            if st.inflight < self.max_inflight && st.waiting.is_empty() {
+                st.inflight += 1;
+                return SubmitPermit {
+                    queue: self.clone(),
+                };
+            }
+
+            let (tx, rx) = oneshot::channel();
+            let seq = st.next_seq;
+            st.next_seq += 1;
+            st.waiting.push(Waiter { priority, seq, tx });
+            rx
+        };
+
+        // safe unwrap(): a waiter is only removed from the queue when a permit is sent to it.
+        rx.await.unwrap()
+    }
+
+    /// The number of writes being proposed.
+    pub fn inflight(&self) -> usize {
+        self.state.lock().unwrap().inflight
+    }
+
+    /// The number of writes waiting to be proposed.
+    pub fn waiting(&self) -> usize {
+        self.state.lock().unwrap().waiting.len()
+    }
+
+    /// Pass the slot of a dropped permit to the waiter with the highest priority.
+    fn release(self: &Arc<Self>) {
+        let waiter = {
+            let mut st = self.state.lock().unwrap();
+            match st.waiting.pop() {
+                Some(w) => w,
+                None => {
+                    st.inflight -= 1;
+                    return;
+                }
+            }
+        };
+
+        // If the waiter has gone, the returned permit is dropped and the slot goes to the next one.
+        let _ = waiter.tx.send(SubmitPermit {
+            queue: self.clone(),
+        });
+    }
+}
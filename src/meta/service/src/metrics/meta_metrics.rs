@@ -57,6 +57,8 @@ pub mod server_metrics {
         proposals_failed: Counter,
         read_failed: Counter,
         watchers: Gauge,
+        writes_handled_as_leader: Counter,
+        writes_forwarded_to_leader: Counter,
     }
 
     impl ServerMetrics {
@@ -75,6 +77,8 @@ pub mod server_metrics {
                 proposals_failed: Counter::default(),
                 read_failed: Counter::default(),
                 watchers: Gauge::default(),
+                writes_handled_as_leader: Counter::default(),
+                writes_forwarded_to_leader: Counter::default(),
             };
 
             let mut registry = load_global_registry();
@@ -131,6 +135,16 @@ pub mod server_metrics {
                 metrics.read_failed.clone(),
             );
             registry.register(key!("watchers"), "watchers", metrics.watchers.clone());
+            registry.register(
+                key!("writes_handled_as_leader"),
+                "writes handled as leader",
+                metrics.writes_handled_as_leader.clone(),
+            );
+            registry.register(
+                key!("writes_forwarded_to_leader"),
+                "writes forwarded to leader",
+                metrics.writes_forwarded_to_leader.clone(),
+            );
             metrics
         }
     }
@@ -193,6 +207,23 @@ pub mod server_metrics {
     pub fn incr_watchers(cnt: i64) {
         SERVER_METRICS.watchers.inc_by(cnt);
     }
+
+    /// Count a write by whether it is handled locally as the leader or forwarded to the leader.
+    pub fn incr_write_handled(forwarded: bool) {
+        if forwarded {
+            SERVER_METRICS.writes_forwarded_to_leader.inc();
+        } else {
+            SERVER_METRICS.writes_handled_as_leader.inc();
+        }
+    }
+
+    /// The number of writes handled as the leader and the number of writes forwarded to the leader.
+    pub fn get_writes_handled() -> (u64, u64) {
+        (
+            SERVER_METRICS.writes_handled_as_leader.get(),
+            SERVER_METRICS.writes_forwarded_to_leader.get(),
+        )
+    }
 }
 
 pub mod raft_metrics {
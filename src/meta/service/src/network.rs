@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 
 use anyerror::AnyError;
 use backon::BackoffBuilder;
+use backon::ExponentialBackoff;
 use backon::ExponentialBuilder;
 use common_base::base::tokio::time::sleep;
 use common_base::containers::ItemManager;
@@ -28,6 +31,7 @@ use common_meta_sled_store::openraft::RaftNetworkFactory;
 use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::AppendEntriesRequest;
 use common_meta_types::AppendEntriesResponse;
+use common_meta_types::ConnectionError;
 use common_meta_types::InstallSnapshotError;
 use common_meta_types::InstallSnapshotRequest;
 use common_meta_types::InstallSnapshotResponse;
@@ -42,6 +46,7 @@ use common_meta_types::VoteRequest;
 use common_meta_types::VoteResponse;
 use log::debug;
 use log::info;
+use log::warn;
 use openraft::async_trait::async_trait;
 use openraft::RaftNetwork;
 use tonic::client::GrpcService;
@@ -127,6 +132,75 @@ impl Default for Backoff {
     }
 }
 
+/// Paces reconnecting to a peer after connection failures,
+/// so that an unreachable peer is not connected to in a tight loop.
+///
+/// After a failure, the next connection is delayed by an exponentially growing duration with jitter.
+/// A successful connection resets the delay.
+#[derive(Debug)]
+pub struct ReconnectBackoff {
+    min_delay: Duration,
+    max_delay: Duration,
+
+    /// The delays for the following failures, `None` if the last connection succeeded.
+    delays: Option<ExponentialBackoff>,
+
+    /// The earliest time to connect again.
+    next_attempt: Option<Instant>,
+}
+
+impl ReconnectBackoff {
+    pub fn new(min_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            min_delay,
+            max_delay,
+            delays: None,
+            next_attempt: None,
+        }
+    }
+
+    /// Returns how long to wait before connecting again, or `None` if it can connect now.
+    pub fn wait_time(&self) -> Option<Duration> {
+        let next_attempt = self.next_attempt?;
+        let now = Instant::now();
+        if next_attempt > now {
+            Some(next_attempt - now)
+        } else {
+            None
+        }
+    }
+
+    /// Record a connection failure and returns the delay before the next connection.
+    pub fn on_failure(&mut self) -> Duration {
+        let (min_delay, max_delay) = (self.min_delay, self.max_delay);
+
+        let delays = self.delays.get_or_insert_with(|| {
+            ExponentialBuilder::default()
+                .with_min_delay(min_delay)
+                .with_max_delay(max_delay)
+                .with_max_times(usize::MAX)
+                .with_jitter()
+                .build()
+        });
+
+        let delay = delays.next().unwrap_or(max_delay);
+        self.next_attempt = Some(Instant::now() + delay);
+        delay
+    }
+
+    /// Record a successful connection, which resets the delay.
+    pub fn on_success(&mut self) {
+        self.delays = None;
+        self.next_attempt = None;
+    }
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(50), Duration::from_secs(2))
+    }
+}
+
 #[derive(Clone)]
 pub struct Network {
     sto: RaftStore,
@@ -134,6 +208,12 @@ pub struct Network {
     conn_pool: Arc<Pool<ChannelManager>>,
 
     backoff: Backoff,
+
+    /// Paces reconnecting to each target after a connection failure.
+    ///
+    /// It is shared by all the connections to the same target,
+    /// because raft creates a new connection when it retries.
+    reconnects: Arc<Mutex<BTreeMap<NodeId, ReconnectBackoff>>>,
 }
 
 impl Network {
@@ -143,9 +223,16 @@ impl Network {
             sto,
             conn_pool: Arc::new(Pool::new(mgr, Duration::from_millis(50))),
             backoff: Backoff::default(),
+            reconnects: Arc::new(Mutex::new(BTreeMap::new())),
         }
     }
 
+    /// Returns how long to wait before connecting to `target` again, or `None` if it can connect now.
+    pub fn reconnect_wait_time(&self, target: &NodeId) -> Option<Duration> {
+        let reconnects = self.reconnects.lock().unwrap();
+        reconnects.get(target)?.wait_time()
+    }
+
     fn incr_meta_metrics_sent_bytes_to_peer(target: &NodeId, message: &RaftRequest) {
         let bytes = message.data.len() as u64;
         raft_metrics::network::incr_sent_bytes_to_peer(target, bytes);
@@ -163,6 +250,9 @@ pub struct NetworkConnection {
     sto: RaftStore,
     conn_pool: Arc<Pool<ChannelManager>>,
     backoff: Backoff,
+
+    /// The reconnect backoff of every target, shared with [`Network`].
+    reconnects: Arc<Mutex<BTreeMap<NodeId, ReconnectBackoff>>>,
 }
 
 impl NetworkConnection {
@@ -171,6 +261,22 @@ impl NetworkConnection {
     pub async fn make_client(&self) -> Result<RaftClient, MetaNetworkError> {
         let target = self.target;
 
+        let wait = {
+            let reconnects = self.reconnects.lock().unwrap();
+            reconnects.get(&target).and_then(|b| b.wait_time())
+        };
+
+        // Fail fast instead of holding up raft; raft will retry later.
+        if let Some(wait) = wait {
+            debug!(id = self.id; "wait {:?} before reconnecting to target={}", wait, target);
+
+            return Err(ConnectionError::new(
+                AnyError::error(format!("reconnect after {:?}", wait)),
+                format!("backoff connecting to target={}", target),
+            )
+            .into());
+        }
+
         let endpoint = self
             .sto
             .get_node_endpoint(&target)
@@ -183,6 +289,10 @@ impl NetworkConnection {
 
         match self.conn_pool.get(&addr).await {
             Ok(channel) => {
+                if let Some(b) = self.reconnects.lock().unwrap().get_mut(&target) {
+                    b.on_success();
+                }
+
                 let client = RaftClientApi::new(target, endpoint, channel);
                 debug!("connected: target={}: {}", target, addr);
 
@@ -193,6 +303,17 @@ impl NetworkConnection {
                     &target,
                     &endpoint.to_string(),
                 );
+
+                let delay = {
+                    let mut reconnects = self.reconnects.lock().unwrap();
+                    reconnects.entry(target).or_default().on_failure()
+                };
+                warn!(
+                    id = self.id;
+                    "fail to connect target={}: {}, error: {}; reconnect after {:?}",
+                    target, addr, err, delay
+                );
+
                 Err(err.into())
             }
         }
@@ -435,6 +556,7 @@ impl RaftNetworkFactory<TypeConfig> for Network {
             sto: self.sto.clone(),
             conn_pool: self.conn_pool.clone(),
             backoff: self.backoff.clone(),
+            reconnects: self.reconnects.clone(),
         }
     }
 }
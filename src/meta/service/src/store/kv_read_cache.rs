@@ -0,0 +1,132 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_cache::Cache;
+use common_cache::LruCache;
+use common_meta_kvapi::kvapi::GetKVReply;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_raft_store::sm_v002::SMV002;
+use common_meta_raft_store::state_machine::StateMachineSubscriber;
+use common_meta_types::Change;
+
+/// A node-local LRU cache of the values read through `get_kv`.
+///
+/// A cached key is invalidated when a change to it is applied to the state machine,
+/// and the whole cache is cleared when a snapshot is installed.
+/// Values with an expiration time are never cached.
+///
+/// It is disabled if the capacity is 0.
+#[derive(Debug)]
+pub struct KVReadCache {
+    cache: Option<Mutex<LruCache<String, GetKVReply>>>,
+}
+
+impl KVReadCache {
+    pub fn new(capacity: u64) -> Self {
+        let cache = if capacity == 0 {
+            None
+        } else {
+            Some(Mutex::new(LruCache::new(capacity)))
+        };
+
+        Self { cache }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// Read `key` from the state machine, serving it from the cache if it is cached.
+    ///
+    /// The caller must hold the state machine lock across this call,
+    /// so that no change to `key` is applied between reading it and caching it.
+    pub async fn get_kv(&self, sm: &SMV002, key: &str) -> GetKVReply {
+        if let Some(got) = self.get(key) {
+            return got;
+        }
+
+        // safe unwrap(): Infallible
+        let got = sm.kv_api().get_kv(key).await.unwrap();
+
+        self.put(key, &got);
+        got
+    }
+
+    fn get(&self, key: &str) -> Option<GetKVReply> {
+        let cache = self.cache.as_ref()?;
+        let mut cache = cache.lock().unwrap();
+        cache.get(key).cloned()
+    }
+
+    fn put(&self, key: &str, value: &GetKVReply) {
+        let Some(cache) = self.cache.as_ref() else {
+            return;
+        };
+
+        if let Some(seq_v) = value {
+            if seq_v.get_expire_at() != u64::MAX {
+                return;
+            }
+        }
+
+        let mut cache = cache.lock().unwrap();
+        cache.put(key.to_string(), value.clone());
+    }
+
+    /// Remove a key from the cache.
+    pub fn invalidate(&self, key: &str) {
+        if let Some(cache) = self.cache.as_ref() {
+            cache.lock().unwrap().pop(key);
+        }
+    }
+
+    /// Remove all keys from the cache.
+    pub fn clear(&self) {
+        if let Some(cache) = self.cache.as_ref() {
+            cache.lock().unwrap().clear();
+        }
+    }
+}
+
+/// A state machine subscriber that invalidates the changed key in a [`KVReadCache`]
+/// before passing the change to the next subscriber.
+///
+/// It is called synchronously when a change is applied or a snapshot is installed,
+/// with the state machine write lock held,
+/// thus a read that happens after the apply never sees the stale cached value.
+#[derive(Debug)]
+pub struct KVReadCacheInvalidator<S> {
+    pub cache: Arc<KVReadCache>,
+    pub next: S,
+}
+
+impl<S> StateMachineSubscriber for KVReadCacheInvalidator<S>
+where S: StateMachineSubscriber
+{
+    fn kv_changed(&self, change: Change<Vec<u8>, String>) {
+        if let Some(key) = &change.ident {
+            self.cache.invalidate(key);
+        }
+        self.next.kv_changed(change);
+    }
+
+    fn snapshot_installed(&self) {
+        // The installed snapshot replaces all data without emitting changes.
+        self.cache.clear();
+        self.next.snapshot_installed();
+    }
+}
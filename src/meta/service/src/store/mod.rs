@@ -13,10 +13,15 @@
 // limitations under the License.
 
 #[allow(clippy::module_inception)]
+mod kv_read_cache;
 mod store;
 mod store_inner;
 mod to_storage_error;
 
+pub use kv_read_cache::KVReadCache;
+pub use kv_read_cache::KVReadCacheInvalidator;
 pub use store::RaftStore;
+pub use store_inner::FailedApply;
 pub use store_inner::StoreInner;
+pub use store_inner::MAX_FAILED_APPLIES;
 pub use to_storage_error::ToStorageError;
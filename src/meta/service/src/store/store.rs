@@ -15,6 +15,8 @@
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::ops::RangeBounds;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -88,6 +90,26 @@ impl Deref for RaftStore {
     }
 }
 
+/// Sets `installing_snapshot` when created and resets it when dropped.
+struct InstallingSnapshotGuard {
+    installing_snapshot: Arc<AtomicBool>,
+}
+
+impl InstallingSnapshotGuard {
+    fn new(installing_snapshot: Arc<AtomicBool>) -> Self {
+        installing_snapshot.store(true, Ordering::Relaxed);
+        Self {
+            installing_snapshot,
+        }
+    }
+}
+
+impl Drop for InstallingSnapshotGuard {
+    fn drop(&mut self) {
+        self.installing_snapshot.store(false, Ordering::Relaxed);
+    }
+}
+
 #[async_trait]
 impl RaftLogReader<TypeConfig> for RaftStore {
     #[minitrace::trace]
@@ -312,9 +334,19 @@ impl RaftStorage<TypeConfig> for RaftStore {
         }
 
         let mut sm = self.state_machine.write().await;
-        let res = sm.apply_entries(entries).await?;
 
-        Ok(res)
+        match sm.apply_entries(entries).await {
+            Ok(res) => Ok(res),
+            Err(err) => {
+                // The entry that fails is looked up only when there is an error.
+                let ent = entries.iter().find(|ent| ent.log_id == err.log_id);
+                if let Some(ent) = ent {
+                    error!("apply_to_state_machine: failed to apply {}: {}", ent, err);
+                    self.record_failed_apply(ent, &err);
+                }
+                Err(err.source.into())
+            }
+        }
     }
 
     #[minitrace::trace]
@@ -327,15 +359,22 @@ impl RaftStorage<TypeConfig> for RaftStore {
             StorageError::from_io_error(ErrorSubject::Snapshot(None), ErrorVerb::Write, e)
         })?;
 
-        Ok(Box::new(temp))
+        // The flag is reset when the snapshot is installed, or when receiving it is aborted,
+        // in which case the temp snapshot data is dropped.
+        let guard = InstallingSnapshotGuard::new(self.installing_snapshot.clone());
+
+        Ok(Box::new(temp.with_guard(guard)))
     }
 
     #[minitrace::trace]
     async fn install_snapshot(
         &mut self,
         meta: &SnapshotMeta,
-        snapshot: Box<SnapshotData>,
+        mut snapshot: Box<SnapshotData>,
     ) -> Result<(), StorageError> {
+        // Hold the guard until the state machine is replaced.
+        let _guard = snapshot.take_guard();
+
         let data_size = snapshot.data_size().await.map_err(|e| {
             StorageError::from_io_error(
                 ErrorSubject::Snapshot(Some(meta.signature())),
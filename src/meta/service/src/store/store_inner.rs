@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::VecDeque;
 use std::io;
 use std::io::ErrorKind;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use anyerror::AnyError;
@@ -45,6 +48,7 @@ use common_meta_sled_store::openraft::ErrorVerb;
 use common_meta_sled_store::SledTree;
 use common_meta_stoerr::MetaStorageError;
 use common_meta_types::Endpoint;
+use common_meta_types::Entry;
 use common_meta_types::LogId;
 use common_meta_types::Membership;
 use common_meta_types::MetaError;
@@ -65,8 +69,25 @@ use log::info;
 use log::warn;
 
 use crate::export::vec_kv_to_json;
+use crate::store::KVReadCache;
 use crate::Opened;
 
+/// The max number of failed applies that are kept for inspection.
+pub const MAX_FAILED_APPLIES: usize = 64;
+
+/// A log entry that failed to apply to the state machine.
+///
+/// The entry may contain user data,
+/// thus it is only served to root by the `GetFailedApplies` gRPC API.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FailedApply {
+    pub log_id: LogId,
+
+    pub entry: Entry,
+
+    pub error: String,
+}
+
 /// This is the inner store that provides support utilities for implementing the raft storage API.
 ///
 /// This store is backed by a sled db, contents are stored in 3 trees:
@@ -102,6 +123,19 @@ pub struct StoreInner {
 
     /// The current snapshot.
     pub current_snapshot: RwLock<Option<StoredSnapshot>>,
+
+    /// The most recent log entries that failed to apply, the oldest first.
+    ///
+    /// An apply failure is fatal to raft: raft shuts down after the failure is recorded.
+    /// The record is only kept in memory, for inspection via the `GetFailedApplies` gRPC API
+    /// before the process is restarted. At most [`MAX_FAILED_APPLIES`] entries are kept.
+    failed_applies: Mutex<VecDeque<FailedApply>>,
+
+    /// The node-local cache of values read through `get_kv`, disabled if `kv_read_cache_size` is 0.
+    pub kv_read_cache: Arc<KVReadCache>,
+
+    /// Whether a snapshot is being received and installed, during which the state machine is behind the leader.
+    pub installing_snapshot: Arc<AtomicBool>,
 }
 
 impl AsRef<StoreInner> for StoreInner {
@@ -175,6 +209,9 @@ impl StoreInner {
             log: RwLock::new(log),
             state_machine: sm,
             current_snapshot: RwLock::new(stored_snapshot),
+            failed_applies: Mutex::new(VecDeque::new()),
+            kv_read_cache: Arc::new(KVReadCache::new(config.kv_read_cache_size)),
+            installing_snapshot: Arc::new(AtomicBool::new(false)),
         })
     }
 
@@ -210,6 +247,31 @@ impl StoreInner {
         Ok((sm, meta))
     }
 
+    /// Record a log entry that failed to apply, evicting the oldest one if the buffer is full.
+    pub(crate) fn record_failed_apply(&self, entry: &Entry, error: &impl std::fmt::Display) {
+        let failed = FailedApply {
+            log_id: entry.log_id,
+            entry: entry.clone(),
+            error: error.to_string(),
+        };
+
+        let mut failed_applies = self.failed_applies.lock().unwrap();
+        if failed_applies.len() >= MAX_FAILED_APPLIES {
+            failed_applies.pop_front();
+        }
+        failed_applies.push_back(failed);
+    }
+
+    /// Return the most recent log entries that failed to apply, the oldest first.
+    pub fn failed_applies(&self) -> Vec<FailedApply> {
+        self.failed_applies
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
     /// Get a handle to the state machine for testing purposes.
     pub async fn get_state_machine(&self) -> RwLockWriteGuard<'_, SMV002> {
         self.state_machine.write().await
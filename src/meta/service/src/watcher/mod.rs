@@ -23,3 +23,4 @@ pub use watcher_manager::WatcherSender;
 pub use watcher_stream::WatchStream;
 pub use watcher_stream::WatchStreamHandle;
 pub use watcher_stream::Watcher;
+pub use watcher_stream::WATCHER_QUEUE_SIZE;
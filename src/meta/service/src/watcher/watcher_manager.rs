@@ -29,9 +29,9 @@ use common_meta_types::Change;
 use log::info;
 use log::warn;
 use prost::Message;
-use tonic::Status;
 
 use super::WatchStreamHandle;
+use crate::api::grpc::grpc_service::StreamSender;
 use crate::metrics::network_metrics;
 use crate::metrics::server_metrics;
 use crate::watcher::Watcher;
@@ -39,7 +39,7 @@ use crate::watcher::Watcher;
 pub type WatcherId = i64;
 
 /// A sender for dispatcher to send event to interested watchers.
-pub type WatcherSender = mpsc::Sender<Result<WatchResponse, Status>>;
+pub type WatcherSender = StreamSender<WatchResponse>;
 
 /// A sender for event source, such as raft state machine, to send event to [`EventDispatcher`].
 #[derive(Clone, Debug)]
@@ -121,7 +121,7 @@ impl EventDispatcher {
             if let Some(event) = self.event_rx.recv().await {
                 match event {
                     WatchEvent::KVChange(kv_change) => {
-                        self.dispatch_event(kv_change).await;
+                        self.dispatch_event(kv_change);
                     }
                     WatchEvent::Request { req } => req(&mut self),
                 }
@@ -133,7 +133,11 @@ impl EventDispatcher {
     }
 
     /// Dispatch a kv change event to interested watchers.
-    async fn dispatch_event(&mut self, change: Change<Vec<u8>, String>) {
+    ///
+    /// An event is queued for each watcher without waiting, so that a slow client never blocks
+    /// the dispatch to the others. A watcher whose queue is full can not keep up and is removed,
+    /// and its stream is closed with `resource_exhausted`.
+    fn dispatch_event(&mut self, change: Change<Vec<u8>, String>) {
         let k = change.ident.as_ref().unwrap();
         let set = self.watcher_range_map.get_by_point(k);
         if set.is_empty() {
@@ -165,11 +169,13 @@ impl EventDispatcher {
                     current: current.clone().map(pb::SeqV::from),
                     prev: prev.clone().map(pb::SeqV::from),
                 }),
+                is_initialization: false,
+                stream_id: watcher_id,
             };
 
             network_metrics::incr_sent_bytes(resp.encoded_len() as u64);
 
-            if let Err(err) = stream.send(resp).await {
+            if let Err(err) = stream.try_send(resp) {
                 warn!(
                     "close watcher stream {:?} cause send err: {:?}",
                     watcher_id, err
@@ -181,7 +187,6 @@ impl EventDispatcher {
             };
         }
 
-        // TODO: when a watcher stream is dropped, send a event to remove the watcher explicitly
         for range_key in remove_range_keys {
             self.remove_watcher(&range_key);
         }
@@ -191,7 +196,9 @@ impl EventDispatcher {
     pub fn add_watcher(
         &mut self,
         create: WatchRequest,
+        owner: String,
         tx: WatcherSender,
+        handle: EventDispatcherHandle,
     ) -> Result<Watcher, &'static str> {
         info!("add_watcher: {:?}", create);
 
@@ -204,8 +211,8 @@ impl EventDispatcher {
         let watcher_id = self.current_watcher_id;
         let filter: FilterType = create.filter_type();
 
-        let watcher = Watcher::new(watcher_id, filter, range.clone());
-        let stream_handle = WatchStreamHandle::new(watcher.clone(), tx);
+        let watcher = Watcher::new(watcher_id, filter, range.clone(), owner);
+        let stream_handle = WatchStreamHandle::new(watcher.clone(), tx, handle);
 
         self.watcher_range_map
             .insert(range, watcher_id, stream_handle);
@@ -219,10 +226,37 @@ impl EventDispatcher {
     pub fn remove_watcher(&mut self, key: &RangeMapKey<String, WatcherId>) {
         info!("remove_watcher: {:?}", key);
 
-        self.watcher_range_map.remove_by_key(key);
+        // A watcher may be removed more than once, e.g., cancelled and then its stream is dropped.
+        if self.watcher_range_map.remove_by_key(key).is_some() {
+            server_metrics::incr_watchers(-1);
+        }
+    }
+
+    /// Remove a watcher by id on behalf of `user`. Dropping its sender closes the stream to the client.
+    ///
+    /// Returns false if there is no such watcher,
+    /// or an error if the watcher is not created by `user`.
+    #[minitrace::trace]
+    pub fn cancel_watcher(&mut self, watcher_id: WatcherId, user: &str) -> Result<bool, String> {
+        let found = self
+            .watcher_range_map
+            .iter()
+            .find(|(k, _)| k.key == watcher_id)
+            .map(|(k, stream)| (k.clone(), stream.watcher.owner.clone()));
+
+        let Some((key, owner)) = found else {
+            return Ok(false);
+        };
+
+        if owner != user {
+            return Err(format!(
+                "user {} can not cancel stream {} of another user",
+                user, watcher_id
+            ));
+        }
 
-        // TODO: decrease it only when the key is actually removed
-        server_metrics::incr_watchers(-1);
+        self.remove_watcher(&key);
+        Ok(true)
     }
 
     fn build_key_range(
@@ -14,15 +14,21 @@
 
 use std::ops::Range;
 use std::pin::Pin;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
-use common_base::base::tokio::sync::mpsc::error::SendError;
+use common_base::base::tokio;
+use common_base::base::tokio::sync::mpsc;
+use common_base::base::tokio::sync::mpsc::error::TrySendError;
 use common_base::base::tokio::sync::mpsc::Receiver;
 use common_base::rangemap::RangeMapKey;
 use common_meta_types::protobuf::watch_request::FilterType;
 use common_meta_types::protobuf::WatchResponse;
 use futures::Stream;
+use log::warn;
 use tonic::Status;
 
 use super::WatcherId;
@@ -39,34 +45,107 @@ pub struct Watcher {
 
     /// The range of key this watcher is interested in.
     pub key_range: Range<String>,
+
+    /// The user that created this watcher, the only one allowed to cancel it.
+    pub owner: String,
 }
 
 impl Watcher {
-    pub fn new(id: WatcherId, filter_type: FilterType, key_range: Range<String>) -> Self {
+    pub fn new(
+        id: WatcherId,
+        filter_type: FilterType,
+        key_range: Range<String>,
+        owner: impl ToString,
+    ) -> Self {
         Self {
             id,
             filter_type,
             key_range,
+            owner: owner.to_string(),
         }
     }
 }
 
+/// The max number of events queued for a watcher, waiting to be sent to its stream.
+///
+/// It absorbs a burst of changes, such as a txn or a delete by prefix,
+/// while a client that can not keep up overflows it and is disconnected.
+pub const WATCHER_QUEUE_SIZE: usize = 1024;
+
 /// A handle of a watching stream, for feeding messages to the stream.
+///
+/// The messages are queued and sent to the stream by a task of this watcher,
+/// so that the dispatcher never waits for a client.
 pub struct WatchStreamHandle {
     pub watcher: Watcher,
-    tx: WatcherSender,
+    queue: mpsc::Sender<WatchResponse>,
+    /// Set when the queue is full: the stream is closed with `resource_exhausted`.
+    overflowed: Arc<AtomicBool>,
 }
 
 impl WatchStreamHandle {
-    pub fn new(watcher: Watcher, tx: WatcherSender) -> Self {
-        WatchStreamHandle { watcher, tx }
+    /// Create a handle and spawn the task sending its queued messages to `tx`.
+    ///
+    /// If the client does not receive within the timeout of `tx`,
+    /// the task quits and removes the watcher from `dispatcher`.
+    pub fn new(watcher: Watcher, tx: WatcherSender, dispatcher: EventDispatcherHandle) -> Self {
+        let (queue, rx) = mpsc::channel(WATCHER_QUEUE_SIZE);
+        let overflowed = Arc::new(AtomicBool::new(false));
+
+        tokio::spawn(Self::forward(
+            watcher.clone(),
+            rx,
+            tx,
+            overflowed.clone(),
+            dispatcher,
+        ));
+
+        WatchStreamHandle {
+            watcher,
+            queue,
+            overflowed,
+        }
     }
 
-    pub async fn send(
-        &self,
-        resp: WatchResponse,
-    ) -> Result<(), SendError<Result<WatchResponse, Status>>> {
-        self.tx.send(Ok(resp)).await
+    /// Queue a response to the watcher without waiting, or fail if the queue is full or it is closed.
+    pub fn try_send(&self, resp: WatchResponse) -> Result<(), TrySendError<WatchResponse>> {
+        let res = self.queue.try_send(resp);
+        if let Err(TrySendError::Full(_)) = &res {
+            self.overflowed.store(true, Ordering::Release);
+        }
+        res
+    }
+
+    async fn forward(
+        watcher: Watcher,
+        mut queue: mpsc::Receiver<WatchResponse>,
+        tx: WatcherSender,
+        overflowed: Arc<AtomicBool>,
+        dispatcher: EventDispatcherHandle,
+    ) {
+        while let Some(resp) = queue.recv().await {
+            if overflowed.load(Ordering::Acquire) {
+                break;
+            }
+
+            if tx.send(Ok(resp)).await.is_err() {
+                warn!(
+                    "close watcher stream {}: the client does not receive or is gone",
+                    watcher.id
+                );
+                let key = RangeMapKey::new(watcher.key_range.clone(), watcher.id);
+                dispatcher.request(move |d| d.remove_watcher(&key));
+                return;
+            }
+        }
+
+        if overflowed.load(Ordering::Acquire) {
+            let status = Status::resource_exhausted(format!(
+                "watcher stream {} can not keep up with the changes: more than {} events are pending",
+                watcher.id, WATCHER_QUEUE_SIZE
+            ));
+            let _ = tx.send(Err(status)).await;
+        }
     }
 }
 
@@ -65,6 +65,11 @@ join = ["j1", "j2"]
 id = 20
 sled_tree_prefix = "sled_foo"
 cluster_name = "foo_cluster"
+
+[log.tracing]
+on = true
+capture_log_level = "DEBUG"
+otlp_endpoint = "http://127.0.0.1:4318"
              "#
     )?;
 
@@ -92,6 +97,9 @@ cluster_name = "foo_cluster"
         assert_eq!(cfg.raft_config.id, 20);
         assert_eq!(cfg.raft_config.sled_tree_prefix, "sled_foo");
         assert_eq!(cfg.raft_config.cluster_name, "foo_cluster");
+        assert!(cfg.log.tracing.on);
+        assert_eq!(cfg.log.tracing.capture_log_level, "DEBUG");
+        assert_eq!(cfg.log.tracing.otlp_endpoint, "http://127.0.0.1:4318");
     });
 
     temp_env::with_vars(
@@ -123,5 +131,24 @@ cluster_name = "foo_cluster"
         },
     );
 
+    // Test tracing config.
+    temp_env::with_vars(
+        vec![
+            (
+                "METASRV_CONFIG_FILE",
+                Some(file_path.to_str().expect("must be valid str")),
+            ),
+            (
+                "METASRV_LOG_TRACING_OTLP_ENDPOINT",
+                Some("http://collector:4317"),
+            ),
+        ],
+        || {
+            let cfg = Config::load_for_test().expect("load must success");
+            assert!(cfg.log.tracing.on);
+            assert_eq!(cfg.log.tracing.otlp_endpoint, "http://collector:4317");
+        },
+    );
+
     Ok(())
 }
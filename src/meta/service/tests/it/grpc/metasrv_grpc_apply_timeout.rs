@@ -0,0 +1,80 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the apply timeout of writes of metasrv gRPC service.
+
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnRequest;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_apply_timeout() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_apply_timeout_ms = 1_000;
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- writes are applied in time");
+    client.upsert_kv(UpsertKVReq::update("foo", b"foo")).await?;
+
+    let meta_node = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+
+    info!("--- block applying by holding the state machine");
+    {
+        let _sm = meta_node.sto.get_state_machine().await;
+
+        let req = RaftRequest::from(MetaGrpcReq::UpsertKV(UpsertKVReq::update("bar", b"bar")));
+        let status = grpc_client.kv_api(req).await.unwrap_err();
+        assert_eq!(tonic::Code::DeadlineExceeded, status.code());
+        assert!(
+            status.message().starts_with("write is not applied in"),
+            "{}",
+            status.message()
+        );
+
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: vec![TxnOp::put("baz", b"baz".to_vec())],
+            else_then: vec![],
+        };
+        let status = grpc_client.transaction(txn).await.unwrap_err();
+        assert_eq!(tonic::Code::DeadlineExceeded, status.code());
+    }
+
+    info!("--- a timed out write is still applied once it is committed");
+    {
+        // Logs are applied in order: the timed out writes are applied when this one returns.
+        client.upsert_kv(UpsertKVReq::update("qux", b"qux")).await?;
+
+        let got = client.get_kv("bar").await?;
+        assert_eq!(b"bar".to_vec(), got.unwrap().data);
+
+        let got = client.get_kv("baz").await?;
+        assert_eq!(b"baz".to_vec(), got.unwrap().data);
+    }
+
+    Ok(())
+}
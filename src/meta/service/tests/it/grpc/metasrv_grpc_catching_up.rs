@@ -0,0 +1,183 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test that a node catching up does not serve local reads of incomplete data.
+
+use std::sync::atomic::Ordering;
+
+use common_meta_client::MetaGrpcReadReq;
+use common_meta_kvapi::kvapi::GetKVReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_sled_store::openraft::ServerState;
+use common_meta_types::protobuf::RaftRequest;
+use databend_meta::api::grpc::grpc_service::READ_CONSISTENCY_KEY;
+use databend_meta::meta_service::meta_node::CATCHING_UP_APPLY_LAG;
+use futures::TryStreamExt;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::meta_node::timeout;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+fn get_request(key: &str, consistency: Option<&str>) -> tonic::Request<RaftRequest> {
+    let req = MetaGrpcReadReq::GetKV(GetKVReq {
+        key: key.to_string(),
+    });
+    let mut request = tonic::Request::new(RaftRequest::from(req));
+    if let Some(consistency) = consistency {
+        request
+            .metadata_mut()
+            .insert(READ_CONSISTENCY_KEY, consistency.parse().unwrap());
+    }
+    request
+}
+
+/// - Start a leader and a read replica, and make the read replica appear to be installing a snapshot.
+/// - A read that requires to be local is rejected with `unavailable`.
+/// - A read through the leader, or without a consistency requirement, is forwarded to the leader.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_catching_up_read() -> anyhow::Result<()> {
+    let mut tc0 = MetaSrvTestContext::new(0);
+    start_metasrv_with_context(&mut tc0).await?;
+    let leader_addr = tc0.config.raft_config.raft_api_addr().await?;
+
+    let mut tc1 = MetaSrvTestContext::new(1);
+    tc1.config.raft_config.single = false;
+    tc1.config.raft_config.join = vec![leader_addr.to_string()];
+    tc1.config.raft_config.read_replica = true;
+    start_metasrv_with_context(&mut tc1).await?;
+
+    let leader = tc0.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    let replica = tc1.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+
+    replica
+        .raft
+        .wait(timeout())
+        .state(ServerState::Learner, "read replica is a learner")
+        .await?;
+
+    info!("--- write through the leader");
+    let leader_client = tc0.grpc_client().await?;
+    leader_client
+        .upsert_kv(UpsertKVReq::update("foo", b"foo"))
+        .await?;
+
+    let log_index = leader.raft.metrics().borrow().last_log_index;
+    replica
+        .raft
+        .wait(timeout())
+        .log(log_index, "read replica applied the write")
+        .await?;
+
+    let replica_client = tc1.grpc_client().await?;
+    let (mut replica_grpc, _server_version) = replica_client.make_client().await?;
+
+    info!("--- a local read is served when the read replica is up to date");
+    {
+        assert!(replica.catching_up().is_none());
+
+        let strm = replica_grpc
+            .kv_read_v1(get_request("foo", Some("local")))
+            .await?
+            .into_inner();
+        let items = strm.try_collect::<Vec<_>>().await?;
+        assert_eq!(b"foo".to_vec(), items[0].value.as_ref().unwrap().data);
+    }
+
+    info!("--- simulate the read replica installing a snapshot");
+    replica
+        .sto
+        .installing_snapshot
+        .store(true, Ordering::Relaxed);
+    assert_eq!(
+        Some("installing snapshot".to_string()),
+        replica.catching_up()
+    );
+
+    info!("--- a read that requires to be local is rejected");
+    {
+        let status = replica_grpc
+            .kv_read_v1(get_request("foo", Some("local")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::Unavailable, status.code());
+        assert!(
+            status.message().contains("catching up"),
+            "got: {:?}",
+            status
+        );
+    }
+
+    info!("--- a read through the leader is forwarded");
+    for consistency in [Some("leader"), None] {
+        let strm = replica_grpc
+            .kv_read_v1(get_request("foo", consistency))
+            .await?
+            .into_inner();
+        let items = strm.try_collect::<Vec<_>>().await?;
+        assert_eq!(b"foo".to_vec(), items[0].value.as_ref().unwrap().data);
+    }
+
+    info!("--- an invalid consistency is rejected");
+    {
+        let status = replica_grpc
+            .kv_read_v1(get_request("foo", Some("eventual")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::InvalidArgument, status.code());
+    }
+
+    info!("--- local reads are served again when the snapshot is installed");
+    replica
+        .sto
+        .installing_snapshot
+        .store(false, Ordering::Relaxed);
+    {
+        let strm = replica_grpc
+            .kv_read_v1(get_request("foo", Some("local")))
+            .await?
+            .into_inner();
+        let items = strm.try_collect::<Vec<_>>().await?;
+        assert_eq!(b"foo".to_vec(), items[0].value.as_ref().unwrap().data);
+    }
+
+    info!("--- the leader has replicated its committed index with append-entries");
+    {
+        let committed = leader.raft.metrics().borrow().last_applied.unwrap().index;
+        assert!(replica.leader_committed.load(Ordering::Relaxed) <= committed);
+    }
+
+    info!("--- simulate the leader committing far more logs than the read replica received");
+    {
+        let applied = replica.raft.metrics().borrow().last_applied.unwrap().index;
+        replica
+            .leader_committed
+            .store(applied + CATCHING_UP_APPLY_LAG + 1, Ordering::Relaxed);
+
+        let reason = replica.catching_up().unwrap();
+        assert!(reason.contains("lag"), "got: {}", reason);
+
+        let status = replica_grpc
+            .kv_read_v1(get_request("foo", Some("local")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::Unavailable, status.code());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the count_prefix() API.
+
+use std::time::Duration;
+
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::protobuf::CountPrefixRequest;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::start_metasrv_cluster;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_count_prefix() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- write keys under two prefixes");
+    for key in ["a/1", "a/2", "a/3", "b/1"] {
+        client
+            .upsert_kv(UpsertKVReq::update(key, key.as_bytes()))
+            .await?;
+    }
+
+    let all = client.prefix_list_kv("").await?.len() as u64;
+
+    let cases = [
+        ("a/", 3),
+        ("b/", 1),
+        // A nonexistent prefix counts nothing.
+        ("c/", 0),
+        // An empty prefix counts the whole keyspace.
+        ("", all),
+    ];
+
+    for (prefix, want) in cases {
+        info!("--- count prefix: {:?}", prefix);
+        let reply = grpc_client
+            .count_prefix(CountPrefixRequest {
+                prefix: prefix.to_string(),
+            })
+            .await?
+            .into_inner();
+        assert_eq!(want, reply.count, "prefix: {:?}", prefix);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_count_prefix_on_follower() -> anyhow::Result<()> {
+    let tcs = start_metasrv_cluster(&[0, 1]).await?;
+
+    let leader_client = tcs[0].grpc_client().await?;
+    for key in ["a/1", "a/2"] {
+        leader_client
+            .upsert_kv(UpsertKVReq::update(key, key.as_bytes()))
+            .await?;
+    }
+
+    let follower = tcs[1].grpc_client().await?;
+    let (mut grpc_client, _server_version) = follower.make_client().await?;
+
+    let req = || CountPrefixRequest {
+        prefix: "a/".to_string(),
+    };
+
+    info!("--- a follower does not forward count prefix to a leader that may not understand it");
+    {
+        let status = grpc_client.count_prefix(req()).await.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert!(status.message().contains("count prefix"), "{}", status);
+    }
+
+    let leader = tcs[0].grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    leader.set_cluster_version(CLUSTER_VERSION_V1).await?;
+
+    let mn1 = tcs[1].grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    let applied = leader.raft.metrics().borrow().last_applied;
+    mn1.raft
+        .wait(Some(Duration::from_secs(10)))
+        .metrics(
+            |m| m.last_applied >= applied,
+            "follower applied cluster version",
+        )
+        .await?;
+
+    info!("--- a follower forwards count prefix once the cluster version is raised");
+    {
+        let reply = grpc_client.count_prefix(req()).await?.into_inner();
+        assert_eq!(2, reply.count);
+    }
+
+    Ok(())
+}
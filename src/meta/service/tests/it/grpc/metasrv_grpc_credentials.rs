@@ -0,0 +1,245 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test that handshake passwords are verified against the stored PBKDF2 hashes,
+//! and client certificates are mapped to users.
+
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_client::MetaGrpcClient;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use databend_meta::api::grpc::credentials::CertUsers;
+use databend_meta::api::grpc::credentials::PasswordHash;
+use databend_meta::api::grpc::credentials::UserCredentials;
+use databend_meta::api::grpc::credentials::MAX_CONCURRENT_PASSWORD_VERIFY;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_password_hash() -> anyhow::Result<()> {
+    let iterations = NonZeroU32::new(1000).unwrap();
+    let hash = PasswordHash::new("secret", b"salt".to_vec(), iterations);
+
+    info!("--- a correct password is verified, a wrong one is not");
+    {
+        assert!(hash.verify("secret"));
+        assert!(!hash.verify("secreT"));
+        assert!(!hash.verify("secret "));
+        assert!(!hash.verify(""));
+    }
+
+    info!("--- the same password with another salt derives another hash");
+    {
+        let other = PasswordHash::new("secret", b"pepper".to_vec(), iterations);
+        assert_ne!(hash, other);
+        assert!(other.verify("secret"));
+    }
+
+    info!("--- encoded hash is parsed back");
+    {
+        let encoded = hash.to_string();
+        assert!(encoded.starts_with("pbkdf2-sha256$1000$"));
+        assert!(!encoded.contains("secret"));
+
+        let parsed: PasswordHash = encoded.parse().map_err(anyhow::Error::msg)?;
+        assert_eq!(hash, parsed);
+        assert!(parsed.verify("secret"));
+    }
+
+    info!("--- a generated hash has a random salt");
+    {
+        let a = PasswordHash::generate("secret");
+        let b = PasswordHash::generate("secret");
+        assert_ne!(a, b);
+        assert!(a.verify("secret"));
+        assert!(!a.verify("wrong"));
+    }
+
+    info!("--- invalid encoded hash");
+    {
+        assert!("secret".parse::<PasswordHash>().is_err());
+        assert!("sha1$1000$73$00".parse::<PasswordHash>().is_err());
+        assert!(
+            "pbkdf2-sha256$0$73616c74$00"
+                .parse::<PasswordHash>()
+                .is_err()
+        );
+        assert!("pbkdf2-sha256$1000$xx$00".parse::<PasswordHash>().is_err());
+        assert!(
+            "pbkdf2-sha256$1000$73616c74$00"
+                .parse::<PasswordHash>()
+                .is_err()
+        );
+    }
+
+    info!("--- a user without a stored hash is rejected");
+    {
+        let credentials: UserCredentials = format!("alice={}", hash)
+            .parse()
+            .map_err(anyhow::Error::msg)?;
+        assert!(credentials.check("alice", "secret").await.is_ok());
+        assert!(credentials.check("alice", "wrong").await.is_err());
+        assert!(credentials.check("bob", "anything").await.is_err());
+        assert!(UserCredentials::default().check("bob", "").await.is_err());
+    }
+
+    info!("--- a verified password is cached, while another password is still checked");
+    {
+        let credentials: UserCredentials = format!("alice={}", hash)
+            .parse()
+            .map_err(anyhow::Error::msg)?;
+        assert!(credentials.check("alice", "secret").await.is_ok());
+        assert!(credentials.check("alice", "secret").await.is_ok());
+        assert!(credentials.check("alice", "wrong").await.is_err());
+        assert!(credentials.check("alice", "").await.is_err());
+        assert!(credentials.check("alice", "secret").await.is_ok());
+
+        // A clone shares the cache.
+        let cloned = credentials.clone();
+        assert!(cloned.check("alice", "secret").await.is_ok());
+        assert!(cloned.check("alice", "wrong").await.is_err());
+    }
+
+    info!("--- more checks than the concurrency limit all finish");
+    {
+        let credentials: UserCredentials = format!("alice={}", hash)
+            .parse()
+            .map_err(anyhow::Error::msg)?;
+
+        let mut handles = vec![];
+        for i in 0..MAX_CONCURRENT_PASSWORD_VERIFY * 4 {
+            let credentials = credentials.clone();
+            let password = if i % 2 == 0 { "secret" } else { "wrong" };
+            handles.push(tokio::spawn(async move {
+                (password, credentials.check("alice", password).await.is_ok())
+            }));
+        }
+
+        for h in handles {
+            let (password, ok) = h.await?;
+            assert_eq!(password == "secret", ok, "password: {}", password);
+        }
+    }
+
+    info!("--- any password passes if the check is disabled");
+    {
+        let credentials: UserCredentials = format!("alice={}", hash)
+            .parse::<UserCredentials>()
+            .map_err(anyhow::Error::msg)?
+            .with_disabled(true);
+        assert!(credentials.check("alice", "wrong").await.is_ok());
+        assert!(credentials.check("bob", "anything").await.is_ok());
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_cert_users() -> anyhow::Result<()> {
+    let alice_cert = b"alice-cert-der".as_slice();
+    let bob_cert = b"bob-cert-der".as_slice();
+    let fingerprint = hex::encode(CertUsers::fingerprint(alice_cert));
+
+    info!("--- a certificate is identified by its fingerprint");
+    {
+        let users: CertUsers = format!("alice={}", fingerprint)
+            .parse()
+            .map_err(anyhow::Error::msg)?;
+        assert_eq!(Some("alice"), users.user_of([alice_cert]));
+        assert_eq!(Some("alice"), users.user_of([bob_cert, alice_cert]));
+        assert_eq!(None, users.user_of([bob_cert]));
+        assert_eq!(None, users.user_of(Vec::<&[u8]>::new()));
+    }
+
+    info!("--- invalid cert users");
+    {
+        assert!("alice".parse::<CertUsers>().is_err());
+        assert!(format!("={}", fingerprint).parse::<CertUsers>().is_err());
+        assert!("alice=xx".parse::<CertUsers>().is_err());
+        assert!("alice=0011".parse::<CertUsers>().is_err());
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_handshake_password() -> anyhow::Result<()> {
+    let hash = PasswordHash::new("alice-pw", b"salt".to_vec(), NonZeroU32::new(1000).unwrap());
+
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/".to_string();
+    tc.config.grpc_user_credentials = format!("alice={}", hash);
+    tc.config.grpc_disable_password_auth = false;
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = |password: &str| {
+        MetaGrpcClient::try_create(
+            vec![tc.config.grpc_api_address.clone()],
+            "alice",
+            password,
+            None,
+            Some(Duration::from_secs(10)),
+            Duration::from_secs(10),
+            None,
+        )
+    };
+
+    info!("--- handshake with the correct password");
+    {
+        let alice = client("alice-pw")?;
+        alice
+            .upsert_kv(UpsertKVReq::update("alice/foo", b"foo"))
+            .await?;
+        let got = alice.get_kv("alice/foo").await?;
+        assert_eq!(b"foo".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- handshake with a wrong password is rejected");
+    {
+        let alice = client("wrong")?;
+        let res = alice.get_kv("alice/foo").await;
+        let err = res.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid credentials for user: alice"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    info!("--- a user without a stored hash is rejected");
+    {
+        let root = tc.grpc_client().await?;
+        let err = root.get_kv("alice/foo").await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid credentials for user: root"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    Ok(())
+}
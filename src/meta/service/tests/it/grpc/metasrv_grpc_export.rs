@@ -17,7 +17,11 @@ use std::time::Duration;
 use common_base::base::tokio::time::sleep;
 use common_meta_kvapi::kvapi::KVApi;
 use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_raft_store::key_spaces::RaftStoreEntry;
 use common_meta_types::protobuf::Empty;
+use common_meta_types::protobuf::ExportedChunk;
+use common_meta_types::SeqV;
+use databend_meta::api::grpc::grpc_service::MAX_IMPORT_TXN_OPS;
 use log::info;
 use pretty_assertions::assert_eq;
 use regex::Regex;
@@ -119,6 +123,138 @@ async fn test_export() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_export_import() -> anyhow::Result<()> {
+    // - Start a metasrv server and write some data.
+    // - Export all data.
+    // - Import the exported data into another metasrv server.
+    // - Assert the key-values are reproduced.
+
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+
+    info!("--- upsert kv");
+    {
+        for k in ["foo", "bar", "wow"] {
+            client.upsert_kv(UpsertKVReq::update(k, &b(k))).await?;
+        }
+    }
+
+    let mn = tc
+        .grpc_srv
+        .as_ref()
+        .map(|grpc_server| grpc_server.get_meta_node())
+        .unwrap();
+    mn.raft.trigger().snapshot().await?;
+
+    // Wait for snapshot to be ready
+    sleep(Duration::from_secs(2)).await;
+
+    info!("--- export");
+    let chunks = {
+        let (mut grpc_client, _server_version) = client.make_client().await?;
+        let exported = grpc_client.export(tonic::Request::new(Empty {})).await?;
+
+        let mut stream = exported.into_inner();
+        let mut chunks = vec![];
+        while let Some(chunk_res) = stream.next().await {
+            chunks.push(chunk_res?);
+        }
+        chunks
+    };
+
+    info!("--- import into a fresh metasrv");
+    let (tc2, _addr2) = crate::tests::start_metasrv().await?;
+    let client2 = tc2.grpc_client().await?;
+    {
+        let (mut grpc_client, _server_version) = client2.make_client().await?;
+        let reply = grpc_client
+            .import(tonic::Request::new(tokio_stream::iter(chunks)))
+            .await?;
+
+        assert_eq!(3, reply.into_inner().imported);
+    }
+
+    info!("--- check the imported key-values");
+    {
+        let got = client2.prefix_list_kv("").await?;
+        let got = got
+            .into_iter()
+            .map(|(k, v)| (k, v.data))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (s("bar"), b("bar")),
+                (s("foo"), b("foo")),
+                (s("wow"), b("wow")),
+            ],
+            got
+        );
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_import_splits_large_chunk() -> anyhow::Result<()> {
+    // - Import a chunk with more entries than MAX_IMPORT_TXN_OPS.
+    // - Assert it is applied in several txns, i.e., several raft logs.
+
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+    let client = tc.grpc_client().await?;
+
+    let n = MAX_IMPORT_TXN_OPS * 2 + 1;
+
+    let data = (0..n)
+        .map(|i| {
+            let entry = RaftStoreEntry::GenericKV {
+                key: format!("k{:04}", i),
+                value: SeqV::new(1, b(i)),
+            };
+            serde_json::to_string(&("state_machine/0", entry)).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mn = tc
+        .grpc_srv
+        .as_ref()
+        .map(|grpc_server| grpc_server.get_meta_node())
+        .unwrap();
+    let applied_before = mn.raft.metrics().borrow().last_applied.unwrap().index;
+
+    info!("--- import {} entries in one chunk", n);
+    {
+        let (mut grpc_client, _server_version) = client.make_client().await?;
+        let reply = grpc_client
+            .import(tonic::Request::new(tokio_stream::iter(vec![
+                ExportedChunk { data },
+            ])))
+            .await?;
+
+        assert_eq!(n as u64, reply.into_inner().imported);
+    }
+
+    let applied_after = mn.raft.metrics().borrow().last_applied.unwrap().index;
+    assert_eq!(
+        3,
+        applied_after - applied_before,
+        "one txn per MAX_IMPORT_TXN_OPS entries"
+    );
+
+    let got = client.prefix_list_kv("k").await?;
+    assert_eq!(n, got.len());
+
+    Ok(())
+}
+
+fn s(x: &str) -> String {
+    x.to_string()
+}
+
 fn b(s: impl ToString) -> Vec<u8> {
     s.to_string().into_bytes()
 }
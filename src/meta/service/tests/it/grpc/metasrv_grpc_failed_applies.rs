@@ -0,0 +1,118 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the get_failed_applies() admin API.
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_client::MetaGrpcClient;
+use common_meta_types::protobuf::Empty;
+use common_meta_types::Cmd;
+use common_meta_types::EntryPayload;
+use common_meta_types::LogEntry;
+use common_meta_types::UpsertKV;
+use databend_meta::store::FailedApply;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_get_failed_applies() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    let alice = MetaGrpcClient::try_create(
+        vec![tc.config.grpc_api_address.clone()],
+        "alice",
+        "xxx",
+        None,
+        Some(Duration::from_secs(10)),
+        Duration::from_secs(10),
+        None,
+    )?;
+    let (mut alice_client, _server_version) = alice.make_client().await?;
+
+    let mn = tc.meta_node();
+
+    info!("--- no failure yet");
+    {
+        let reply = grpc_client.get_failed_applies(Empty {}).await?.into_inner();
+        assert!(reply.failed_applies.is_empty(), "{:?}", reply);
+    }
+
+    info!("--- fail to apply an entry");
+    let key = "t-failed-apply";
+    {
+        let mut sm = mn.sto.get_state_machine().await;
+        sm.blocking_config_mut().fail_apply = true;
+    }
+
+    // An apply failure is fatal to raft, the write does not have to return.
+    let _ = tokio::time::timeout(
+        Duration::from_secs(3),
+        mn.write(LogEntry {
+            txid: None,
+            time_ms: None,
+            cmd: Cmd::UpsertKV(UpsertKV::update(key, key.as_bytes())),
+        }),
+    )
+    .await;
+
+    info!("--- only root can get the failed applies");
+    {
+        let status = alice_client.get_failed_applies(Empty {}).await.unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+    }
+
+    info!("--- root gets the failed entry and the error");
+    {
+        let reply = grpc_client.get_failed_applies(Empty {}).await?.into_inner();
+
+        let got = reply
+            .failed_applies
+            .iter()
+            .map(|s| serde_json::from_str(s))
+            .collect::<Result<Vec<FailedApply>, _>>()?;
+
+        assert_eq!(mn.sto.failed_applies(), got);
+        assert_eq!(1, got.len(), "got: {:?}", got);
+
+        let failed = &got[0];
+        assert_eq!(failed.log_id, failed.entry.log_id);
+        match &failed.entry.payload {
+            EntryPayload::Normal(log_entry) => match &log_entry.cmd {
+                Cmd::UpsertKV(upsert) => assert_eq!(key, upsert.key),
+                cmd => panic!("unexpected cmd: {}", cmd),
+            },
+            payload => panic!("unexpected payload: {:?}", payload),
+        }
+        assert!(
+            failed.error.contains("apply is set to fail for testing"),
+            "got: {:?}",
+            failed
+        );
+    }
+
+    Ok(())
+}
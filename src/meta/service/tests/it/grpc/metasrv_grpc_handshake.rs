@@ -18,20 +18,35 @@
 use std::ops::Deref;
 use std::time::Duration;
 
+use common_arrow::arrow_format::flight::data::BasicAuth;
 use common_grpc::ConnectionFactory;
+use common_grpc::GrpcClaim;
+use common_grpc::GrpcToken;
 use common_meta_client::from_digit_ver;
+use common_meta_client::reply_to_api_result;
 use common_meta_client::to_digit_ver;
 use common_meta_client::MetaGrpcClient;
+use common_meta_client::MetaGrpcReq;
 use common_meta_client::METACLI_COMMIT_SEMVER;
 use common_meta_client::MIN_METASRV_SEMVER;
+use common_meta_kvapi::kvapi::UpsertKVReply;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::handshake_request::AuthMethod;
+use common_meta_types::protobuf::HandshakeRequest;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::Features;
 use databend_meta::version::MIN_METACLI_SEMVER;
+use futures::StreamExt;
 use log::debug;
 use log::info;
+use prost::Message;
 use semver::Version;
 use test_harness::test;
 
 use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
 use crate::tests::start_metasrv;
+use crate::tests::start_metasrv_with_context;
 
 /// - Test client version < serverside min-compatible-client-ver.
 /// - Test metasrv version < client min-compatible-metasrv-ver.
@@ -120,3 +135,229 @@ async fn test_metasrv_handshake() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// A client advertising compression gets compressed replies,
+/// while a legacy client that sends no features gets plaintext JSON replies.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_handshake_features() -> anyhow::Result<()> {
+    let (_tc, addr) = start_metasrv().await?;
+
+    for (advertised, want_compressed) in [(Features::COMPRESSION, true), (Features::NONE, false)] {
+        info!("--- client advertises: {:?}", advertised);
+
+        let c = ConnectionFactory::create_rpc_channel(
+            addr.clone(),
+            Some(Duration::from_millis(1000)),
+            None,
+        )
+        .await?;
+        let (mut client, once) = MetaGrpcClient::new_real_client(c);
+
+        let auth = BasicAuth {
+            username: "root".to_string(),
+            password: "xxx".to_string(),
+        };
+        let req = HandshakeRequest {
+            protocol_version: to_digit_ver(&METACLI_COMMIT_SEMVER),
+            payload: auth.encode_to_vec(),
+            features: advertised.bits(),
+            auth_method: AuthMethod::BasicAuth as i32,
+        };
+
+        let mut strm = client
+            .handshake(futures::stream::once(async move { req }))
+            .await?
+            .into_inner();
+        let resp = strm.next().await.unwrap()?;
+
+        assert_eq!(advertised.bits(), resp.features);
+        once.set(resp.payload).unwrap();
+
+        let req = MetaGrpcReq::UpsertKV(UpsertKVReq::update("foo", b"foo"));
+        let reply = client.kv_api(RaftRequest::from(req)).await?.into_inner();
+
+        assert_eq!(want_compressed, !reply.compressed_data.is_empty());
+        assert_eq!(want_compressed, reply.data.is_empty());
+
+        let res: UpsertKVReply = reply_to_api_result(reply)?;
+        assert_eq!(Some(b"foo".to_vec()), res.result.map(|x| x.data));
+    }
+
+    Ok(())
+}
+
+/// Handshake with `auth_method` and return the token in the response.
+async fn handshake_with(
+    addr: &str,
+    auth_method: AuthMethod,
+    payload: Vec<u8>,
+) -> anyhow::Result<Result<Vec<u8>, tonic::Status>> {
+    let c = ConnectionFactory::create_rpc_channel(addr, Some(Duration::from_millis(1000)), None)
+        .await?;
+    let (mut client, _once) = MetaGrpcClient::new_real_client(c);
+
+    let req = HandshakeRequest {
+        protocol_version: to_digit_ver(&METACLI_COMMIT_SEMVER),
+        payload,
+        features: Features::COMPRESSION.bits(),
+        auth_method: auth_method as i32,
+    };
+
+    let res = client
+        .handshake(futures::stream::once(async move { req }))
+        .await;
+    let mut strm = match res {
+        Ok(resp) => resp.into_inner(),
+        Err(status) => return Ok(Err(status)),
+    };
+
+    match strm.next().await.unwrap() {
+        Ok(resp) => {
+            assert_eq!(Features::COMPRESSION.bits(), resp.features);
+            Ok(Ok(resp.payload))
+        }
+        Err(status) => Ok(Err(status)),
+    }
+}
+
+/// Upsert a key with the token of a handshake and return the result.
+async fn upsert_with_token(
+    addr: &str,
+    token: Vec<u8>,
+    key: &str,
+) -> anyhow::Result<Result<UpsertKVReply, tonic::Status>> {
+    let c = ConnectionFactory::create_rpc_channel(addr, Some(Duration::from_millis(1000)), None)
+        .await?;
+    let (mut client, once) = MetaGrpcClient::new_real_client(c);
+    once.set(token).unwrap();
+
+    let req = MetaGrpcReq::UpsertKV(UpsertKVReq::update(key, key.as_bytes()));
+    let reply = match client.kv_api(RaftRequest::from(req)).await {
+        Ok(reply) => reply.into_inner(),
+        Err(status) => return Ok(Err(status)),
+    };
+
+    // The negotiated compression is kept in the claim.
+    assert!(!reply.compressed_data.is_empty());
+
+    Ok(Ok(reply_to_api_result(reply)?))
+}
+
+/// A handshake with a token issued by a previous handshake produces an equivalent claim
+/// to the one of `BasicAuth`.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_handshake_auth_method() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/".to_string();
+    tc.config.grpc_disable_root = true;
+
+    start_metasrv_with_context(&mut tc).await?;
+    let addr = tc.config.grpc_api_address.clone();
+
+    let basic_auth = |username: &str| {
+        BasicAuth {
+            username: username.to_string(),
+            password: "xxx".to_string(),
+        }
+        .encode_to_vec()
+    };
+
+    info!("--- BasicAuth is the default");
+    let basic_token = handshake_with(&addr, AuthMethod::BasicAuth, basic_auth("alice")).await??;
+
+    info!("--- handshake with the token of BasicAuth does not re-issue it");
+    let jwt_token = handshake_with(&addr, AuthMethod::Jwt, basic_token.clone()).await??;
+    assert_eq!(basic_token, jwt_token);
+
+    info!("--- both tokens act as alice with the negotiated features");
+    for token in [basic_token, jwt_token] {
+        let res = upsert_with_token(&addr, token.clone(), "alice/foo").await??;
+        assert_eq!(Some(b"alice/foo".to_vec()), res.result.map(|x| x.data));
+
+        let status = upsert_with_token(&addr, token, "bob/foo")
+            .await?
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+        assert!(
+            status
+                .message()
+                .contains("user alice is not allowed to access key: bob/foo"),
+            "unexpected error: {}",
+            status
+        );
+    }
+
+    info!("--- a token that is not issued by this server is rejected");
+    {
+        let status = handshake_with(&addr, AuthMethod::Jwt, b"not-a-token".to_vec())
+            .await?
+            .unwrap_err();
+        assert_eq!(tonic::Code::Unauthenticated, status.code());
+        assert!(status.message().contains("jwt verify failed"), "{}", status);
+    }
+
+    info!("--- a token signed with another secret is rejected");
+    {
+        let other = GrpcToken::create_with_secret(b"other-secret").try_create_token(GrpcClaim {
+            username: "alice".to_string(),
+            features: Features::COMPRESSION.bits(),
+        })?;
+        let status = handshake_with(&addr, AuthMethod::Jwt, other.into_bytes())
+            .await?
+            .unwrap_err();
+        assert_eq!(tonic::Code::Unauthenticated, status.code());
+        assert!(status.message().contains("jwt verify failed"), "{}", status);
+    }
+
+    info!("--- the user checks apply to every auth method");
+    {
+        let status = handshake_with(&addr, AuthMethod::BasicAuth, basic_auth("root"))
+            .await?
+            .unwrap_err();
+        assert_eq!(tonic::Code::Unauthenticated, status.code());
+        assert!(status.message().contains("user root is disabled"));
+    }
+
+    info!("--- mTLS without a client certificate is rejected");
+    {
+        let status = handshake_with(&addr, AuthMethod::Mtls, vec![])
+            .await?
+            .unwrap_err();
+        assert_eq!(tonic::Code::Unauthenticated, status.code());
+        assert!(
+            status.message().contains("requires a client certificate"),
+            "{}",
+            status
+        );
+    }
+
+    Ok(())
+}
+
+/// Without a configured `token_secret` there is no issuer key to verify a JWT handshake against.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_handshake_jwt_requires_token_secret() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.raft_config.token_secret = "".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+    let addr = tc.config.grpc_api_address.clone();
+
+    let basic_auth = BasicAuth {
+        username: "root".to_string(),
+        password: "xxx".to_string(),
+    }
+    .encode_to_vec();
+    let token = handshake_with(&addr, AuthMethod::BasicAuth, basic_auth).await??;
+
+    let status = handshake_with(&addr, AuthMethod::Jwt, token)
+        .await?
+        .unwrap_err();
+    assert_eq!(tonic::Code::Unauthenticated, status.code());
+    assert!(status.message().contains("token_secret"), "{}", status);
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the increment() API.
+
+use common_base::base::tokio;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::protobuf::IncrementRequest;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_increment() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    let req = |key: &str, delta: i64| IncrementRequest {
+        key: key.to_string(),
+        delta,
+    };
+
+    info!("--- increment is rejected before the cluster is upgraded");
+    {
+        let status = grpc_client.increment(req("c/new", 5)).await.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert!(status.message().contains("cluster version"), "{}", status);
+
+        assert!(client.get_kv("c/new").await?.is_none());
+    }
+
+    tc.meta_node()
+        .set_cluster_version(CLUSTER_VERSION_V1)
+        .await?;
+
+    info!("--- an absent key is created with the delta");
+    {
+        let reply = grpc_client.increment(req("c/new", 5)).await?.into_inner();
+        assert_eq!(5, reply.value);
+
+        let got = client.get_kv("c/new").await?;
+        assert_eq!(b"5".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- increment an existing counter");
+    {
+        client.upsert_kv(UpsertKVReq::update("c/a", b"10")).await?;
+
+        let reply = grpc_client.increment(req("c/a", 3)).await?.into_inner();
+        assert_eq!(13, reply.value);
+
+        let reply = grpc_client.increment(req("c/a", -20)).await?.into_inner();
+        assert_eq!(-7, reply.value);
+
+        let got = client.get_kv("c/a").await?;
+        assert_eq!(b"-7".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- a value that is not a number is left unchanged");
+    {
+        client.upsert_kv(UpsertKVReq::update("c/s", b"foo")).await?;
+        let before = client.get_kv("c/s").await?.unwrap();
+
+        let res = grpc_client.increment(req("c/s", 1)).await;
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert!(status.message().contains("not an i64"), "{}", status);
+
+        let after = client.get_kv("c/s").await?.unwrap();
+        assert_eq!(before, after);
+    }
+
+    info!("--- an overflowing sum is rejected");
+    {
+        grpc_client.increment(req("c/max", i64::MAX)).await?;
+
+        let res = grpc_client.increment(req("c/max", 1)).await;
+        assert_eq!(tonic::Code::FailedPrecondition, res.unwrap_err().code());
+
+        let got = client.get_kv("c/max").await?;
+        assert_eq!(i64::MAX.to_string().into_bytes(), got.unwrap().data);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_increment_concurrent() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+    tc.meta_node()
+        .set_cluster_version(CLUSTER_VERSION_V1)
+        .await?;
+
+    let client = tc.grpc_client().await?;
+    let (grpc_client, _server_version) = client.make_client().await?;
+
+    let n_tasks = 10;
+    let n_per_task = 20;
+
+    info!("--- increment a counter concurrently");
+    let mut handles = vec![];
+    for i in 0..n_tasks {
+        let mut grpc_client = grpc_client.clone();
+        handles.push(tokio::spawn(async move {
+            let mut values = vec![];
+            for _ in 0..n_per_task {
+                let reply = grpc_client
+                    .increment(IncrementRequest {
+                        key: "counter".to_string(),
+                        delta: i + 1,
+                    })
+                    .await?
+                    .into_inner();
+                values.push(reply.value);
+            }
+            Ok::<_, tonic::Status>(values)
+        }));
+    }
+
+    let mut values = vec![];
+    for h in handles {
+        values.extend(h.await??);
+    }
+
+    info!("--- no increment is lost");
+    {
+        let want = (1..=n_tasks).sum::<i64>() * n_per_task;
+
+        let got = client.get_kv("counter").await?;
+        assert_eq!(want.to_string().into_bytes(), got.unwrap().data);
+
+        // Every increment sees a distinct value, the last of which is the sum.
+        values.sort();
+        values.dedup();
+        assert_eq!(n_tasks as usize * n_per_task as usize, values.len());
+        assert_eq!(Some(&want), values.last());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,301 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test per-user key prefix acl of metasrv gRPC API.
+
+use std::time::Duration;
+
+use common_meta_client::MetaGrpcClient;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::watch_request::FilterType;
+use common_meta_types::protobuf::CancelStreamRequest;
+use common_meta_types::protobuf::Empty;
+use common_meta_types::protobuf::WatchRequest;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_kv_acl() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/,shared/".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let addr = tc.config.grpc_api_address.clone();
+    let alice = MetaGrpcClient::try_create(
+        vec![addr],
+        "alice",
+        "xxx",
+        None,
+        Some(Duration::from_secs(10)),
+        Duration::from_secs(10),
+        None,
+    )?;
+
+    info!("--- write to an allowed prefix");
+    {
+        alice
+            .upsert_kv(UpsertKVReq::update("alice/foo", b"foo"))
+            .await?;
+        let got = alice.get_kv("alice/foo").await?;
+        assert_eq!(b"foo".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- write out of allowed prefixes is denied");
+    {
+        let res = alice
+            .upsert_kv(UpsertKVReq::update("bob/foo", b"foo"))
+            .await;
+        let err = res.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("user alice is not allowed to access key: bob/foo"),
+            "unexpected error: {}",
+            err
+        );
+
+        let res = alice.get_kv("bob/foo").await;
+        assert!(res.is_err());
+
+        let res = alice.prefix_list_kv("").await;
+        assert!(res.is_err());
+    }
+
+    info!("--- root writes anywhere");
+    {
+        let root = tc.grpc_client().await?;
+        root.upsert_kv(UpsertKVReq::update("bob/foo", b"bar"))
+            .await?;
+        root.upsert_kv(UpsertKVReq::update("alice/bar", b"bar"))
+            .await?;
+
+        let got = root.get_kv("bob/foo").await?;
+        assert_eq!(b"bar".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- unknown user can not handshake");
+    {
+        let addr = tc.config.grpc_api_address.clone();
+        let bob = MetaGrpcClient::try_create(
+            vec![addr],
+            "bob",
+            "xxx",
+            None,
+            Some(Duration::from_secs(10)),
+            Duration::from_secs(10),
+            None,
+        )?;
+
+        let res = bob.get_kv("bob/foo").await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_root_disabled() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/".to_string();
+    tc.config.grpc_disable_root = true;
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    info!("--- root handshake is rejected");
+    {
+        let root = tc.grpc_client().await?;
+        let res = root.get_kv("alice/foo").await;
+        let err = res.unwrap_err();
+        assert!(
+            err.to_string().contains("user root is disabled"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    info!("--- a configured user still handshakes");
+    {
+        let addr = tc.config.grpc_api_address.clone();
+        let alice = MetaGrpcClient::try_create(
+            vec![addr],
+            "alice",
+            "xxx",
+            None,
+            Some(Duration::from_secs(10)),
+            Duration::from_secs(10),
+            None,
+        )?;
+
+        alice
+            .upsert_kv(UpsertKVReq::update("alice/foo", b"foo"))
+            .await?;
+        let got = alice.get_kv("alice/foo").await?;
+        assert_eq!(b"foo".to_vec(), got.unwrap().data);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_export_requires_root() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let addr = tc.config.grpc_api_address.clone();
+    let alice = MetaGrpcClient::try_create(
+        vec![addr],
+        "alice",
+        "xxx",
+        None,
+        Some(Duration::from_secs(10)),
+        Duration::from_secs(10),
+        None,
+    )?;
+
+    info!("--- a non-root user can not export");
+    {
+        let (mut grpc_client, _server_version) = alice.make_client().await?;
+        let res = grpc_client.export(tonic::Request::new(Empty {})).await;
+
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+    }
+
+    info!("--- root exports");
+    {
+        let root = tc.grpc_client().await?;
+        let (mut grpc_client, _server_version) = root.make_client().await?;
+        grpc_client.export(tonic::Request::new(Empty {})).await?;
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_watch_acl() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/,shared/".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let addr = tc.config.grpc_api_address.clone();
+    let alice = MetaGrpcClient::try_create(
+        vec![addr],
+        "alice",
+        "xxx",
+        None,
+        Some(Duration::from_secs(10)),
+        Duration::from_secs(10),
+        None,
+    )?;
+
+    let watch = |key: &str, key_end: Option<&str>| WatchRequest {
+        key: key.to_string(),
+        key_end: key_end.map(|x| x.to_string()),
+        filter_type: FilterType::All.into(),
+        initial_flush: false,
+    };
+
+    info!("--- watch a key or a range in an allowed prefix");
+    {
+        alice.request(watch("alice/foo", None)).await?;
+        alice.request(watch("alice/a", Some("alice/z"))).await?;
+    }
+
+    info!("--- watch out of allowed prefixes is denied");
+    {
+        let res = alice.request(watch("bob/foo", None)).await;
+        assert!(res.is_err());
+
+        let res = alice.request(watch("alice/a", Some("bob/z"))).await;
+        assert!(res.is_err());
+
+        // Both ends are allowed, but the range covers keys of other users in between.
+        let res = alice.request(watch("alice/a", Some("shared/z"))).await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_cancel_stream_of_other_user() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/,shared/".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let addr = tc.config.grpc_api_address.clone();
+    let alice = MetaGrpcClient::try_create(
+        vec![addr],
+        "alice",
+        "xxx",
+        None,
+        Some(Duration::from_secs(10)),
+        Duration::from_secs(10),
+        None,
+    )?;
+    let root = tc.grpc_client().await?;
+
+    let watch = WatchRequest {
+        key: "shared/a".to_string(),
+        key_end: Some("shared/z".to_string()),
+        filter_type: FilterType::All.into(),
+        initial_flush: false,
+    };
+
+    let mut root_stream = root.request(watch.clone()).await?;
+    let root_stream_id = root_stream.message().await?.unwrap().stream_id;
+
+    let mut alice_stream = alice.request(watch).await?;
+    let alice_stream_id = alice_stream.message().await?.unwrap().stream_id;
+
+    let (mut alice_client, _server_version) = alice.make_client().await?;
+
+    info!("--- alice can not cancel a stream of root");
+    {
+        let res = alice_client
+            .cancel_stream(CancelStreamRequest {
+                stream_id: root_stream_id,
+            })
+            .await;
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+    }
+
+    info!("--- alice cancels her own stream");
+    {
+        let reply = alice_client
+            .cancel_stream(CancelStreamRequest {
+                stream_id: alice_stream_id,
+            })
+            .await?
+            .into_inner();
+        assert!(reply.cancelled);
+    }
+
+    Ok(())
+}
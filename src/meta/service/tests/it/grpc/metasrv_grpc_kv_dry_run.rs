@@ -0,0 +1,174 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test dry-run upsert of metasrv gRPC kv_api.
+
+use std::time::Duration;
+
+use common_meta_client::reply_to_api_result;
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReply;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::protobuf::SetReadOnlyRequest;
+use common_meta_types::MatchSeq;
+use common_meta_types::Operation;
+use databend_meta::api::grpc::grpc_service::DRY_RUN_KEY;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::start_metasrv_cluster;
+
+fn dry_run_request(req: UpsertKVReq) -> tonic::Request<RaftRequest> {
+    let mut request = tonic::Request::new(RaftRequest::from(MetaGrpcReq::UpsertKV(req)));
+    request
+        .metadata_mut()
+        .insert(DRY_RUN_KEY, "true".parse().unwrap());
+    request
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_kv_api_dry_run() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- dry run passes validation, but does not write");
+    {
+        let req = UpsertKVReq::update("foo", b"foo");
+        let reply = grpc_client.kv_api(dry_run_request(req)).await?.into_inner();
+        let res: UpsertKVReply = reply_to_api_result(reply)?;
+
+        assert_eq!(None, res.prev);
+        assert_eq!(Some(b"foo".to_vec()), res.result.map(|x| x.data));
+
+        let got = client.get_kv("foo").await?;
+        assert!(got.is_none(), "dry run must not write");
+    }
+
+    info!("--- failing MatchSeq is reported in dry run");
+    {
+        client.upsert_kv(UpsertKVReq::update("bar", b"bar")).await?;
+        let seq = client.get_kv("bar").await?.unwrap().seq;
+
+        let req = UpsertKVReq::new(
+            "bar",
+            MatchSeq::Exact(seq + 1),
+            Operation::Update(b"wow".to_vec()),
+            None,
+        );
+        let reply = grpc_client.kv_api(dry_run_request(req)).await?.into_inner();
+        let res: UpsertKVReply = reply_to_api_result(reply)?;
+
+        assert_eq!(res.prev, res.result, "unchanged if MatchSeq fails");
+        assert_eq!(Some(b"bar".to_vec()), res.result.map(|x| x.data));
+
+        let got = client.get_kv("bar").await?.unwrap();
+        assert_eq!(seq, got.seq);
+        assert_eq!(b"bar".to_vec(), got.data);
+    }
+
+    info!("--- matching MatchSeq predicts the next seq");
+    {
+        let seq = client.get_kv("bar").await?.unwrap().seq;
+
+        let req = UpsertKVReq::new(
+            "bar",
+            MatchSeq::Exact(seq),
+            Operation::Update(b"wow".to_vec()),
+            None,
+        );
+        let reply = grpc_client.kv_api(dry_run_request(req)).await?.into_inner();
+        let res: UpsertKVReply = reply_to_api_result(reply)?;
+
+        let result = res.result.unwrap();
+        assert_eq!(seq + 1, result.seq);
+        assert_eq!(b"wow".to_vec(), result.data);
+
+        let got = client.get_kv("bar").await?.unwrap();
+        assert_eq!(b"bar".to_vec(), got.data, "dry run must not write");
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_kv_api_dry_run_read_only() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    grpc_client
+        .set_read_only(SetReadOnlyRequest { read_only: true })
+        .await?;
+
+    info!("--- dry run is rejected like a write in read-only mode");
+    {
+        let req = UpsertKVReq::update("foo", b"foo");
+        let res = grpc_client.kv_api(dry_run_request(req)).await;
+
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_kv_api_dry_run_on_follower() -> anyhow::Result<()> {
+    let tcs = start_metasrv_cluster(&[0, 1]).await?;
+
+    let follower = tcs[1].grpc_client().await?;
+    let (mut grpc_client, _server_version) = follower.make_client().await?;
+
+    info!("--- a follower does not forward a dry run to a leader that may not understand it");
+    {
+        let req = UpsertKVReq::update("foo", b"foo");
+        let res = grpc_client.kv_api(dry_run_request(req)).await;
+
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+    }
+
+    let leader = tcs[0].grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    leader.set_cluster_version(CLUSTER_VERSION_V1).await?;
+
+    let mn1 = tcs[1].grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    let applied = leader.raft.metrics().borrow().last_applied;
+    mn1.raft
+        .wait(Some(Duration::from_secs(10)))
+        .metrics(
+            |m| m.last_applied >= applied,
+            "follower applied cluster version",
+        )
+        .await?;
+
+    info!("--- a follower forwards a dry run once the cluster version is raised");
+    {
+        let req = UpsertKVReq::update("foo", b"foo");
+        let reply = grpc_client.kv_api(dry_run_request(req)).await?.into_inner();
+        let res: UpsertKVReply = reply_to_api_result(reply)?;
+        assert_eq!(Some(b"foo".to_vec()), res.result.map(|x| x.data));
+    }
+
+    Ok(())
+}
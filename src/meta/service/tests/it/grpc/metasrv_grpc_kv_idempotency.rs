@@ -0,0 +1,94 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test de-duplicating kv writes by an idempotency key.
+
+use common_meta_client::reply_to_api_result;
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReply;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::cluster_version::MAX_CLUSTER_VERSION;
+use common_meta_types::protobuf::RaftRequest;
+use databend_meta::api::grpc::grpc_service::IDEMPOTENCY_KEY;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+
+fn idempotent_request(req: UpsertKVReq, txid: &str) -> tonic::Request<RaftRequest> {
+    let mut request = tonic::Request::new(RaftRequest::from(MetaGrpcReq::UpsertKV(req)));
+    request
+        .metadata_mut()
+        .insert(IDEMPOTENCY_KEY, txid.parse().unwrap());
+    request
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_kv_api_idempotency_key() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- rejected before the cluster version is raised");
+    {
+        let req = UpsertKVReq::update("foo", b"foo");
+        let res = grpc_client
+            .kv_api(idempotent_request(req, "txid-c1-1"))
+            .await;
+
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+    }
+
+    info!("--- a version this build does not support can not be set");
+    {
+        let res = tc
+            .meta_node()
+            .set_cluster_version(MAX_CLUSTER_VERSION + 1)
+            .await;
+        assert!(res.is_err());
+    }
+
+    tc.meta_node()
+        .set_cluster_version(CLUSTER_VERSION_V1)
+        .await?;
+
+    info!("--- a retried write is applied only once");
+    {
+        for value in [b"foo", b"bar"] {
+            let req = UpsertKVReq::update("foo", value);
+            let reply = grpc_client
+                .kv_api(idempotent_request(req, "txid-c1-1"))
+                .await?
+                .into_inner();
+            let res: UpsertKVReply = reply_to_api_result(reply)?;
+            assert_eq!(Some(b"foo".to_vec()), res.result.map(|x| x.data));
+        }
+
+        let got = client.get_kv("foo").await?.unwrap();
+        assert_eq!(b"foo".to_vec(), got.data);
+    }
+
+    info!("--- the cluster version can not be lowered");
+    {
+        let res = tc.meta_node().set_cluster_version(0).await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
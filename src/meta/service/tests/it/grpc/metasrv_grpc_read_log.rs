@@ -0,0 +1,159 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the read_log() admin API.
+
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::ReadLogRequest;
+use common_meta_types::Cmd;
+use common_meta_types::Entry;
+use common_meta_types::EntryPayload;
+use databend_meta::api::grpc::grpc_service::MAX_READ_LOG_BYTES;
+use databend_meta::api::grpc::grpc_service::MAX_READ_LOG_ENTRIES;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_read_log() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- write several entries");
+    for key in ["a", "b", "c"] {
+        client
+            .upsert_kv(UpsertKVReq::update(key, key.as_bytes()))
+            .await?;
+    }
+
+    let mn = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    let last = mn.raft.metrics().borrow().last_log_index.unwrap();
+
+    info!("--- read back a subrange");
+    {
+        let reply = grpc_client
+            .read_log(ReadLogRequest {
+                start: last - 1,
+                end: last + 1,
+            })
+            .await?
+            .into_inner();
+
+        let keys = reply
+            .entries
+            .iter()
+            .map(|s| {
+                let entry: Entry = serde_json::from_str(s).unwrap();
+                match entry.payload {
+                    EntryPayload::Normal(log_entry) => match log_entry.cmd {
+                        Cmd::UpsertKV(upsert) => upsert.key,
+                        cmd => panic!("unexpected cmd: {}", cmd),
+                    },
+                    payload => panic!("unexpected payload: {:?}", payload),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["b".to_string(), "c".to_string()], keys);
+
+        let want = mn.sto.log.read().await.range_values(last - 1..last + 1)?;
+        let want = want
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(want, reply.entries);
+    }
+
+    info!("--- the number of entries is capped");
+    {
+        let reply = grpc_client
+            .read_log(ReadLogRequest {
+                start: 0,
+                end: u64::MAX,
+            })
+            .await?
+            .into_inner();
+
+        let all = mn.sto.log.read().await.range_values(..)?;
+        assert!(reply.entries.len() as u64 <= MAX_READ_LOG_ENTRIES);
+        assert_eq!(all.len(), reply.entries.len(), "all logs are read");
+    }
+
+    info!("--- invalid range is rejected");
+    {
+        let res = grpc_client
+            .read_log(ReadLogRequest { start: 2, end: 1 })
+            .await;
+        assert_eq!(tonic::Code::InvalidArgument, res.unwrap_err().code());
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_read_log_bytes_limit() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- write entries larger than MAX_READ_LOG_BYTES in total");
+    let value = vec![b'x'; MAX_READ_LOG_BYTES / 4];
+    for i in 0..8 {
+        client
+            .upsert_kv(UpsertKVReq::update(&format!("k{}", i), &value))
+            .await?;
+    }
+
+    let mn = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    let last = mn.raft.metrics().borrow().last_log_index.unwrap();
+
+    info!("--- the size of a reply is capped");
+    let mut start = 0;
+    let mut calls = 0;
+    while start <= last {
+        calls += 1;
+        let reply = grpc_client
+            .read_log(ReadLogRequest {
+                start,
+                end: last + 1,
+            })
+            .await?
+            .into_inner();
+
+        assert!(!reply.entries.is_empty());
+
+        let size = reply.entries.iter().map(|s| s.len()).sum::<usize>();
+        assert!(
+            reply.entries.len() == 1 || size <= MAX_READ_LOG_BYTES,
+            "{} entries of {} bytes",
+            reply.entries.len(),
+            size
+        );
+
+        start += reply.entries.len() as u64;
+    }
+
+    let all = mn.sto.log.read().await.range_values(..)?;
+    assert_eq!(all.len() as u64, start, "all logs are read");
+    assert!(calls > 1, "large entries are read by several calls");
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the read-only mode of metasrv set by the set_read_only() admin API.
+
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::protobuf::SetReadOnlyRequest;
+use common_meta_types::txn_op;
+use common_meta_types::TxnGetRequest;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnRequest;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+
+fn upsert_request(key: &str) -> RaftRequest {
+    RaftRequest::from(MetaGrpcReq::UpsertKV(UpsertKVReq::update(
+        key,
+        key.as_bytes(),
+    )))
+}
+
+fn txn_request(ops: Vec<TxnOp>) -> TxnRequest {
+    TxnRequest {
+        condition: vec![],
+        if_then: ops,
+        else_then: vec![],
+    }
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_read_only() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    client.upsert_kv(UpsertKVReq::update("foo", b"foo")).await?;
+
+    info!("--- set read-only");
+    grpc_client
+        .set_read_only(SetReadOnlyRequest { read_only: true })
+        .await?;
+
+    info!("--- writes are rejected");
+    {
+        let status = grpc_client.kv_api(upsert_request("bar")).await.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert_eq!("cluster is read-only", status.message());
+
+        let txn = txn_request(vec![TxnOp::put("bar", b"bar".to_vec())]);
+        let status = grpc_client.transaction(txn).await.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+
+        let txn = txn_request(vec![TxnOp::delete("foo")]);
+        let status = grpc_client.transaction(txn).await.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+    }
+
+    info!("--- reads still work");
+    {
+        let got = client.get_kv("foo").await?;
+        assert_eq!(b"foo".to_vec(), got.unwrap().data);
+
+        let got = client.get_kv("bar").await?;
+        assert!(got.is_none(), "rejected write must not be applied");
+
+        let get = TxnOp {
+            request: Some(txn_op::Request::Get(TxnGetRequest {
+                key: "foo".to_string(),
+            })),
+        };
+        let txn = txn_request(vec![get]);
+        let reply = grpc_client.transaction(txn).await?.into_inner();
+        assert!(reply.success);
+    }
+
+    info!("--- clear read-only restores writes");
+    {
+        grpc_client
+            .set_read_only(SetReadOnlyRequest { read_only: false })
+            .await?;
+
+        grpc_client.kv_api(upsert_request("bar")).await?;
+
+        let got = client.get_kv("bar").await?;
+        assert_eq!(b"bar".to_vec(), got.unwrap().data);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test a metasrv node running as a read replica.
+
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_sled_store::openraft::ServerState;
+use common_meta_types::protobuf::Empty;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnRequest;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::meta_node::timeout;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+/// - Start a leader and a read replica that joins as a learner.
+/// - The read replica serves reads from its local state machine.
+/// - The read replica rejects writes with a not-leader error.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_read_replica() -> anyhow::Result<()> {
+    let mut tc0 = MetaSrvTestContext::new(0);
+    start_metasrv_with_context(&mut tc0).await?;
+    let leader_addr = tc0.config.raft_config.raft_api_addr().await?;
+
+    let mut tc1 = MetaSrvTestContext::new(1);
+    tc1.config.raft_config.single = false;
+    tc1.config.raft_config.join = vec![leader_addr.to_string()];
+    tc1.config.raft_config.read_replica = true;
+    start_metasrv_with_context(&mut tc1).await?;
+
+    let leader = tc0.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    let replica = tc1.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+
+    info!("--- the read replica joins as a learner, not a voter");
+    replica
+        .raft
+        .wait(timeout())
+        .state(ServerState::Learner, "read replica is a learner")
+        .await?;
+
+    let voters = leader
+        .raft
+        .metrics()
+        .borrow()
+        .membership_config
+        .membership()
+        .voter_ids()
+        .collect::<Vec<_>>();
+    assert_eq!(vec![0], voters);
+    assert!(replica.is_read_replica());
+
+    info!("--- write through the leader");
+    let leader_client = tc0.grpc_client().await?;
+    leader_client
+        .upsert_kv(UpsertKVReq::update("foo", b"foo"))
+        .await?;
+
+    let log_index = leader.raft.metrics().borrow().last_log_index;
+    replica
+        .raft
+        .wait(timeout())
+        .log(log_index, "read replica applied the write")
+        .await?;
+
+    let replica_client = tc1.grpc_client().await?;
+    let (mut replica_grpc, _server_version) = replica_client.make_client().await?;
+
+    info!("--- the read replica serves local reads");
+    {
+        let got = replica_client.get_kv("foo").await?;
+        assert_eq!(b"foo".to_vec(), got.unwrap().data);
+
+        let got = replica_client.prefix_list_kv("f").await?;
+        assert_eq!(
+            vec!["foo".to_string()],
+            got.into_iter().map(|(k, _)| k).collect::<Vec<_>>()
+        );
+    }
+
+    info!("--- the read replica rejects writes");
+    {
+        let req = RaftRequest::from(MetaGrpcReq::UpsertKV(UpsertKVReq::update("bar", b"bar")));
+        let status = replica_grpc.kv_api(req).await.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert!(
+            status.message().starts_with("not leader"),
+            "got: {:?}",
+            status
+        );
+
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: vec![TxnOp::put("bar", b"bar".to_vec())],
+            else_then: vec![],
+        };
+        let status = replica_grpc.transaction(txn).await.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+
+        let got = leader_client.get_kv("bar").await?;
+        assert!(got.is_none(), "rejected write must not be applied");
+    }
+
+    info!("--- the read replica advertises its learner status");
+    {
+        let status = replica_grpc
+            .get_cluster_status(Empty {})
+            .await?
+            .into_inner();
+        assert!(status.is_learner);
+        assert!(status.read_replica);
+        assert!(!status.is_leader);
+    }
+
+    Ok(())
+}
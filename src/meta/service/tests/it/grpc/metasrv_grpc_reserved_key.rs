@@ -0,0 +1,171 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test that clients can not write the reserved keys, which are only changed by the admin APIs.
+
+use std::time::Duration;
+
+use common_meta_client::MetaGrpcClient;
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::cluster_version::CLUSTER_VERSION_KEY;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V0;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::cluster_version::MAX_CLUSTER_VERSION;
+use common_meta_types::protobuf::IncrementRequest;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::protobuf::SetClusterVersionRequest;
+use common_meta_types::txn_op;
+use common_meta_types::TxnDeleteByPrefixRequest;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnRequest;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+fn txn(op: TxnOp) -> TxnRequest {
+    TxnRequest {
+        condition: vec![],
+        if_then: vec![op],
+        else_then: vec![],
+    }
+}
+
+fn delete_by_prefix(prefix: &str) -> TxnOp {
+    TxnOp {
+        request: Some(txn_op::Request::DeleteByPrefix(TxnDeleteByPrefixRequest {
+            prefix: prefix.to_string(),
+        })),
+    }
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_reserved_key_not_writable_by_client() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- only root can set the cluster version");
+    {
+        let alice = MetaGrpcClient::try_create(
+            vec![tc.config.grpc_api_address.clone()],
+            "alice",
+            "xxx",
+            None,
+            Some(Duration::from_secs(10)),
+            Duration::from_secs(10),
+            None,
+        )?;
+        let (mut alice_client, _server_version) = alice.make_client().await?;
+        let status = alice_client
+            .set_cluster_version(SetClusterVersionRequest {
+                version: CLUSTER_VERSION_V1,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+    }
+
+    info!("--- root raises the cluster version");
+    {
+        let reply = grpc_client
+            .set_cluster_version(SetClusterVersionRequest {
+                version: CLUSTER_VERSION_V1,
+            })
+            .await?
+            .into_inner();
+        assert_eq!(CLUSTER_VERSION_V1, reply.version);
+        assert_eq!(MAX_CLUSTER_VERSION, reply.max_version);
+
+        let status = grpc_client
+            .set_cluster_version(SetClusterVersionRequest {
+                version: CLUSTER_VERSION_V0,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert!(
+            status.message().contains("can not be lowered"),
+            "{}",
+            status
+        );
+    }
+
+    info!("--- a reserved key can be read but not written by a client");
+    {
+        let got = client.get_kv(CLUSTER_VERSION_KEY).await?;
+        assert_eq!(b"1".to_vec(), got.unwrap().data);
+
+        let req = UpsertKVReq::update(CLUSTER_VERSION_KEY, b"0");
+        let status = grpc_client
+            .kv_api(RaftRequest::from(MetaGrpcReq::UpsertKV(req)))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+        assert!(status.message().contains("key is reserved"), "{}", status);
+
+        let req = UpsertKVReq::delete(CLUSTER_VERSION_KEY);
+        let status = grpc_client
+            .kv_api(RaftRequest::from(MetaGrpcReq::UpsertKV(req)))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+
+        let status = grpc_client
+            .increment(IncrementRequest {
+                key: CLUSTER_VERSION_KEY.to_string(),
+                delta: -1,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+    }
+
+    info!("--- a txn can not write, delete or delete a prefix covering a reserved key");
+    {
+        let ops = [
+            TxnOp::put(CLUSTER_VERSION_KEY, b"0".to_vec()),
+            TxnOp::delete(CLUSTER_VERSION_KEY),
+            delete_by_prefix(""),
+            delete_by_prefix("__fd"),
+            delete_by_prefix("__fd_meta/"),
+            delete_by_prefix("__fd_meta/schema_version/"),
+        ];
+        for op in ops {
+            let status = grpc_client.transaction(txn(op.clone())).await.unwrap_err();
+            assert_eq!(tonic::Code::PermissionDenied, status.code(), "{:?}", op);
+        }
+
+        // A prefix that does not cover a reserved key is allowed.
+        grpc_client
+            .transaction(txn(delete_by_prefix("__fd_other/")))
+            .await?;
+    }
+
+    info!("--- the cluster version is unchanged");
+    {
+        assert_eq!(CLUSTER_VERSION_V1, tc.meta_node().cluster_version().await);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test that writes are checked against the minimum schema version of their keys.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_meta_client::MetaGrpcClient;
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::IncrementRequest;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::protobuf::SetSchemaVersionRequest;
+use common_meta_types::schema_version::schema_version_key;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnRequest;
+use databend_meta::api::grpc::grpc_service::SCHEMA_VERSION_KEY;
+use databend_meta::meta_service::MetaNode;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+fn with_schema_version<T>(message: T, version: Option<&str>) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    if let Some(version) = version {
+        request
+            .metadata_mut()
+            .insert(SCHEMA_VERSION_KEY, version.parse().unwrap());
+    }
+    request
+}
+
+fn upsert_request(key: &str, version: Option<&str>) -> tonic::Request<RaftRequest> {
+    let req = UpsertKVReq::update(key, key.as_bytes());
+    with_schema_version(RaftRequest::from(MetaGrpcReq::UpsertKV(req)), version)
+}
+
+fn set_schema_version_request(prefix: &str, version: u64) -> SetSchemaVersionRequest {
+    SetSchemaVersionRequest {
+        prefix: prefix.to_string(),
+        version,
+    }
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_schema_version() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- without a minimum, writes are not checked");
+    {
+        grpc_client.kv_api(upsert_request("tbl/0", None)).await?;
+        assert!(client.get_kv("tbl/0").await?.is_some());
+    }
+
+    info!("--- root sets the minimum versions");
+    {
+        for (prefix, version) in [("tbl/", 2), ("tbl/tmp/", 3)] {
+            let reply = grpc_client
+                .set_schema_version(set_schema_version_request(prefix, version))
+                .await?
+                .into_inner();
+            assert_eq!(version, reply.version);
+        }
+
+        let status = grpc_client
+            .set_schema_version(set_schema_version_request("tbl/", 1))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert_eq!(
+            "schema version of tbl/ can not be lowered from 2 to 1",
+            status.message()
+        );
+
+        let status = grpc_client
+            .set_schema_version(set_schema_version_request("", 1))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::InvalidArgument, status.code());
+
+        let mn: Arc<MetaNode> = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+        let versions = mn.schema_versions().await?;
+        assert_eq!(Some(&2), versions.get("tbl/"));
+        assert_eq!(Some(&3), versions.get("tbl/tmp/"));
+    }
+
+    info!("--- a write with the minimum or a newer version succeeds");
+    {
+        grpc_client
+            .kv_api(upsert_request("tbl/1", Some("2")))
+            .await?;
+        grpc_client
+            .kv_api(upsert_request("tbl/tmp/1", Some("4")))
+            .await?;
+
+        let got = client.get_kv("tbl/1").await?;
+        assert_eq!(b"tbl/1".to_vec(), got.unwrap().data);
+
+        let got = client.get_kv("tbl/tmp/1").await?;
+        assert_eq!(b"tbl/tmp/1".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- a write with an older version or without a version is rejected");
+    {
+        let status = grpc_client
+            .kv_api(upsert_request("tbl/2", Some("1")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert_eq!(
+            "schema version is too old for key: tbl/2, request: 1, min: 2 for prefix: tbl/",
+            status.message()
+        );
+
+        let status = grpc_client
+            .kv_api(upsert_request("tbl/2", None))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+        assert_eq!(
+            "schema version is required for key: tbl/2, min: 2 for prefix: tbl/",
+            status.message()
+        );
+
+        // The longest prefix decides the minimum version.
+        let status = grpc_client
+            .kv_api(upsert_request("tbl/tmp/2", Some("2")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: vec![TxnOp::put("tbl/3", b"tbl/3".to_vec())],
+            else_then: vec![],
+        };
+        let status = grpc_client
+            .transaction(with_schema_version(txn, Some("1")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: vec![TxnOp::delete("tbl/1")],
+            else_then: vec![],
+        };
+        let status = grpc_client
+            .transaction(with_schema_version(txn, None))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+
+        let status = grpc_client
+            .increment(with_schema_version(
+                IncrementRequest {
+                    key: "tbl/4".to_string(),
+                    delta: 1,
+                },
+                None,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+
+        for key in ["tbl/2", "tbl/tmp/2", "tbl/3", "tbl/4"] {
+            let got = client.get_kv(key).await?;
+            assert!(
+                got.is_none(),
+                "rejected write of {} must not be applied",
+                key
+            );
+        }
+        assert!(client.get_kv("tbl/1").await?.is_some());
+    }
+
+    info!("--- keys out of any namespace are not checked");
+    {
+        grpc_client.kv_api(upsert_request("other/1", None)).await?;
+        assert!(client.get_kv("other/1").await?.is_some());
+    }
+
+    info!("--- a malformed version is an invalid argument");
+    {
+        let status = grpc_client
+            .kv_api(upsert_request("tbl/5", Some("two")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::InvalidArgument, status.code());
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_set_schema_version_only_by_root() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_key_acl = "alice=alice/".to_string();
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    let alice = MetaGrpcClient::try_create(
+        vec![tc.config.grpc_api_address.clone()],
+        "alice",
+        "xxx",
+        None,
+        Some(Duration::from_secs(10)),
+        Duration::from_secs(10),
+        None,
+    )?;
+    let (mut alice_client, _server_version) = alice.make_client().await?;
+
+    info!("--- a non-root user can not set a schema version");
+    {
+        let status = alice_client
+            .set_schema_version(set_schema_version_request("alice/", 2))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+    }
+
+    info!("--- root sets a schema version");
+    {
+        let reply = grpc_client
+            .set_schema_version(set_schema_version_request("alice/", 2))
+            .await?
+            .into_inner();
+        assert_eq!(2, reply.version);
+    }
+
+    info!("--- a client can not delete the schema version key");
+    {
+        let key = schema_version_key("alice/");
+        let req = UpsertKVReq::delete(&key);
+        let status = grpc_client
+            .kv_api(RaftRequest::from(MetaGrpcReq::UpsertKV(req)))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: vec![TxnOp::delete(&key)],
+            else_then: vec![],
+        };
+        let status = grpc_client.transaction(txn).await.unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+    }
+
+    info!("--- the schema version is unchanged");
+    {
+        let versions = tc.meta_node().schema_versions().await?;
+        assert_eq!(Some(&2), versions.get("alice/"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::base::tokio::time::timeout;
+use common_meta_types::protobuf::watch_request::FilterType;
+use common_meta_types::protobuf::Empty;
+use common_meta_types::protobuf::WatchRequest;
+use futures::StreamExt;
+use log::info;
+use prost::Message;
+use test_harness::test;
+use tonic::transport::Channel;
+use tonic_reflection::pb::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::ServerReflectionRequest;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_grpc_max_concurrent_streams() -> anyhow::Result<()> {
+    // - Start a metasrv that allows only 1 stream per connection.
+    // - Hold a watch stream open.
+    // - Assert another request on the same connection waits until the watch stream is closed.
+
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_max_concurrent_streams = Some(1);
+    tc.config.grpc_accept_backlog = 16;
+
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- a request succeeds when no other stream is open");
+    grpc_client.get_cluster_status(Empty {}).await?;
+
+    info!("--- hold the only stream with a watch");
+    let watch_stream = grpc_client
+        .watch(WatchRequest {
+            key: "a".to_string(),
+            key_end: None,
+            filter_type: FilterType::All.into(),
+            initial_flush: false,
+        })
+        .await?;
+
+    info!("--- another request on the same connection is blocked");
+    {
+        let mut c = grpc_client.clone();
+        let res = timeout(Duration::from_secs(1), c.get_cluster_status(Empty {})).await;
+        assert!(res.is_err(), "expect timeout, got: {:?}", res);
+    }
+
+    info!("--- the request proceeds after the watch stream is closed");
+    drop(watch_stream);
+    {
+        let mut c = grpc_client.clone();
+        let res = timeout(Duration::from_secs(3), c.get_cluster_status(Empty {})).await;
+        assert!(res?.is_ok());
+    }
+
+    Ok(())
+}
+
+/// Send one reflection request and return the response message.
+async fn reflect(
+    client: &mut ServerReflectionClient<Channel>,
+    req: MessageRequest,
+) -> Result<MessageResponse, tonic::Status> {
+    let req = ServerReflectionRequest {
+        host: "".to_string(),
+        message_request: Some(req),
+    };
+
+    let mut strm = client
+        .server_reflection_info(futures::stream::iter([req]))
+        .await?
+        .into_inner();
+
+    let resp = strm.next().await.expect("one response")?;
+    Ok(resp.message_response.expect("non-empty response"))
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_grpc_reflection() -> anyhow::Result<()> {
+    info!("--- reflection is disabled by default");
+    {
+        let mut tc = MetaSrvTestContext::new(0);
+        start_metasrv_with_context(&mut tc).await?;
+
+        let addr = format!("http://{}", tc.config.grpc_api_address);
+        let mut client = ServerReflectionClient::connect(addr).await?;
+
+        let res = reflect(&mut client, MessageRequest::ListServices("".to_string())).await;
+        assert_eq!(tonic::Code::Unimplemented, res.unwrap_err().code());
+    }
+
+    info!("--- enabled reflection lists MetaService and its methods");
+    {
+        let mut tc = MetaSrvTestContext::new(1);
+        tc.config.grpc_enable_reflection = true;
+        start_metasrv_with_context(&mut tc).await?;
+
+        let addr = format!("http://{}", tc.config.grpc_api_address);
+        let mut client = ServerReflectionClient::connect(addr).await?;
+
+        let resp = reflect(&mut client, MessageRequest::ListServices("".to_string())).await?;
+        let MessageResponse::ListServicesResponse(list) = resp else {
+            panic!("expect ListServicesResponse, got: {:?}", resp);
+        };
+        let services = list.service.into_iter().map(|s| s.name).collect::<Vec<_>>();
+        assert!(
+            services.contains(&"meta.MetaService".to_string()),
+            "services: {:?}",
+            services
+        );
+
+        let req = MessageRequest::FileContainingSymbol("meta.MetaService".to_string());
+        let resp = reflect(&mut client, req).await?;
+        let MessageResponse::FileDescriptorResponse(files) = resp else {
+            panic!("expect FileDescriptorResponse, got: {:?}", resp);
+        };
+
+        let methods = files
+            .file_descriptor_proto
+            .iter()
+            .map(|b| prost_types::FileDescriptorProto::decode(b.as_slice()).unwrap())
+            .flat_map(|f| f.service)
+            .filter(|s| s.name() == "MetaService")
+            .flat_map(|s| s.method)
+            .map(|m| m.name().to_string())
+            .collect::<Vec<_>>();
+
+        for want in ["Handshake", "KvApi", "Transaction", "Watch", "Export"] {
+            assert!(
+                methods.contains(&want.to_string()),
+                "{} not in {:?}",
+                want,
+                methods
+            );
+        }
+    }
+
+    Ok(())
+}
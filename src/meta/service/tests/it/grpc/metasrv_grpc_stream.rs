@@ -0,0 +1,65 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_base::base::tokio::sync::mpsc::error::SendTimeoutError;
+use databend_meta::api::grpc::grpc_service::bounded_stream;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+
+/// A stalled consumer blocks the producer instead of letting the server buffer without bound,
+/// and the producer gives up once the timeout is reached.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_bounded_stream_stalled_consumer_blocks_producer() -> anyhow::Result<()> {
+    let (tx, mut rx) = bounded_stream::<u64>(2, Duration::from_millis(1_000));
+
+    let sent = Arc::new(AtomicUsize::new(0));
+
+    let h = {
+        let sent = sent.clone();
+        tokio::spawn(async move {
+            for i in 0..100 {
+                tx.send(Ok(i)).await?;
+                sent.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok::<(), SendTimeoutError<_>>(())
+        })
+    };
+
+    info!("--- the consumer does not read, the producer is blocked when the buffer is full");
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(2, sent.load(Ordering::Relaxed));
+    assert!(!h.is_finished());
+
+    info!("--- consuming one item lets the producer send one more");
+    let got = rx.recv().await.unwrap().unwrap();
+    assert_eq!(0, got);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(3, sent.load(Ordering::Relaxed));
+
+    info!("--- the producer gives up after the client stalls past the timeout");
+    let res = h.await?;
+    assert!(matches!(res, Err(SendTimeoutError::Timeout(_))));
+    assert_eq!(3, sent.load(Ordering::Relaxed));
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the transfer_leader() admin API.
+
+use std::time::Duration;
+
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::Empty;
+use common_meta_types::protobuf::TransferLeaderRequest;
+use log::info;
+use test_harness::test;
+use tonic::metadata::MetadataValue;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::start_metasrv_cluster;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_transfer_leader() -> anyhow::Result<()> {
+    let tcs = start_metasrv_cluster(&[0, 1, 2]).await?;
+
+    let client = tcs[0].grpc_client().await?;
+    client.upsert_kv(UpsertKVReq::update("foo", b"foo")).await?;
+
+    info!("--- a follower refuses to transfer leader");
+    {
+        let follower = tcs[1].grpc_client().await?;
+        let (mut grpc_client, _server_version) = follower.make_client().await?;
+
+        let res = grpc_client
+            .transfer_leader(TransferLeaderRequest { to: 1 })
+            .await;
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::FailedPrecondition, status.code());
+    }
+
+    info!("--- an unknown target is rejected");
+    {
+        let (mut grpc_client, _server_version) = client.make_client().await?;
+
+        let res = grpc_client
+            .transfer_leader(TransferLeaderRequest { to: 5 })
+            .await;
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::InvalidArgument, status.code());
+    }
+
+    info!("--- transfer leader from 0 to 2");
+    {
+        let (mut grpc_client, _server_version) = client.make_client().await?;
+        grpc_client
+            .transfer_leader(TransferLeaderRequest { to: 2 })
+            .await?;
+    }
+
+    info!("--- all nodes see 2 as the leader");
+    for tc in tcs.iter() {
+        let mn = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+        mn.raft
+            .wait(Some(Duration::from_secs(10)))
+            .current_leader(2, "leader is 2")
+            .await?;
+    }
+
+    info!("--- the new leader serves writes");
+    {
+        let client = tcs[2].grpc_client().await?;
+        client.upsert_kv(UpsertKVReq::update("bar", b"bar")).await?;
+
+        let got = client.get_kv("bar").await?;
+        assert_eq!(b"bar".to_vec(), got.unwrap().data);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_elect_requires_root_token() -> anyhow::Result<()> {
+    let tcs = start_metasrv_cluster(&[0, 1, 2]).await?;
+
+    let mut raft_client = tcs[1].raft_client().await?;
+
+    info!("--- elect without a token is rejected");
+    {
+        let res = raft_client.elect(Empty {}).await;
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::Unauthenticated, status.code());
+    }
+
+    info!("--- elect with a token not issued with the shared secret is rejected");
+    {
+        let mut req = tonic::Request::new(Empty {});
+        req.metadata_mut()
+            .insert_bin("auth-token-bin", MetadataValue::from_bytes(b"foo"));
+
+        let res = raft_client.elect(req).await;
+        let status = res.unwrap_err();
+        assert_eq!(tonic::Code::Unauthenticated, status.code());
+    }
+
+    info!("--- leader is not changed");
+    {
+        let mn = tcs[1].grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+        assert_eq!(Some(0), mn.raft.metrics().borrow().current_leader);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test read-your-writes across nodes with the log index of a write and `wait_applied`.
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::cluster_version::CLUSTER_VERSION_V1;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::protobuf::WaitAppliedRequest;
+use databend_meta::api::grpc::grpc_service::LOG_INDEX_KEY;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::start_metasrv_cluster;
+
+fn upsert_request(key: &str) -> RaftRequest {
+    RaftRequest::from(MetaGrpcReq::UpsertKV(UpsertKVReq::update(
+        key,
+        key.as_bytes(),
+    )))
+}
+
+fn log_index_of<T>(response: &tonic::Response<T>) -> u64 {
+    let v = response.metadata().get(LOG_INDEX_KEY).unwrap();
+    v.to_str().unwrap().parse().unwrap()
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_wait_applied() -> anyhow::Result<()> {
+    let tcs = start_metasrv_cluster(&[0, 1, 2]).await?;
+
+    let leader = tcs[0].grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    let follower = tcs[1].grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+
+    let leader_client = tcs[0].grpc_client().await?;
+    let (mut leader_grpc, _server_version) = leader_client.make_client().await?;
+
+    let follower_client = tcs[1].grpc_client().await?;
+    let (mut follower_grpc, _server_version) = follower_client.make_client().await?;
+
+    info!("--- a write on the leader replies with its log index");
+    let log_index = {
+        let resp = leader_grpc.kv_api(upsert_request("foo")).await?;
+        let log_index = log_index_of(&resp);
+
+        let last_applied = leader.raft.metrics().borrow().last_applied.unwrap();
+        assert!(log_index > 0);
+        assert!(last_applied.index >= log_index);
+
+        log_index
+    };
+
+    info!("--- wait_applied on a follower returns once it applies the write");
+    {
+        let reply = follower_grpc
+            .wait_applied(WaitAppliedRequest { log_index })
+            .await?
+            .into_inner();
+        assert!(reply.last_applied >= log_index);
+
+        let sm = follower.sto.state_machine.read().await;
+        let got = sm.kv_api().get_kv("foo").await.unwrap();
+        assert_eq!(b"foo".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- a forwarded write has no log index before the cluster version is raised");
+    {
+        let resp = follower_grpc.kv_api(upsert_request("baz")).await?;
+        assert!(resp.metadata().get(LOG_INDEX_KEY).is_none());
+    }
+
+    info!("--- raise the cluster version to get the index of a forwarded write");
+    {
+        leader.set_cluster_version(CLUSTER_VERSION_V1).await?;
+
+        while follower.cluster_version().await < CLUSTER_VERSION_V1 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    info!("--- wait_applied blocks until a later write is applied");
+    {
+        let next = follower.raft.metrics().borrow().last_applied.unwrap().index + 1;
+
+        let mut waiting_grpc = follower_grpc.clone();
+        let waiting = tokio::spawn(async move {
+            waiting_grpc
+                .wait_applied(WaitAppliedRequest { log_index: next })
+                .await
+        });
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!waiting.is_finished(), "log {} is not written yet", next);
+
+        // A write through a follower is forwarded to the leader, and still has an index.
+        let resp = follower_grpc.kv_api(upsert_request("bar")).await?;
+        assert!(log_index_of(&resp) >= next);
+
+        let reply = waiting.await??.into_inner();
+        assert!(reply.last_applied >= next);
+
+        let sm = follower.sto.state_machine.read().await;
+        let got = sm.kv_api().get_kv("bar").await.unwrap();
+        assert_eq!(b"bar".to_vec(), got.unwrap().data);
+    }
+
+    info!("--- wait_applied fails if the log is not applied in time");
+    {
+        let mut req = tonic::Request::new(WaitAppliedRequest {
+            log_index: log_index + 1_000,
+        });
+        req.set_timeout(Duration::from_millis(500));
+
+        let status = follower_grpc.wait_applied(req).await.unwrap_err();
+        assert_eq!(tonic::Code::DeadlineExceeded, status.code());
+    }
+
+    Ok(())
+}
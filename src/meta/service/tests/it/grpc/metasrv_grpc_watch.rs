@@ -25,11 +25,13 @@ use common_meta_kvapi::kvapi;
 use common_meta_kvapi::kvapi::KVApi;
 use common_meta_kvapi::kvapi::UpsertKVReq;
 use common_meta_types::protobuf::watch_request::FilterType;
+use common_meta_types::protobuf::CancelStreamRequest;
 use common_meta_types::protobuf::Event;
 use common_meta_types::protobuf::KvMeta;
 use common_meta_types::protobuf::SeqV;
 use common_meta_types::protobuf::TxnRequest;
 use common_meta_types::protobuf::WatchRequest;
+use common_meta_types::protobuf::WatchResponse;
 use common_meta_types::txn_condition;
 use common_meta_types::txn_op;
 use common_meta_types::ConditionResult;
@@ -40,7 +42,13 @@ use common_meta_types::TxnDeleteByPrefixRequest;
 use common_meta_types::TxnDeleteRequest;
 use common_meta_types::TxnOp;
 use common_meta_types::TxnPutRequest;
+use databend_meta::api::grpc::grpc_service::bounded_stream;
+use databend_meta::api::grpc::grpc_service::STREAM_BUFFER_SIZE;
+use databend_meta::api::grpc::grpc_service::STREAM_SEND_TIMEOUT;
 use databend_meta::meta_service::MetaNode;
+use databend_meta::watcher::WatchStreamHandle;
+use databend_meta::watcher::Watcher;
+use databend_meta::watcher::WATCHER_QUEUE_SIZE;
 use log::info;
 use test_harness::test;
 
@@ -133,6 +141,7 @@ async fn test_watch() -> anyhow::Result<()> {
             key: "a".to_string(),
             key_end: Some("z".to_string()),
             filter_type: FilterType::All.into(),
+            initial_flush: false,
         };
 
         let key_a = s("a");
@@ -191,6 +200,7 @@ async fn test_watch() -> anyhow::Result<()> {
             key_end: None,
             // filter only delete events
             filter_type: FilterType::Delete.into(),
+            initial_flush: false,
         };
 
         let key = s(key_str);
@@ -254,6 +264,7 @@ async fn test_watch() -> anyhow::Result<()> {
             key: start,
             key_end: Some(end),
             filter_type: FilterType::All.into(),
+            initial_flush: false,
         };
 
         let conditions = vec![TxnCondition {
@@ -378,6 +389,7 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
             key: start,
             key_end: Some(end),
             filter_type: FilterType::All.into(),
+            initial_flush: false,
         };
         watch_client.request(watch).await?
     };
@@ -409,6 +421,12 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
 
     info!("--- check emitted events");
     {
+        let msg = client_stream.message().await?.unwrap();
+        assert_eq!(
+            None, msg.event,
+            "the first response carries only the stream id"
+        );
+
         // 32 expired keys are auto cleaned.
         for i in 0..(32 + 1) {
             let k = format!("w_auto_gc_{}", i);
@@ -463,6 +481,7 @@ async fn test_watch_stream_count() -> anyhow::Result<()> {
         key: "a".to_string(),
         key_end: Some("z".to_string()),
         filter_type: FilterType::All.into(),
+        initial_flush: false,
     };
 
     let client1 = make_client(&addr)?;
@@ -520,6 +539,315 @@ async fn test_watch_stream_count() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_watch_initial_flush() -> anyhow::Result<()> {
+    // - Write some data.
+    // - Watch with `initial_flush`.
+    // - Assert the watcher gets the current values, the end-of-initialization marker, then the live update.
+
+    let (_tc, addr) = crate::tests::start_metasrv().await?;
+
+    let client = make_client(&addr)?;
+
+    info!("--- write a, b and z; z is out of the watched range");
+    for k in ["a", "b", "z"] {
+        client
+            .upsert_kv(UpsertKVReq::new(
+                k,
+                MatchSeq::GE(0),
+                Operation::Update(b(k)),
+                None,
+            ))
+            .await?;
+    }
+
+    let watch = WatchRequest {
+        key: s("a"),
+        key_end: Some(s("z")),
+        filter_type: FilterType::All.into(),
+        initial_flush: true,
+    };
+
+    let mut watch_stream = client.request(watch).await?;
+
+    info!("--- initial values");
+    {
+        let resp = watch_stream.message().await?.unwrap();
+        assert!(resp.is_initialization);
+        assert_eq!(Some(add_event("a", 1, "a", None)), resp.event);
+
+        let resp = watch_stream.message().await?.unwrap();
+        assert!(resp.is_initialization);
+        assert_eq!(Some(add_event("b", 2, "b", None)), resp.event);
+    }
+
+    info!("--- end of initialization");
+    {
+        let resp = watch_stream.message().await?.unwrap();
+        assert!(!resp.is_initialization);
+        assert_eq!(None, resp.event);
+    }
+
+    info!("--- live update");
+    {
+        client
+            .upsert_kv(UpsertKVReq::new(
+                "b",
+                MatchSeq::GE(0),
+                Operation::Update(b("new")),
+                None,
+            ))
+            .await?;
+
+        let resp = watch_stream.message().await?.unwrap();
+        assert!(!resp.is_initialization);
+        assert_eq!(
+            Some(Event {
+                key: s("b"),
+                current: pb_seqv(4, "new", None),
+                prev: pb_seqv(2, "b", None),
+            }),
+            resp.event
+        );
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_watch_cancel_stream() -> anyhow::Result<()> {
+    // - Watch without `initial_flush`, the stream id is still in the first response.
+    // - Cancel the stream by the id.
+    // - Assert the stream ends without further events and the subscription is released.
+
+    let (tc, addr) = crate::tests::start_metasrv().await?;
+
+    let client = make_client(&addr)?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    let watch = WatchRequest {
+        key: s("a"),
+        key_end: Some(s("z")),
+        filter_type: FilterType::All.into(),
+        initial_flush: false,
+    };
+
+    let mut watch_stream = client.request(watch).await?;
+
+    let resp = watch_stream.message().await?.unwrap();
+    assert_eq!(None, resp.event, "the first response is sent at once");
+    let stream_id = resp.stream_id;
+    assert!(stream_id > 0);
+
+    let mn: Arc<MetaNode> = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+
+    assert_eq!(1, watcher_count(&mn).await);
+
+    info!("--- cancel the stream");
+    {
+        let reply = grpc_client
+            .cancel_stream(CancelStreamRequest { stream_id })
+            .await?
+            .into_inner();
+        assert!(reply.cancelled);
+
+        assert_eq!(0, watcher_count(&mn).await, "subscription is released");
+    }
+
+    info!("--- no more events after cancelling");
+    {
+        client
+            .upsert_kv(UpsertKVReq::new(
+                "b",
+                MatchSeq::GE(0),
+                Operation::Update(b("b")),
+                None,
+            ))
+            .await?;
+
+        let resp = watch_stream.message().await?;
+        assert!(resp.is_none(), "stream ends, got: {:?}", resp);
+    }
+
+    info!("--- cancelling again finds no stream");
+    {
+        let reply = grpc_client
+            .cancel_stream(CancelStreamRequest { stream_id })
+            .await?
+            .into_inner();
+        assert!(!reply.cancelled);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_watch_stalled_watcher_is_dropped() -> anyhow::Result<()> {
+    // - Watch without consuming the stream.
+    // - Write more than the stream can buffer for it.
+    // - Assert the stalled watcher is dropped after the send timeout, without blocking the dispatcher.
+
+    let (tc, addr) = crate::tests::start_metasrv().await?;
+
+    let client = make_client(&addr)?;
+
+    let watch = WatchRequest {
+        key: s("a"),
+        key_end: Some(s("z")),
+        filter_type: FilterType::All.into(),
+        initial_flush: false,
+    };
+
+    let _watch_stream = client.request(watch).await?;
+
+    let mn: Arc<MetaNode> = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+    assert_eq!(1, watcher_count(&mn).await);
+
+    info!("--- write more than the stream buffers");
+    {
+        let value = vec![b'x'; 64 * 1024];
+        for _ in 0..64 {
+            client.upsert_kv(UpsertKVReq::update("b", &value)).await?;
+        }
+    }
+
+    info!("--- the dispatcher is not blocked by the stalled watcher");
+    {
+        let n = tokio::time::timeout(Duration::from_secs(3), watcher_count(&mn)).await?;
+        assert_eq!(1, n);
+    }
+
+    info!("--- the stalled watcher is removed after the send timeout");
+    {
+        sleep(STREAM_SEND_TIMEOUT + Duration::from_secs(3)).await;
+        assert_eq!(0, watcher_count(&mn).await);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_watch_burst_larger_than_stream_buffer() -> anyhow::Result<()> {
+    // - Watch and keep reading the stream.
+    // - Change many more keys at once than the stream buffers, in one txn.
+    // - Assert every event is received and the watcher is kept.
+
+    let (tc, addr) = crate::tests::start_metasrv().await?;
+
+    let client = make_client(&addr)?;
+
+    let watch = WatchRequest {
+        key: s("a"),
+        key_end: Some(s("z")),
+        filter_type: FilterType::All.into(),
+        initial_flush: false,
+    };
+
+    let mut watch_stream = client.request(watch).await?;
+    let resp = watch_stream.message().await?.unwrap();
+    assert_eq!(None, resp.event);
+
+    let n = STREAM_BUFFER_SIZE * 50;
+
+    info!("--- put {} keys in one txn", n);
+    {
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: (0..n)
+                .map(|i| TxnOp::put(format!("b/{:04}", i), b("v")))
+                .collect(),
+            else_then: vec![],
+        };
+        client.transaction(txn).await?;
+    }
+
+    info!("--- a reading client receives every event");
+    {
+        for i in 0..n {
+            let resp = tokio::time::timeout(Duration::from_secs(5), watch_stream.message())
+                .await??
+                .unwrap();
+            assert_eq!(format!("b/{:04}", i), resp.event.unwrap().key);
+        }
+
+        let mn: Arc<MetaNode> = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+        assert_eq!(1, watcher_count(&mn).await);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_watch_queue_overflow_closes_with_status() -> anyhow::Result<()> {
+    // - Queue more events than a watcher holds, without reading its stream.
+    // - Assert the stream ends with `resource_exhausted` instead of a silent end.
+
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+    let mn: Arc<MetaNode> = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+
+    let (tx, mut rx) = bounded_stream(STREAM_BUFFER_SIZE, STREAM_SEND_TIMEOUT);
+    let watcher = Watcher::new(1_000_000, FilterType::All, s("a")..s("z"), "root");
+    let handle = WatchStreamHandle::new(watcher, tx, mn.dispatcher_handle.clone());
+
+    let resp = |i: usize| WatchResponse {
+        event: Some(Event {
+            key: format!("b/{}", i),
+            current: pb_seqv(1, "v", None),
+            prev: None,
+        }),
+        is_initialization: false,
+        stream_id: 1_000_000,
+    };
+
+    let mut overflowed = false;
+    for i in 0..WATCHER_QUEUE_SIZE * 2 {
+        if handle.try_send(resp(i)).is_err() {
+            overflowed = true;
+            break;
+        }
+    }
+    assert!(overflowed);
+
+    // The dispatcher drops the handle of an overflowed watcher.
+    drop(handle);
+
+    let mut n_events = 0;
+    let status = loop {
+        let item = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await?
+            .expect("the stream ends with an error status");
+        match item {
+            Ok(_) => n_events += 1,
+            Err(status) => break status,
+        }
+    };
+    assert_eq!(tonic::Code::ResourceExhausted, status.code());
+    assert!(n_events < WATCHER_QUEUE_SIZE, "pending events are dropped");
+    assert!(rx.recv().await.is_none());
+
+    Ok(())
+}
+
+/// The number of watchers registered in the dispatcher of a meta node.
+async fn watcher_count(mn: &MetaNode) -> usize {
+    let cnt = Arc::new(std::sync::Mutex::new(0usize));
+    {
+        let cnt = cnt.clone();
+        mn.dispatcher_handle
+            .request_blocking(move |d| {
+                *cnt.lock().unwrap() = d.watchers().count();
+            })
+            .await;
+    }
+    let n = *cnt.lock().unwrap();
+    n
+}
+
 fn s(x: &str) -> String {
     x.to_string()
 }
@@ -0,0 +1,62 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test sampled logging of writes of metasrv gRPC service.
+
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use databend_meta::api::grpc::write_log_sampler::WriteLogSampler;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_write_log_sampler() -> anyhow::Result<()> {
+    let sampled = |rate: u64, n: usize| {
+        let sampler = WriteLogSampler::new(rate);
+        (0..n).filter(|_| sampler.sample()).count()
+    };
+
+    assert_eq!(10, sampled(1, 10), "rate 1 logs every write");
+    assert_eq!(0, sampled(0, 10), "rate 0 logs nothing");
+    assert_eq!(4, sampled(3, 10), "1 in 3, starting from the first write");
+    assert_eq!(0, WriteLogSampler::default().rate(), "disabled by default");
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_metasrv_write_log_sample_rate() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.grpc_write_log_sample_rate = 1;
+    start_metasrv_with_context(&mut tc).await?;
+
+    let client = tc.grpc_client().await?;
+
+    info!("--- writes are served as usual when every write is logged");
+    {
+        client.upsert_kv(UpsertKVReq::update("foo", b"foo")).await?;
+        client.upsert_kv(UpsertKVReq::update("foo", b"bar")).await?;
+
+        let got = client.get_kv("foo").await?.unwrap();
+        assert_eq!(b"bar".to_vec(), got.data);
+    }
+
+    Ok(())
+}
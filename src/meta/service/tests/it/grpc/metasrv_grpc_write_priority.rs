@@ -0,0 +1,87 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the write priority of metasrv gRPC kv_api and transaction.
+
+use common_meta_client::reply_to_api_result;
+use common_meta_client::MetaGrpcReq;
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_kvapi::kvapi::UpsertKVReply;
+use common_meta_kvapi::kvapi::UpsertKVReq;
+use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::TxnOp;
+use common_meta_types::TxnRequest;
+use databend_meta::api::grpc::grpc_service::WRITE_PRIORITY_KEY;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+
+fn with_priority<T>(message: T, priority: &str) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    request
+        .metadata_mut()
+        .insert(WRITE_PRIORITY_KEY, priority.parse().unwrap());
+    request
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_write_priority() -> anyhow::Result<()> {
+    let (tc, _addr) = crate::tests::start_metasrv().await?;
+
+    let client = tc.grpc_client().await?;
+    let (mut grpc_client, _server_version) = client.make_client().await?;
+
+    info!("--- kv_api write with a priority");
+    {
+        let req = RaftRequest::from(MetaGrpcReq::UpsertKV(UpsertKVReq::update("foo", b"foo")));
+        let reply = grpc_client
+            .kv_api(with_priority(req, "low"))
+            .await?
+            .into_inner();
+        let res: UpsertKVReply = reply_to_api_result(reply)?;
+        assert_eq!(Some(b"foo".to_vec()), res.result.map(|x| x.data));
+    }
+
+    info!("--- transaction with a priority");
+    {
+        let txn = TxnRequest {
+            condition: vec![],
+            if_then: vec![TxnOp::put("bar", b"bar".to_vec())],
+            else_then: vec![],
+        };
+        let reply = grpc_client
+            .transaction(with_priority(txn, "high"))
+            .await?
+            .into_inner();
+        assert!(reply.success, "txn should succeed: {:?}", reply);
+
+        let got = client.get_kv("bar").await?;
+        assert_eq!(Some(b"bar".to_vec()), got.map(|x| x.data));
+    }
+
+    info!("--- an unknown priority is rejected");
+    {
+        let req = RaftRequest::from(MetaGrpcReq::UpsertKV(UpsertKVReq::update("foo", b"wow")));
+        let err = grpc_client
+            .kv_api(with_priority(req, "urgent"))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::InvalidArgument, err.code());
+        assert!(err.message().contains(WRITE_PRIORITY_KEY));
+    }
+
+    Ok(())
+}
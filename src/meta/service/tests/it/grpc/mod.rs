@@ -14,14 +14,34 @@
 
 pub mod metasrv_connection_error;
 pub mod metasrv_grpc_api;
+mod metasrv_grpc_apply_timeout;
+mod metasrv_grpc_catching_up;
+mod metasrv_grpc_count_prefix;
+mod metasrv_grpc_credentials;
 mod metasrv_grpc_export;
+mod metasrv_grpc_failed_applies;
 pub mod metasrv_grpc_get_client_info;
 pub mod metasrv_grpc_handshake;
+mod metasrv_grpc_increment;
+mod metasrv_grpc_kv_acl;
 pub mod metasrv_grpc_kv_api;
 pub mod metasrv_grpc_kv_api_restart_cluster;
+mod metasrv_grpc_kv_dry_run;
+mod metasrv_grpc_kv_idempotency;
 pub mod metasrv_grpc_kv_read_v1;
+mod metasrv_grpc_read_log;
+mod metasrv_grpc_read_only;
+mod metasrv_grpc_read_replica;
+mod metasrv_grpc_reserved_key;
 pub mod metasrv_grpc_schema_api;
 pub mod metasrv_grpc_schema_api_follower_follower;
 pub mod metasrv_grpc_schema_api_leader_follower;
+mod metasrv_grpc_schema_version;
+mod metasrv_grpc_server_config;
+mod metasrv_grpc_stream;
 pub mod metasrv_grpc_tls;
+mod metasrv_grpc_transfer_leader;
+mod metasrv_grpc_wait_applied;
 pub mod metasrv_grpc_watch;
+mod metasrv_grpc_write_log;
+mod metasrv_grpc_write_priority;
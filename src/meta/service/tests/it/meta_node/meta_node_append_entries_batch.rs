@@ -0,0 +1,121 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test that a lagging follower catches up in bounded append-entries batches.
+
+use std::collections::BTreeMap;
+
+use common_base::base::tokio;
+use common_meta_sled_store::openraft::ServerState;
+use common_meta_types::Cmd;
+use common_meta_types::LogEntry;
+use common_meta_types::Node;
+use common_meta_types::UpsertKV;
+use databend_meta::meta_service::MetaNode;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::meta_node::timeout;
+use crate::tests::service::MetaSrvTestContext;
+
+/// - Start a leader that sends at most 100 entries per append-entries request.
+/// - Write a backlog of 10,000 logs, then add a fresh follower.
+/// - The follower receives the backlog in many batches instead of one,
+///   each of at most 100 entries, and together all of the logs.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_append_entries_batch() -> anyhow::Result<()> {
+    let max_payload_entries = 100;
+    let n_writer: u64 = 10;
+    let n_per_writer: u64 = 1_000;
+
+    let mut tc0 = MetaSrvTestContext::new(0);
+    tc0.config.raft_config.max_payload_entries = max_payload_entries;
+    // Do not build a snapshot, so that the backlog is replicated as logs.
+    tc0.config.raft_config.snapshot_logs_since_last = 1_000_000;
+
+    let mn0 = MetaNode::boot(&tc0.config).await?;
+    tc0.assert_raft_server_connection().await?;
+
+    mn0.raft
+        .wait(timeout())
+        .state(ServerState::Leader, "leader started")
+        .await?;
+
+    info!("--- write a backlog of logs");
+    let mut handles = vec![];
+    for w in 0..n_writer {
+        let mn = mn0.clone();
+        handles.push(tokio::spawn(async move {
+            for i in 0..n_per_writer {
+                let key = format!("backlog-{}-{}", w, i);
+                mn.write(LogEntry::new(Cmd::UpsertKV(UpsertKV::update(&key, b"v"))))
+                    .await?;
+            }
+            Ok::<(), anyhow::Error>(())
+        }));
+    }
+    for h in handles {
+        h.await??;
+    }
+
+    info!("--- start a fresh follower");
+    let tc1 = MetaSrvTestContext::new(1);
+    let addr = tc1.config.raft_config.raft_api_addr().await?;
+    let mn1 = MetaNode::open_create(&tc1.config.raft_config, None, Some(())).await?;
+
+    mn0.add_node(
+        1,
+        Node::new(1, addr).with_grpc_advertise_address(tc1.config.grpc_api_advertise_address()),
+    )
+    .await?;
+
+    let last_log_index = mn0.raft.metrics().borrow().last_log_index;
+    assert!(last_log_index >= Some(n_writer * n_per_writer));
+
+    mn1.raft
+        .wait(timeout())
+        .log(last_log_index, "follower received the backlog")
+        .await?;
+
+    // A batch re-sent after a timeout starts at the same index; count it only once.
+    let batches = mn1
+        .append_entries_batches()
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+    info!("follower received batches: {:?}", batches);
+
+    assert!(
+        batches.len() > 1,
+        "expect multiple batches, got: {:?}",
+        batches
+    );
+
+    for (first_index, n_entries) in batches.iter() {
+        assert!(
+            *n_entries <= max_payload_entries,
+            "batch starting at {} has {} entries, more than {}",
+            first_index,
+            n_entries,
+            max_payload_entries
+        );
+    }
+
+    // Logs are indexed from 0: the backlog written, plus the membership and blank logs.
+    let n_received: u64 = batches.values().sum();
+    assert_eq!(last_log_index.unwrap() + 1, n_received);
+
+    Ok(())
+}
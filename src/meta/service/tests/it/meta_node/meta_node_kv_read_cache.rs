@@ -0,0 +1,76 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_meta_kvapi::kvapi::KVApi;
+use common_meta_sled_store::openraft::ServerState;
+use common_meta_types::Cmd;
+use common_meta_types::LogEntry;
+use common_meta_types::UpsertKV;
+use databend_meta::meta_service::MetaNode;
+use log::info;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::meta_node::timeout;
+use crate::tests::service::MetaSrvTestContext;
+
+/// A write applied to a cached key invalidates it, thus the next read returns the updated value.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_kv_read_cache_invalidated_by_write() -> anyhow::Result<()> {
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.raft_config.kv_read_cache_size = 16;
+
+    let mn = MetaNode::boot(&tc.config).await?;
+
+    mn.raft
+        .wait(timeout())
+        .state(ServerState::Leader, "leader started")
+        .await?;
+
+    assert!(mn.sto.kv_read_cache.is_enabled());
+
+    let key = "test_meta_node_kv_read_cache";
+
+    info!("--- read an absent key, cache it");
+    let got = mn.get_kv(key).await?;
+    assert_eq!(None, got);
+
+    info!("--- write v1, the cached absent value is invalidated");
+    mn.write(LogEntry::new(Cmd::UpsertKV(UpsertKV::update(key, b"v1"))))
+        .await?;
+
+    let got = mn.get_kv(key).await?;
+    assert_eq!(Some(b"v1".to_vec()), got.map(|x| x.data));
+
+    info!("--- read again, served from the cache");
+    let got = mn.get_kv(key).await?;
+    assert_eq!(Some(b"v1".to_vec()), got.map(|x| x.data));
+
+    info!("--- write v2, the cached v1 is invalidated");
+    mn.write(LogEntry::new(Cmd::UpsertKV(UpsertKV::update(key, b"v2"))))
+        .await?;
+
+    let got = mn.get_kv(key).await?;
+    assert_eq!(Some(b"v2".to_vec()), got.map(|x| x.data));
+
+    info!("--- delete it");
+    mn.write(LogEntry::new(Cmd::UpsertKV(UpsertKV::delete(key))))
+        .await?;
+
+    let got = mn.get_kv(key).await?;
+    assert_eq!(None, got);
+
+    Ok(())
+}
@@ -14,18 +14,23 @@
 
 //! Test raft protocol behaviors
 
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use common_base::base::tokio;
+use common_meta_types::AppendEntriesRequest;
+use common_meta_types::AppendEntriesResponse;
 use common_meta_types::Cmd;
 use common_meta_types::LogEntry;
 use common_meta_types::UpsertKV;
+use common_meta_types::Vote;
 use log::info;
 use maplit::btreeset;
 use test_harness::test;
 
 use crate::testing::meta_service_test_harness;
 use crate::tests::meta_node::start_meta_node_cluster;
+use crate::tests::meta_node::timeout;
 
 /// When a follower is dumping a snapshot, it should not block append entries request.
 /// Thus heartbeat should still be processed, and logs can be committed by leader(but not by followers).
@@ -118,3 +123,78 @@ async fn test_meta_node_serializing_snapshot_does_not_block_append_entries() ->
 
     Ok(())
 }
+
+/// `MetaNode::trigger_snapshot()` waits for the snapshot to be built and reports the last log id it includes.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_trigger_snapshot() -> anyhow::Result<()> {
+    info!("--- initialize cluster 1 voter");
+    let (mut _log_index, mut tcs) = start_meta_node_cluster(btreeset![0], btreeset![]).await?;
+
+    let tc0 = tcs.remove(0);
+    let mn0 = tc0.meta_node.clone().unwrap();
+
+    info!("--- write some data");
+    for key in ["a", "b", "c"] {
+        mn0.assume_leader()
+            .await?
+            .write(LogEntry {
+                txid: None,
+                time_ms: None,
+                cmd: Cmd::UpsertKV(UpsertKV::update(key, key.as_bytes())),
+            })
+            .await?;
+    }
+
+    let last_applied = mn0.raft.metrics().borrow().last_applied;
+
+    info!("--- trigger snapshot and wait for it");
+    let status = mn0.trigger_snapshot().await?;
+
+    assert!(!status.in_progress);
+    assert!(status.snapshot_last_log_id >= last_applied);
+    assert!(!mn0.building_snapshot.load(Ordering::Relaxed));
+
+    Ok(())
+}
+
+/// A heartbeat, i.e., an `append_entries` without entries, updates the vote of a follower.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_heartbeat_append_entries() -> anyhow::Result<()> {
+    info!("--- initialize cluster 2 voters");
+    let (mut _log_index, mut tcs) = start_meta_node_cluster(btreeset![0, 1], btreeset![]).await?;
+
+    let _tc0 = tcs.remove(0);
+    let tc1 = tcs.remove(0);
+
+    let mn1 = tc1.meta_node.clone().unwrap();
+
+    let (term, last_applied) = {
+        let m = mn1.raft.metrics().borrow().clone();
+        (m.current_term, m.last_applied)
+    };
+
+    let heartbeat = AppendEntriesRequest {
+        vote: Vote::new_committed(term + 1, 0),
+        prev_log_id: last_applied,
+        entries: vec![],
+        leader_commit: last_applied,
+    };
+
+    info!("--- send a heartbeat with a greater term to the follower");
+    let mut client = tc1.raft_client().await?;
+    let reply = client.append_entries(heartbeat.clone()).await?.into_inner();
+    let resp: AppendEntriesResponse = serde_json::from_str(&reply.data)?;
+    assert_eq!(AppendEntriesResponse::Success, resp);
+
+    mn1.raft
+        .wait(timeout())
+        .metrics(
+            |m| m.current_term == term + 1 && m.last_applied == last_applied,
+            "follower accepted the term of the heartbeat",
+        )
+        .await?;
+
+    Ok(())
+}
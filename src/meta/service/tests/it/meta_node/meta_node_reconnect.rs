@@ -0,0 +1,134 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_sled_store::openraft::RaftNetworkFactory;
+use common_meta_types::Cmd;
+use common_meta_types::LogEntry;
+use common_meta_types::MembershipNode;
+use common_meta_types::UpsertKV;
+use databend_meta::meta_service::MetaNode;
+use databend_meta::network::Network;
+use databend_meta::network::ReconnectBackoff;
+use log::info;
+use maplit::btreeset;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::meta_node::start_meta_node_cluster;
+use crate::tests::meta_node::timeout;
+
+/// Connection failures delay the next connection more and more, until a connection succeeds.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_reconnect_backoff() -> anyhow::Result<()> {
+    let min_delay = Duration::from_millis(10);
+    let max_delay = Duration::from_millis(100);
+
+    let mut backoff = ReconnectBackoff::new(min_delay, max_delay);
+    assert_eq!(None, backoff.wait_time(), "connect at once at first");
+
+    info!("--- the peer is down: every failure delays the next connection");
+    for _ in 0..10 {
+        let delay = backoff.on_failure();
+        assert!(delay > Duration::ZERO);
+        assert!(delay <= max_delay * 2, "delay with jitter is bounded");
+        assert!(backoff.wait_time().is_some());
+    }
+
+    info!("--- the delay passes");
+    tokio::time::sleep(max_delay * 2).await;
+    assert_eq!(None, backoff.wait_time());
+
+    info!("--- the peer recovers: a successful connection resets the delay");
+    backoff.on_failure();
+    backoff.on_success();
+    assert_eq!(None, backoff.wait_time());
+
+    Ok(())
+}
+
+/// After failing to connect to a down peer, connecting to it again fails fast,
+/// even through a new connection, until the backoff passes.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_network_reconnect_backoff() -> anyhow::Result<()> {
+    info!("--- initialize cluster 2 voters");
+    let (_log_index, tcs) = start_meta_node_cluster(btreeset![0, 1], btreeset![]).await?;
+
+    let mn0 = tcs[0].meta_node();
+
+    info!("--- stop node-1");
+    tcs[1].meta_node().stop().await?;
+
+    let mut network = Network::new(mn0.sto.clone());
+    assert_eq!(None, network.reconnect_wait_time(&1));
+
+    info!("--- the first connection fails and starts a backoff");
+    let conn = network.new_client(1, &MembershipNode {}).await;
+    let res = conn.make_client().await;
+    assert!(res.is_err());
+    assert!(
+        network.reconnect_wait_time(&1).is_some(),
+        "a failure starts a backoff"
+    );
+
+    info!("--- a new connection to the same target fails fast during the backoff");
+    let conn = network.new_client(1, &MembershipNode {}).await;
+    let err = conn.make_client().await.unwrap_err();
+    assert!(err.to_string().contains("backoff"), "got: {}", err);
+
+    info!("--- other targets are not affected");
+    assert_eq!(None, network.reconnect_wait_time(&0));
+
+    Ok(())
+}
+
+/// The leader keeps reconnecting to a down follower with backoff,
+/// and replicates to it once it is back.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_reconnect_to_recovered_follower() -> anyhow::Result<()> {
+    info!("--- initialize cluster 3 voters");
+    let (mut log_index, tcs) = start_meta_node_cluster(btreeset![0, 1, 2], btreeset![]).await?;
+
+    let mn0 = tcs[0].meta_node();
+    let tc2 = &tcs[2];
+
+    info!("--- stop follower node-2");
+    tc2.meta_node().stop().await?;
+
+    info!("--- write to the leader while node-2 is down");
+    for i in 0..3 {
+        let key = format!("test_meta_node_reconnect-{}", i);
+        mn0.write(LogEntry::new(Cmd::UpsertKV(UpsertKV::update(&key, b"v"))))
+            .await?;
+    }
+    log_index += 3;
+
+    info!("--- let the leader fail to connect to node-2 for a while");
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    info!("--- restart node-2");
+    let mn2 = MetaNode::open_create(&tc2.config.raft_config, Some(()), None).await?;
+
+    mn2.raft
+        .wait(timeout())
+        .log(Some(log_index), "node-2 catches up after reconnected")
+        .await?;
+
+    Ok(())
+}
@@ -13,15 +13,28 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use common_meta_kvapi::kvapi::KVApi;
 use common_meta_sled_store::openraft::error::RaftError;
+use common_meta_types::protobuf::raft_service_server::RaftService;
+use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::ClientWriteError;
 use common_meta_types::Cmd;
+use common_meta_types::Endpoint;
 use common_meta_types::ForwardToLeader;
 use common_meta_types::LogEntry;
 use common_meta_types::UpsertKV;
+use databend_meta::message::ForwardResponse;
+use databend_meta::message::FORWARD_CONTENT_TYPE;
 use databend_meta::meta_service::meta_leader::MetaLeader;
+use databend_meta::meta_service::ForwardContentType;
+use databend_meta::meta_service::ForwardRequest;
+use databend_meta::meta_service::ForwardRequestBody;
+use databend_meta::meta_service::JoinRequest;
 use databend_meta::meta_service::MetaNode;
+use databend_meta::meta_service::RaftServiceImpl;
+use databend_meta::metrics::server_metrics;
 use maplit::btreeset;
 use test_harness::test;
 
@@ -76,6 +89,266 @@ async fn test_meta_node_forward_to_leader() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_write_forwarding_counters() -> anyhow::Result<()> {
+    // - Start a leader and 2 followers;
+    // - Write through a follower, expect the forwarded counter to increase;
+    // - Write through the leader, expect the handled-as-leader counter to increase.
+    //
+    // The counters are process wide and other tests may write concurrently,
+    // thus only the lower bound of an increment is asserted.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0, 1, 2], btreeset![]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let leader_id = all[0].raft.metrics().borrow().current_leader.unwrap();
+    let leader: &MetaNode = &all[leader_id as usize];
+    let follower: &MetaNode = all.iter().find(|mn| mn.sto.id != leader_id).unwrap();
+
+    let entry = |key: &str| LogEntry {
+        txid: None,
+        time_ms: None,
+        cmd: Cmd::UpsertKV(UpsertKV::update(key, key.as_bytes())),
+    };
+
+    let (_, forwarded) = server_metrics::get_writes_handled();
+
+    follower.write(entry("t-forwarded-1")).await?;
+    follower.write(entry("t-forwarded-2")).await?;
+
+    let (handled, forwarded_after) = server_metrics::get_writes_handled();
+    assert!(forwarded_after >= forwarded + 2);
+
+    leader.write(entry("t-handled")).await?;
+
+    let (handled_after, _) = server_metrics::get_writes_handled();
+    assert!(handled_after > handled);
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_stale_leader_rejects_write() -> anyhow::Result<()> {
+    // - Start a leader and 2 followers;
+    // - Stop the followers so that the leader is partitioned away and can not reach a quorum;
+    // - Write to the leader, expect a ForwardToLeader error without a known leader,
+    //   and nothing is applied to its local state machine.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0, 1, 2], btreeset![]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let leader_id = all[0].raft.metrics().borrow().current_leader.unwrap();
+    let leader = &all[leader_id as usize];
+
+    for (id, mn) in all.iter().enumerate() {
+        if id as u64 != leader_id {
+            mn.stop().await?;
+        }
+    }
+
+    let key = "t-stale-leader-write";
+    let rst = MetaLeader::new(leader)
+        .write(LogEntry {
+            txid: None,
+            time_ms: None,
+            cmd: Cmd::UpsertKV(UpsertKV::update(key, key.as_bytes())),
+        })
+        .await;
+
+    match rst {
+        Err(RaftError::APIError(ClientWriteError::ForwardToLeader(ForwardToLeader {
+            leader_id: None,
+            ..
+        }))) => {}
+        _ => {
+            panic!("expect ForwardToLeader without leader, got: {:?}", rst)
+        }
+    }
+
+    let sm = leader.sto.state_machine.read().await;
+    let got = sm.kv_api().get_kv(key).await.unwrap();
+    assert!(got.is_none(), "stale leader must not apply the write");
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_forward_timeout() -> anyhow::Result<()> {
+    // - Start a leader and a learner;
+    // - Stop the leader, so that a request forwarded to it keeps being retried;
+    // - Send a write to the learner, expect a deadline_exceeded status.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0], btreeset![1]).await?;
+    let all = test_context_nodes(&tcs);
+
+    all[0].stop().await?;
+
+    let srv = RaftServiceImpl::create(all[1].clone()).with_forward_timeout(Duration::from_secs(1));
+
+    let key = "t-forward-timeout";
+    let req = ForwardRequest {
+        forward_to_leader: 1,
+        body: ForwardRequestBody::Write(LogEntry {
+            txid: None,
+            time_ms: None,
+            cmd: Cmd::UpsertKV(UpsertKV::update(key, key.as_bytes())),
+        }),
+    };
+    let req = tonic::Request::new(RaftRequest {
+        data: serde_json::to_string(&req)?,
+        binary_data: vec![],
+    });
+
+    let res = srv.forward(req).await;
+    let status = res.unwrap_err();
+    assert_eq!(
+        tonic::Code::DeadlineExceeded,
+        status.code(),
+        "got: {:?}",
+        status
+    );
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_forward_rejects_oversized_payload() -> anyhow::Result<()> {
+    // - Send a forward request larger than the limit, expect invalid_argument before it is parsed.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0], btreeset![]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let srv = RaftServiceImpl::create(all[0].clone()).with_max_forward_request_size(1024);
+
+    // Not a valid json: a payload that reaches the parser would get a parse error instead.
+    let req = tonic::Request::new(RaftRequest {
+        data: "x".repeat(1025),
+        binary_data: vec![],
+    });
+
+    let res = srv.forward(req).await;
+    let status = res.unwrap_err();
+    assert_eq!(tonic::Code::InvalidArgument, status.code());
+    assert!(
+        status.message().starts_with("request payload too large"),
+        "got: {:?}",
+        status
+    );
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_forward_rejects_deeply_nested_payload() -> anyhow::Result<()> {
+    // - Send a deeply nested forward request, expect invalid_argument from the recursion limit.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0], btreeset![]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let srv = RaftServiceImpl::create(all[0].clone());
+
+    let depth = 100_000;
+    let data = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    let req = tonic::Request::new(RaftRequest {
+        data,
+        binary_data: vec![],
+    });
+
+    let res = srv.forward(req).await;
+    let status = res.unwrap_err();
+    assert_eq!(tonic::Code::InvalidArgument, status.code());
+    assert!(
+        status.message().contains("recursion limit exceeded"),
+        "got: {:?}",
+        status
+    );
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_forward_content_type_round_trip() -> anyhow::Result<()> {
+    // - Encode a membership request in every content type, expect it to decode to the same request.
+
+    let req = ForwardRequest {
+        forward_to_leader: 1,
+        body: ForwardRequestBody::Join(JoinRequest::new(
+            3,
+            Endpoint::new("127.0.0.1", 29003),
+            Some("127.0.0.1:19003"),
+        )),
+    };
+
+    for content_type in [ForwardContentType::Json, ForwardContentType::MsgPack] {
+        let request = content_type.encode_req(&req);
+        assert_eq!(
+            content_type,
+            ForwardContentType::from_metadata(request.metadata())?
+        );
+
+        let got: ForwardRequest<ForwardRequestBody> = content_type.decode_req(request, 1024)?;
+        assert_eq!(req, got, "content type: {:?}", content_type);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_forward_with_content_type() -> anyhow::Result<()> {
+    // - Forward a Join of an existing voter in every content type, expect Ok encoded in the same type;
+    // - Forward with an unknown content type, expect invalid_argument.
+
+    let (mut _nlog, tcs) = start_meta_node_cluster(btreeset![0], btreeset![]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let srv = RaftServiceImpl::create(all[0].clone());
+
+    let req = ForwardRequest {
+        forward_to_leader: 1,
+        body: ForwardRequestBody::Join(JoinRequest::new(
+            0,
+            tcs[0].config.raft_config.raft_api_addr().await?,
+            tcs[0].config.grpc_api_advertise_address(),
+        )),
+    };
+
+    for content_type in [ForwardContentType::Json, ForwardContentType::MsgPack] {
+        let resp = srv.forward(content_type.encode_req(&req)).await?;
+        assert_eq!(
+            Some(content_type.as_str()),
+            resp.metadata()
+                .get(FORWARD_CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+        );
+
+        let reply = resp.into_inner();
+        assert_eq!(
+            content_type == ForwardContentType::MsgPack,
+            !reply.binary_data.is_empty()
+        );
+
+        let got: ForwardResponse = content_type.decode_reply(reply)?;
+        assert_eq!(ForwardResponse::Join(()), got);
+    }
+
+    let mut request = ForwardContentType::Json.encode_req(&req);
+    request
+        .metadata_mut()
+        .insert(FORWARD_CONTENT_TYPE, "xml".parse()?);
+
+    let status = srv.forward(request).await.unwrap_err();
+    assert_eq!(tonic::Code::InvalidArgument, status.code());
+
+    Ok(())
+}
+
 fn test_context_nodes(tcs: &[MetaSrvTestContext]) -> Vec<Arc<MetaNode>> {
     tcs.iter().map(|tc| tc.meta_node()).collect::<Vec<_>>()
 }
@@ -0,0 +1,123 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the order in which waiting writes are proposed.
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_meta_types::AppliedState;
+use common_meta_types::Change;
+use common_meta_types::Cmd;
+use common_meta_types::LogEntry;
+use common_meta_types::UpsertKV;
+use databend_meta::meta_service::MetaNode;
+use databend_meta::meta_service::WritePriority;
+use log::info;
+use maplit::btreeset;
+use test_harness::test;
+
+use crate::testing::meta_service_test_harness;
+use crate::tests::meta_node::start_meta_node_cluster;
+
+/// A high-priority write submitted after a backlog of low-priority writes is proposed before them.
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_node_write_priority() -> anyhow::Result<()> {
+    info!("--- initialize cluster 1 voter");
+    let (mut _log_index, mut tcs) = start_meta_node_cluster(btreeset![0], btreeset![]).await?;
+
+    let tc0 = tcs.remove(0);
+    let mn0 = tc0.meta_node();
+    let max_inflight = tc0.config.raft_config.max_inflight_proposals;
+
+    info!("--- occupy all proposal slots, so that new writes wait in the submit queue");
+    let mut permits = vec![];
+    for _ in 0..max_inflight {
+        permits.push(mn0.submit_queue.acquire(WritePriority::Normal).await);
+    }
+
+    info!("--- submit a backlog of low-priority writes");
+    let n_low = 5;
+    let mut low_handles = vec![];
+    for i in 0..n_low {
+        let mn = mn0.clone();
+        low_handles.push(tokio::spawn(async move {
+            mn.write_with_priority(upsert(&format!("low-{}", i)), WritePriority::Low)
+                .await
+        }));
+        wait_for_waiting(&mn0, i + 1).await?;
+    }
+
+    info!("--- submit a high-priority write after the backlog");
+    let mn = mn0.clone();
+    let high_handle = tokio::spawn(async move {
+        mn.write_with_priority(upsert("high"), WritePriority::High)
+            .await
+    });
+    wait_for_waiting(&mn0, n_low + 1).await?;
+
+    info!("--- release one slot: the writes are admitted one at a time through it");
+    drop(permits.pop());
+
+    // The high-priority write is admitted first, then the low-priority ones in arrival order.
+    let mut seqs = vec![result_seq(high_handle.await??)];
+    for h in low_handles {
+        seqs.push(result_seq(h.await??));
+    }
+
+    for w in seqs.windows(2) {
+        assert!(
+            w[0] < w[1],
+            "writes should be admitted in order: high, low-0..low-{}, got seqs: {:?}",
+            n_low - 1,
+            seqs
+        );
+    }
+
+    drop(permits);
+
+    assert_eq!(0, mn0.submit_queue.waiting());
+    assert_eq!(0, mn0.submit_queue.inflight());
+
+    Ok(())
+}
+
+fn upsert(key: &str) -> LogEntry {
+    LogEntry::new(Cmd::UpsertKV(UpsertKV::update(key, key.as_bytes())))
+}
+
+fn result_seq(state: AppliedState) -> u64 {
+    match state {
+        AppliedState::KV(Change {
+            result: Some(seqv), ..
+        }) => seqv.seq,
+        _ => unreachable!("expect a kv change with result, got: {:?}", state),
+    }
+}
+
+async fn wait_for_waiting(mn: &MetaNode, n: usize) -> anyhow::Result<()> {
+    for _ in 0..100 {
+        if mn.submit_queue.waiting() == n {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    Err(anyhow::anyhow!(
+        "expect {} waiting writes, got: {}",
+        n,
+        mn.submit_queue.waiting()
+    ))
+}
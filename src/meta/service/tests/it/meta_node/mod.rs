@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod meta_node_append_entries_batch;
 pub(crate) mod meta_node_kv_api;
 pub(crate) mod meta_node_kv_api_expire;
+pub(crate) mod meta_node_kv_read_cache;
 pub(crate) mod meta_node_lifecycle;
 pub(crate) mod meta_node_raft_api;
+pub(crate) mod meta_node_reconnect;
 pub(crate) mod meta_node_replication;
 pub(crate) mod meta_node_request_forwarding;
+pub(crate) mod meta_node_write_priority;
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::Ordering;
+
 use common_meta_raft_store::sm_v002::leveled_store::sys_data_api::SysDataApiRO;
 use common_meta_raft_store::state_machine::testing::snapshot_logs;
 use common_meta_sled_store::openraft::async_trait::async_trait;
@@ -287,3 +289,21 @@ async fn test_meta_store_install_snapshot() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test(harness = meta_service_test_harness)]
+#[minitrace::trace]
+async fn test_meta_store_abort_receiving_snapshot() -> anyhow::Result<()> {
+    // - Begin receiving a snapshot, then drop the snapshot data without installing it.
+    // - The store is no longer installing a snapshot.
+
+    let tc = MetaSrvTestContext::new(0);
+    let mut sto = RaftStore::open_create(&tc.config.raft_config, None, Some(())).await?;
+
+    let data = sto.begin_receiving_snapshot().await?;
+    assert!(sto.installing_snapshot.load(Ordering::Relaxed));
+
+    drop(data);
+    assert!(!sto.installing_snapshot.load(Ordering::Relaxed));
+
+    Ok(())
+}
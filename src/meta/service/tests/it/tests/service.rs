@@ -140,6 +140,12 @@ impl MetaSrvTestContext {
         config.raft_config.raft_listen_host = "127.0.0.1".to_string();
         config.raft_config.raft_advertise_host = "localhost".to_string();
 
+        // Nodes in a cluster share the secret of tokens, to accept the tokens issued by each other.
+        config.raft_config.token_secret = "test-token-secret".to_string();
+
+        // Test clients handshake with a placeholder password, without a stored hash.
+        config.grpc_disable_password_auth = true;
+
         let host = "127.0.0.1";
 
         // We use a single sled db for all unit test. Every unit test need a unique prefix so that it opens different tree.
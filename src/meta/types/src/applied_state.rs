@@ -121,6 +121,8 @@ impl From<AppliedState> for RaftReply {
         RaftReply {
             data,
             error: "".to_string(),
+            compressed_data: vec![],
+            binary_data: vec![],
         }
     }
 }
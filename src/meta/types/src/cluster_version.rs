@@ -0,0 +1,63 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The version of the replicated data a whole meta-service cluster agrees on.
+//!
+//! A feature that changes what is sent between nodes, such as a new raft log payload,
+//! a new snapshot entry or a new forwarded request, can only be used after every node is upgraded
+//! to a build that understands it. Such a feature is gated on the cluster version,
+//! which an operator raises with the root-only `SetClusterVersion` gRPC API when a rolling upgrade is done.
+//!
+//! The version is stored as a plain kv under [`CLUSTER_VERSION_KEY`],
+//! so that a node of an older build can still replicate, apply and snapshot it.
+//! The key is reserved: a client can read it but can not write or delete it.
+
+/// The key storing the cluster version in the state machine, as a decimal string.
+pub const CLUSTER_VERSION_KEY: &str = "__fd_meta/cluster_version";
+
+/// The version of a cluster that has never been raised, or is written by an older build.
+pub const CLUSTER_VERSION_V0: u64 = 0;
+
+/// Enables:
+/// - de-duplicating writes by an idempotency key, which stores a txid in raft logs and
+///   the last responses to clients in snapshots;
+/// - forwarding a write that replies with its log index, a priority, or a dry run;
+/// - forwarding a `CountPrefix` request to the leader;
+/// - the `Increment` command.
+pub const CLUSTER_VERSION_V1: u64 = 1;
+
+/// The greatest cluster version this build supports.
+pub const MAX_CLUSTER_VERSION: u64 = CLUSTER_VERSION_V1;
+
+/// Decode the value stored under [`CLUSTER_VERSION_KEY`].
+///
+/// An absent or invalid value is [`CLUSTER_VERSION_V0`].
+pub fn decode_cluster_version(value: Option<&[u8]>) -> u64 {
+    value
+        .and_then(|v| std::str::from_utf8(v).ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(CLUSTER_VERSION_V0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_cluster_version() {
+        assert_eq!(CLUSTER_VERSION_V0, decode_cluster_version(None));
+        assert_eq!(CLUSTER_VERSION_V0, decode_cluster_version(Some(b"x")));
+        assert_eq!(CLUSTER_VERSION_V1, decode_cluster_version(Some(b"1")));
+    }
+}
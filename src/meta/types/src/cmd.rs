@@ -46,6 +46,9 @@ pub enum Cmd {
 
     /// Update one or more kv with a transaction.
     Transaction(TxnRequest),
+
+    /// Add a delta to the number stored in a key, or create the key with the delta if it is absent.
+    IncrementKV(IncrementKV),
 }
 
 /// Update or insert a general purpose kv store
@@ -66,6 +69,16 @@ pub struct UpsertKV {
     pub value_meta: Option<KVMeta>,
 }
 
+/// Atomically add `delta` to the number stored in `key`.
+///
+/// The value is an `i64` encoded as decimal text.
+/// A value that is not such a number, or an overflowing sum, leaves the key unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IncrementKV {
+    pub key: String,
+    pub delta: i64,
+}
+
 impl fmt::Display for Cmd {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -89,10 +102,19 @@ impl fmt::Display for Cmd {
             Cmd::Transaction(txn) => {
                 write!(f, "txn:{}", txn)
             }
+            Cmd::IncrementKV(incr) => {
+                write!(f, "increment_kv:{}", incr)
+            }
         }
     }
 }
 
+impl fmt::Display for IncrementKV {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} += {}", self.key, self.delta)
+    }
+}
+
 impl fmt::Display for UpsertKV {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -160,6 +182,35 @@ impl UpsertKV {
     }
 }
 
+impl IncrementKV {
+    pub fn new(key: impl ToString, delta: i64) -> Self {
+        Self {
+            key: key.to_string(),
+            delta,
+        }
+    }
+
+    /// Decode a counter value, which is an `i64` encoded as decimal text.
+    pub fn decode_value(data: &[u8]) -> Option<i64> {
+        std::str::from_utf8(data).ok()?.parse().ok()
+    }
+
+    pub fn encode_value(value: i64) -> Vec<u8> {
+        value.to_string().into_bytes()
+    }
+
+    /// Returns the value after adding `delta` to the previous value, which is 0 if it is absent.
+    ///
+    /// Returns None if the previous value is not a number or the sum overflows.
+    pub fn incremented(&self, prev: Option<&[u8]>) -> Option<i64> {
+        let prev = match prev {
+            None => 0,
+            Some(data) => Self::decode_value(data)?,
+        };
+        prev.checked_add(self.delta)
+    }
+}
+
 impl With<MatchSeq> for UpsertKV {
     fn with(mut self, seq: MatchSeq) -> Self {
         self.seq = seq;
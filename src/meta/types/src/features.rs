@@ -0,0 +1,81 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+/// A bitmap of optional RPC behaviors that a meta-client and a metasrv negotiate in handshake.
+///
+/// A client advertises the features it supports in `HandshakeRequest.features`,
+/// and the server replies with the intersection of them and the features it supports.
+/// A legacy peer that does not know about features sends or replies with `0`, i.e., none.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Features(u64);
+
+impl Features {
+    /// No optional feature is enabled.
+    pub const NONE: Features = Features(0);
+
+    /// `RaftReply.data` is sent zstd-compressed in `RaftReply.compressed_data`.
+    pub const COMPRESSION: Features = Features(1);
+
+    /// All of the features this build supports.
+    pub const SUPPORTED: Features = Features::COMPRESSION;
+
+    pub const fn from_bits(bits: u64) -> Self {
+        Features(bits)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: Features) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Return the features enabled in both `self` and `other`.
+    pub const fn intersect(&self, other: Features) -> Self {
+        Features(self.0 & other.0)
+    }
+
+    /// Return the features this build supports among the ones advertised by a peer.
+    pub const fn negotiate(peer_bits: u64) -> Self {
+        Features::from_bits(peer_bits).intersect(Features::SUPPORTED)
+    }
+}
+
+impl fmt::Debug for Features {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Features({:#b})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Features;
+
+    #[test]
+    fn test_features_negotiate() {
+        assert_eq!(Features::COMPRESSION, Features::negotiate(1));
+        assert_eq!(Features::NONE, Features::negotiate(0));
+
+        // Unknown bits from a newer peer are dropped.
+        let f = Features::negotiate(0b111);
+        assert_eq!(Features::SUPPORTED, f);
+        assert!(f.contains(Features::COMPRESSION));
+
+        assert!(!Features::NONE.contains(Features::COMPRESSION));
+        assert!(Features::NONE.contains(Features::NONE));
+    }
+}
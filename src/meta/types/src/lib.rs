@@ -21,10 +21,12 @@
 mod applied_state;
 mod change;
 mod cluster;
+pub mod cluster_version;
 mod cmd;
 pub mod config;
 mod endpoint;
 pub mod errors;
+mod features;
 mod grpc_config;
 mod log_entry;
 mod match_seq;
@@ -33,6 +35,7 @@ mod operation;
 mod raft_snapshot_data;
 mod raft_txid;
 mod raft_types;
+pub mod schema_version;
 mod seq_errors;
 mod seq_num;
 mod seq_value;
@@ -57,6 +60,7 @@ pub use change::Change;
 pub use cluster::Node;
 pub use cluster::NodeInfo;
 pub use cmd::Cmd;
+pub use cmd::IncrementKV;
 pub use cmd::UpsertKV;
 pub use endpoint::Endpoint;
 pub use errors::meta_api_errors::MetaAPIError;
@@ -74,6 +78,7 @@ pub use errors::meta_network_errors::MetaNetworkError;
 pub use errors::meta_network_errors::MetaNetworkResult;
 pub use errors::meta_startup_errors::MetaStartupError;
 pub use errors::rpc_errors::ForwardRPCError;
+pub use features::Features;
 pub use grpc_config::GrpcConfig;
 pub use log_entry::LogEntry;
 pub use match_seq::MatchSeq;
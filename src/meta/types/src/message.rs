@@ -26,6 +26,7 @@ impl tonic::IntoRequest<RaftRequest> for LogEntry {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(&self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -45,6 +46,7 @@ impl tonic::IntoRequest<RaftRequest> for AppendEntriesRequest {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(&self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -54,6 +56,7 @@ impl tonic::IntoRequest<RaftRequest> for &AppendEntriesRequest {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -63,6 +66,7 @@ impl tonic::IntoRequest<RaftRequest> for InstallSnapshotRequest {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(&self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -72,6 +76,7 @@ impl tonic::IntoRequest<RaftRequest> for &InstallSnapshotRequest {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -81,6 +86,7 @@ impl tonic::IntoRequest<RaftRequest> for VoteRequest {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(&self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -90,6 +96,7 @@ impl tonic::IntoRequest<RaftRequest> for &VoteRequest {
     fn into_request(self) -> tonic::Request<RaftRequest> {
         let mes = RaftRequest {
             data: serde_json::to_string(self).expect("fail to serialize"),
+            binary_data: vec![],
         };
         tonic::Request::new(mes)
     }
@@ -105,6 +112,8 @@ where T: Serialize
                 RaftReply {
                     data,
                     error: Default::default(),
+                    compressed_data: Default::default(),
+                    binary_data: Default::default(),
                 }
             }
             Err(e) => {
@@ -112,6 +121,8 @@ where T: Serialize
                 RaftReply {
                     data: Default::default(),
                     error,
+                    compressed_data: Default::default(),
+                    binary_data: Default::default(),
                 }
             }
         }
@@ -14,6 +14,7 @@
 
 //! Extend protobuf generated code with some useful methods.
 
+mod raft_reply_ext;
 mod seq_v_ext;
 mod stream_item_ext;
 mod txn_ext;
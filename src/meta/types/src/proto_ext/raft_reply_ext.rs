@@ -0,0 +1,89 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+
+use crate::protobuf::RaftReply;
+
+impl RaftReply {
+    /// Move `data` into `compressed_data` in zstd format.
+    ///
+    /// An error reply is left as is.
+    pub fn compress(self) -> Result<Self, io::Error> {
+        if self.data.is_empty() {
+            return Ok(self);
+        }
+
+        let compressed = zstd::bulk::compress(self.data.as_bytes(), 0)?;
+        Ok(RaftReply {
+            data: "".to_string(),
+            error: self.error,
+            compressed_data: compressed,
+            binary_data: self.binary_data,
+        })
+    }
+
+    /// Restore `data` from `compressed_data`, if it is compressed.
+    pub fn decompress(self) -> Result<Self, io::Error> {
+        if self.compressed_data.is_empty() {
+            return Ok(self);
+        }
+
+        let mut decoded = vec![];
+        zstd::stream::copy_decode(self.compressed_data.as_slice(), &mut decoded)?;
+        let data = String::from_utf8(decoded)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(RaftReply {
+            data,
+            error: self.error,
+            compressed_data: vec![],
+            binary_data: self.binary_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protobuf::RaftReply;
+
+    #[test]
+    fn test_raft_reply_compress() -> anyhow::Result<()> {
+        let reply = RaftReply {
+            data: r#"{"foo":"bar"}"#.to_string(),
+            error: "".to_string(),
+            compressed_data: vec![],
+            binary_data: vec![],
+        };
+
+        let compressed = reply.clone().compress()?;
+        assert_eq!("", compressed.data);
+        assert!(!compressed.compressed_data.is_empty());
+
+        assert_eq!(reply, compressed.decompress()?);
+
+        // Plain reply and error reply are not changed.
+        assert_eq!(reply, reply.clone().decompress()?);
+
+        let err_reply = RaftReply {
+            data: "".to_string(),
+            error: "err".to_string(),
+            compressed_data: vec![],
+            binary_data: vec![],
+        };
+        assert_eq!(err_reply, err_reply.clone().compress()?);
+
+        Ok(())
+    }
+}
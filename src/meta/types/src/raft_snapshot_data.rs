@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
 use std::io;
 use std::io::SeekFrom;
 use std::pin::Pin;
@@ -33,6 +34,8 @@ pub struct SnapshotData {
     is_temp: bool,
     path: String,
     f: fs::File,
+    /// A value dropped along with this snapshot data, e.g., to reset a state if receiving is aborted.
+    guard: Option<Box<dyn Any + Send + Sync>>,
 }
 
 impl SnapshotData {
@@ -47,6 +50,7 @@ impl SnapshotData {
             is_temp: false,
             path,
             f: fs::File::from_std(f),
+            guard: None,
         })
     }
 
@@ -62,9 +66,20 @@ impl SnapshotData {
             is_temp: true,
             path,
             f,
+            guard: None,
         })
     }
 
+    /// Keep `guard` until this snapshot data is dropped, or until it is taken by [`Self::take_guard`].
+    pub fn with_guard(mut self, guard: impl Any + Send + Sync) -> Self {
+        self.guard = Some(Box::new(guard));
+        self
+    }
+
+    pub fn take_guard(&mut self) -> Option<Box<dyn Any + Send + Sync>> {
+        self.guard.take()
+    }
+
     pub async fn data_size(&self) -> Result<u64, io::Error> {
         self.f.metadata().await.map(|m| m.len())
     }
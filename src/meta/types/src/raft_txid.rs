@@ -14,6 +14,7 @@
 
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::str::FromStr;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -44,3 +45,23 @@ impl Display for RaftTxId {
         write!(f, "txid-{}-{}", &self.client, self.serial)
     }
 }
+
+/// Parse a RaftTxId from its display form: `txid-<client>-<serial>`.
+///
+/// The client id may contain `-`, the serial is the part after the last `-`.
+impl FromStr for RaftTxId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid txid: {:?}, expect: txid-<client>-<serial>", s);
+
+        let body = s.strip_prefix("txid-").ok_or_else(invalid)?;
+        let (client, serial) = body.rsplit_once('-').ok_or_else(invalid)?;
+        if client.is_empty() {
+            return Err(invalid());
+        }
+        let serial = serial.parse::<u64>().map_err(|_| invalid())?;
+
+        Ok(Self::new(client, serial))
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The minimum schema version a write must carry for the keys of a namespace.
+//!
+//! When the layout of the keys under a prefix changes, raising the minimum version of the prefix
+//! stops clients of an older layout from writing to it.
+//!
+//! The minimum of a namespace is stored as a plain kv under [`SCHEMA_VERSION_KEY_PREFIX`]
+//! followed by the namespace prefix, so that every node checks writes against the same versions.
+//! A minimum is raised only with the root-only `SetSchemaVersion` gRPC API;
+//! the keys are reserved, a client can read them but can not write or delete them.
+
+/// The key prefix storing the minimum schema version of each namespace, as a decimal string.
+///
+/// E.g., `__fd_meta/schema_version/tbl/` stores the minimum version of keys starting with `tbl/`.
+pub const SCHEMA_VERSION_KEY_PREFIX: &str = "__fd_meta/schema_version/";
+
+/// The key storing the minimum schema version of the namespace `prefix`.
+pub fn schema_version_key(prefix: &str) -> String {
+    format!("{}{}", SCHEMA_VERSION_KEY_PREFIX, prefix)
+}
+
+/// Decode a key-value stored under [`SCHEMA_VERSION_KEY_PREFIX`] into the namespace prefix and its minimum version.
+///
+/// It returns `None` if the key is not under [`SCHEMA_VERSION_KEY_PREFIX`] or the value is invalid.
+pub fn decode_schema_version(key: &str, value: &[u8]) -> Option<(String, u64)> {
+    let prefix = key.strip_prefix(SCHEMA_VERSION_KEY_PREFIX)?;
+    let ver = std::str::from_utf8(value).ok()?.parse::<u64>().ok()?;
+    Some((prefix.to_string(), ver))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_schema_version() {
+        let key = schema_version_key("tbl/");
+        assert_eq!(
+            Some(("tbl/".to_string(), 2)),
+            decode_schema_version(&key, b"2")
+        );
+        assert_eq!(None, decode_schema_version(&key, b"x"));
+        assert_eq!(None, decode_schema_version("tbl/", b"2"));
+    }
+}
@@ -492,6 +492,8 @@ pub enum JsonOperator {
     QuestionOr,
     /// ?& Checks whether all of the text keys exist as top-level keys or array elements.
     QuestionAnd,
+    /// @> Checks whether the left JSON value contains the right JSON value.
+    AtArrow,
 }
 
 impl JsonOperator {
@@ -504,6 +506,7 @@ impl JsonOperator {
             JsonOperator::Question => "json_exists_key".to_string(),
             JsonOperator::QuestionOr => "json_exists_any_keys".to_string(),
             JsonOperator::QuestionAnd => "json_exists_all_keys".to_string(),
+            JsonOperator::AtArrow => "json_contains_in_left".to_string(),
         }
     }
 }
@@ -768,6 +771,9 @@ impl Display for JsonOperator {
             JsonOperator::QuestionAnd => {
                 write!(f, "?&")
             }
+            JsonOperator::AtArrow => {
+                write!(f, "@>")
+            }
         }
     }
 }
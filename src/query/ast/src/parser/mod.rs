@@ -31,4 +31,5 @@ pub use parser::parse_expr;
 pub use parser::parse_sql;
 pub use parser::parser_values_with_placeholder;
 pub use parser::tokenize_sql;
+pub use token::all_keywords;
 pub use token::all_reserved_keywords;
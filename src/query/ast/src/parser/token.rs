@@ -292,6 +292,9 @@ pub enum TokenKind {
     /// Used as JSON operator.
     #[token("?&")]
     QuestionAnd,
+    /// Used as JSON operator.
+    #[token("@>")]
+    AtArrow,
 
     // Keywords
     //
@@ -1413,6 +1416,49 @@ impl TokenKind {
             _ => false
         }
     }
+
+    /// Returns true if the keyword names a data type, as accepted by `type_name` in the expression parser.
+    pub fn is_type_name(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::ARRAY
+                | TokenKind::BIGINT
+                | TokenKind::BINARY
+                | TokenKind::BITMAP
+                | TokenKind::BOOL
+                | TokenKind::BOOLEAN
+                | TokenKind::CHAR
+                | TokenKind::CHARACTER
+                | TokenKind::DATE
+                | TokenKind::DATETIME
+                | TokenKind::DECIMAL
+                | TokenKind::DOUBLE
+                | TokenKind::FLOAT
+                | TokenKind::FLOAT32
+                | TokenKind::FLOAT64
+                | TokenKind::INT
+                | TokenKind::INT8
+                | TokenKind::INT16
+                | TokenKind::INT32
+                | TokenKind::INT64
+                | TokenKind::INTEGER
+                | TokenKind::JSON
+                | TokenKind::MAP
+                | TokenKind::SMALLINT
+                | TokenKind::STRING
+                | TokenKind::TEXT
+                | TokenKind::TIMESTAMP
+                | TokenKind::TINYINT
+                | TokenKind::TUPLE
+                | TokenKind::UINT8
+                | TokenKind::UINT16
+                | TokenKind::UINT32
+                | TokenKind::UINT64
+                | TokenKind::VARBINARY
+                | TokenKind::VARCHAR
+                | TokenKind::VARIANT
+        )
+    }
 }
 
 pub fn all_reserved_keywords() -> Vec<String> {
@@ -1422,3 +1468,16 @@ pub fn all_reserved_keywords() -> Vec<String> {
     }
     result
 }
+
+/// All keywords, excluding literals, symbols and other non-keyword tokens.
+pub fn all_keywords() -> Vec<TokenKind> {
+    TokenKind::iter()
+        .filter(|token| {
+            token.is_keyword()
+                && !matches!(
+                    token,
+                    Error | Whitespace | Comment | CommentBlock | ColumnPosition | AtString
+                )
+        })
+        .collect()
+}
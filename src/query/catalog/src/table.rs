@@ -101,6 +101,12 @@ pub trait Table: Sync + Send {
         false
     }
 
+    /// Whether the min and max in the column statistics are exact,
+    /// i.e., they are the min and max of the values in the table, not an estimation.
+    fn has_exact_column_statistics(&self) -> bool {
+        false
+    }
+
     fn cluster_keys(&self, _ctx: Arc<dyn TableContext>) -> Vec<RemoteExpr<String>> {
         vec![]
     }
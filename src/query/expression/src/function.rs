@@ -95,6 +95,9 @@ pub enum FunctionEval {
 pub struct FunctionContext {
     pub tz: TzLUT,
     pub rounding_mode: bool,
+    /// Whether the session uses the MySQL dialect, in which a string compared with a number
+    /// is converted to a number instead of being rejected.
+    pub mysql_compat: bool,
 
     pub openai_api_chat_base_url: String,
     pub openai_api_embedding_base_url: String,
@@ -19,9 +19,15 @@ extern crate criterion;
 mod parser;
 
 use common_expression::type_check;
+use common_expression::types::DataType;
+use common_expression::types::Int32Type;
+use common_expression::types::NumberDataType;
+use common_expression::BlockEntry;
 use common_expression::DataBlock;
 use common_expression::Evaluator;
+use common_expression::FromData;
 use common_expression::FunctionContext;
+use common_expression::Value;
 use common_functions::BUILTIN_FUNCTIONS;
 use criterion::Criterion;
 
@@ -50,5 +56,28 @@ fn bench(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, bench);
+fn bench_cmp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_cmp");
+
+    let n = 1_000_000;
+    let col = Int32Type::from_data((0..n).collect::<Vec<i32>>());
+    let data_type = DataType::Number(NumberDataType::Int32);
+    let block = DataBlock::new(
+        vec![BlockEntry::new(data_type.clone(), Value::Column(col))],
+        n as usize,
+    );
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    for (name, text) in [("col_gt_const", "a > 500000"), ("col_gt_col", "a > a")] {
+        let raw_expr = parser::parse_raw_expr(text, &[("a", data_type.clone())]);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+
+        group.bench_function(format!("eval/{name}/{n}"), |b| {
+            b.iter(|| evaluator.run(&expr))
+        });
+    }
+}
+
+criterion_group!(benches, bench, bench_cmp);
 criterion_main!(benches);
@@ -48,7 +48,21 @@ pub fn register(registry: &mut FunctionRegistry) {
 
     for func_name in ALL_COMP_FUNC_NAMES {
         // Disable auto cast from strings, e.g., `1 < '1'`.
-        registry.register_additional_cast_rules(func_name, GENERAL_CAST_RULES.iter().cloned());
+        // Disable auto cast from `UInt64` to `Int64`, which overflows for values above `i64::MAX`;
+        // comparisons between them have dedicated overloads.
+        registry.register_additional_cast_rules(
+            func_name,
+            GENERAL_CAST_RULES
+                .iter()
+                .filter(|rule| {
+                    *rule
+                        != &(
+                            DataType::Number(NumberDataType::UInt64),
+                            DataType::Number(NumberDataType::Int64),
+                        )
+                })
+                .cloned(),
+        );
         registry.register_additional_cast_rules(func_name, CAST_FROM_VARIANT_RULES());
     }
 
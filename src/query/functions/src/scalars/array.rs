@@ -101,6 +101,7 @@ pub fn register(registry: &mut FunctionRegistry) {
     registry.register_aliases("slice", &["array_slice"]);
 
     register_array_aggr(registry);
+    register_arrays_overlap(registry);
 
     registry.register_0_arg_core::<EmptyArrayType, _, _>(
         "array",
@@ -651,6 +652,89 @@ pub fn register(registry: &mut FunctionRegistry) {
     );
 }
 
+fn register_arrays_overlap(registry: &mut FunctionRegistry) {
+    registry.register_2_arg::<EmptyArrayType, EmptyArrayType, BooleanType, _, _>(
+        "arrays_overlap",
+        |_, _, _| {
+            FunctionDomain::Domain(BooleanDomain {
+                has_false: true,
+                has_true: false,
+            })
+        },
+        |_, _, _| false,
+    );
+
+    // Two arrays overlap if they share at least one element.
+    //
+    // Unlike `eq` on arrays, NULL elements never match, since NULL is not equal to anything:
+    // `arrays_overlap([1, NULL], [NULL, 2])` is false.
+    // Only a NULL array as a whole makes the result NULL.
+    //
+    // The elements of one side are hashed into a set, which the other side is probed against.
+    // A constant side is hashed only once for the whole column.
+    registry.register_passthrough_nullable_2_arg::<ArrayType<GenericType<0>>, ArrayType<GenericType<0>>, BooleanType, _, _>(
+        "arrays_overlap",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| match (lhs, rhs) {
+            (ValueRef::Scalar(lhs), ValueRef::Scalar(rhs)) => {
+                Value::Scalar(element_hash_set(&lhs).overlaps(&rhs))
+            }
+            (ValueRef::Scalar(arr), ValueRef::Column(col))
+            | (ValueRef::Column(col), ValueRef::Scalar(arr)) => {
+                let set = element_hash_set(&arr);
+                Value::Column(BooleanType::column_from_iter(
+                    col.iter().map(|other| set.overlaps(&other)),
+                    &[],
+                ))
+            }
+            (ValueRef::Column(lhs), ValueRef::Column(rhs)) => {
+                Value::Column(BooleanType::column_from_iter(
+                    lhs.iter().zip(rhs.iter()).map(|(lhs, rhs)| {
+                        // Hash the shorter array and probe with the longer one.
+                        let (short, long) = if lhs.len() <= rhs.len() {
+                            (lhs, rhs)
+                        } else {
+                            (rhs, lhs)
+                        };
+                        element_hash_set(&short).overlaps(&long)
+                    }),
+                    &[],
+                ))
+            }
+        },
+    );
+}
+
+/// The 128-bit hashes of the non-NULL elements of an array.
+struct ElementHashSet(StackHashSet<u128, 16>);
+
+impl ElementHashSet {
+    /// Returns true if any non-NULL element of `arr` is in the set.
+    fn overlaps(&self, arr: &Column) -> bool {
+        !self.0.is_empty()
+            && arr
+                .iter()
+                .any(|val| val != ScalarRef::Null && self.0.contains(&hash_element(&val)))
+    }
+}
+
+fn element_hash_set(arr: &Column) -> ElementHashSet {
+    let mut set = StackHashSet::with_capacity(arr.len());
+    for val in arr.iter() {
+        if val == ScalarRef::Null {
+            continue;
+        }
+        let _ = set.set_insert(hash_element(&val));
+    }
+    ElementHashSet(set)
+}
+
+fn hash_element(val: &ScalarRef) -> u128 {
+    let mut hasher = SipHasher24::new();
+    val.hash(&mut hasher);
+    hasher.finish128().into()
+}
+
 fn register_array_aggr(registry: &mut FunctionRegistry) {
     fn eval_array_aggr(
         name: &str,
@@ -14,10 +14,17 @@
 
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::sync::Mutex;
 
+use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::arrow::bitmap::MutableBitmap;
+use common_cache::Cache;
+use common_cache::LruCache;
 use common_expression::types::boolean::BooleanDomain;
+use common_expression::types::number::Float64Type;
+use common_expression::types::number::F64;
 use common_expression::types::string::StringDomain;
 use common_expression::types::AnyType;
 use common_expression::types::ArgType;
@@ -27,14 +34,22 @@ use common_expression::types::DataType;
 use common_expression::types::DateType;
 use common_expression::types::EmptyArrayType;
 use common_expression::types::GenericType;
+use common_expression::types::Int64Type;
+use common_expression::types::NullableType;
 use common_expression::types::NumberClass;
 use common_expression::types::NumberType;
+use common_expression::types::SimpleDomain;
 use common_expression::types::StringType;
 use common_expression::types::TimestampType;
+use common_expression::types::UInt64Type;
 use common_expression::types::ValueType;
 use common_expression::types::VariantType;
 use common_expression::types::ALL_NUMBER_CLASSES;
 use common_expression::values::Value;
+use common_expression::vectorize_2_arg;
+use common_expression::vectorize_with_builder_1_arg;
+use common_expression::vectorize_with_builder_2_arg;
+use common_expression::vectorize_with_builder_3_arg;
 use common_expression::with_number_mapped_type;
 use common_expression::Column;
 use common_expression::EvalContext;
@@ -46,8 +61,12 @@ use common_expression::FunctionSignature;
 use common_expression::ScalarRef;
 use common_expression::SimpleDomainCmp;
 use common_expression::ValueRef;
+use jsonb::get_by_path_first;
+use jsonb::jsonpath::parse_json_path;
+use jsonb::jsonpath::JsonPath;
 use memchr::memchr;
 use memchr::memmem;
+use once_cell::sync::Lazy;
 use regex::bytes::Regex;
 
 use crate::scalars::decimal::register_decimal_compare_op;
@@ -63,6 +82,10 @@ pub fn register(registry: &mut FunctionRegistry) {
     register_array_cmp(registry);
     register_tuple_cmp(registry);
     register_like(registry);
+    register_ip_contains(registry);
+    register_variant_contains(registry);
+    register_approx_eq(registry);
+    register_mysql_to_float64(registry);
 }
 
 pub const ALL_COMP_FUNC_NAMES: &[&str] = &["eq", "noteq", "lt", "lte", "gt", "gte", "contains"];
@@ -159,14 +182,133 @@ macro_rules! register_simple_domain_type_cmp {
 
 fn register_string_cmp(registry: &mut FunctionRegistry) {
     register_simple_domain_type_cmp!(registry, StringType);
+
+    // Case-insensitive equality in one pass, without lowercasing both sides first.
+    // Only ASCII letters are folded: any other byte, including the bytes of
+    // non-ASCII UTF-8 characters, must match exactly.
+    registry.register_2_arg::<StringType, StringType, BooleanType, _, _>(
+        "eq_ignore_case",
+        |_, _, _| FunctionDomain::Full,
+        |lhs, rhs, _| lhs.eq_ignore_ascii_case(rhs),
+    );
+}
+
+macro_rules! register_fixed_width_type_cmp_op {
+    ($registry:ident, $T:ty, $name:expr, $domain_cmp:ident, $op:tt) => {
+        $registry.register_passthrough_nullable_2_arg::<$T, $T, BooleanType, _, _>(
+            $name,
+            |_, d1, d2| d1.$domain_cmp(d2),
+            |lhs, rhs, ctx| match (lhs, rhs) {
+                (ValueRef::Scalar(lhs), ValueRef::Scalar(rhs)) => Value::Scalar(lhs $op rhs),
+                (ValueRef::Column(lhs), ValueRef::Scalar(rhs)) => {
+                    Value::Column(compare_selected(
+                        lhs.len(),
+                        ctx.validity.as_ref(),
+                        |i| lhs[i] $op rhs,
+                        || compare_column_with_scalar(&lhs, |v| v $op rhs),
+                    ))
+                }
+                (ValueRef::Scalar(lhs), ValueRef::Column(rhs)) => {
+                    Value::Column(compare_selected(
+                        rhs.len(),
+                        ctx.validity.as_ref(),
+                        |i| lhs $op rhs[i],
+                        || compare_column_with_scalar(&rhs, |v| lhs $op v),
+                    ))
+                }
+                (ValueRef::Column(lhs), ValueRef::Column(rhs)) => {
+                    Value::Column(compare_selected(
+                        lhs.len(),
+                        ctx.validity.as_ref(),
+                        |i| lhs[i] $op rhs[i],
+                        || {
+                            let iter = lhs.iter().zip(rhs.iter()).map(|(l, r)| *l $op *r);
+                            BooleanType::column_from_iter(iter, &[])
+                        },
+                    ))
+                }
+            },
+        );
+    };
+}
+
+/// Register comparisons for the types whose column is a plain buffer of values,
+/// so that comparing a column against a constant runs over the buffer directly.
+macro_rules! register_fixed_width_type_cmp {
+    ($registry:ident, $T:ty) => {
+        register_fixed_width_type_cmp_op!($registry, $T, "eq", domain_eq, ==);
+        register_fixed_width_type_cmp_op!($registry, $T, "noteq", domain_noteq, !=);
+        register_fixed_width_type_cmp_op!($registry, $T, "gt", domain_gt, >);
+        register_fixed_width_type_cmp_op!($registry, $T, "gte", domain_gte, >=);
+        register_fixed_width_type_cmp_op!($registry, $T, "lt", domain_lt, <);
+        register_fixed_width_type_cmp_op!($registry, $T, "lte", domain_lte, <=);
+    };
+}
+
+/// Compare every value in `col` against a constant captured by `op`.
+///
+/// The results of 8 rows are packed into one byte at a time,
+/// instead of being pushed into the bitmap one row at a time.
+#[inline]
+fn compare_column_with_scalar<T: Copy>(col: &[T], op: impl Fn(T) -> bool) -> Bitmap {
+    let pack = |chunk: &[T]| {
+        chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |byte, (i, v)| byte | ((op(*v) as u8) << i))
+    };
+
+    let chunks = col.chunks_exact(8);
+    let remainder = chunks.remainder();
+
+    let mut bytes = Vec::with_capacity((col.len() + 7) / 8);
+    bytes.extend(chunks.map(pack));
+    if !remainder.is_empty() {
+        bytes.push(pack(remainder));
+    }
+
+    MutableBitmap::from_vec(bytes, col.len()).into()
+}
+
+/// Compare the `len` rows of a column, with the rows a prior predicate selected.
+///
+/// An empty input returns at once. If a selection is given, e.g., by the previous
+/// filter of `and_filters`, only the selected rows are compared by `op`, and
+/// the others are `false`. Otherwise `dense` compares all of the rows.
+#[inline]
+fn compare_selected(
+    len: usize,
+    selection: Option<&Bitmap>,
+    op: impl Fn(usize) -> bool,
+    dense: impl FnOnce() -> Bitmap,
+) -> Bitmap {
+    if len == 0 {
+        return Bitmap::new();
+    }
+
+    match selection {
+        Some(selection) if selection.unset_bits() == len => {
+            MutableBitmap::from_len_zeroed(len).into()
+        }
+        Some(selection) if selection.unset_bits() > 0 => {
+            let mut bitmap = MutableBitmap::from_len_zeroed(len);
+            for (i, selected) in selection.iter().enumerate() {
+                if selected && op(i) {
+                    bitmap.set(i, true);
+                }
+            }
+            bitmap.into()
+        }
+        _ => dense(),
+    }
 }
 
 fn register_date_cmp(registry: &mut FunctionRegistry) {
-    register_simple_domain_type_cmp!(registry, DateType);
+    register_fixed_width_type_cmp!(registry, DateType);
 }
 
 fn register_timestamp_cmp(registry: &mut FunctionRegistry) {
-    register_simple_domain_type_cmp!(registry, TimestampType);
+    register_fixed_width_type_cmp!(registry, TimestampType);
 }
 
 fn register_boolean_cmp(registry: &mut FunctionRegistry) {
@@ -236,7 +378,7 @@ fn register_number_cmp(registry: &mut FunctionRegistry) {
     for ty in ALL_NUMBER_CLASSES {
         with_number_mapped_type!(|NUM_TYPE| match ty {
             NumberClass::NUM_TYPE => {
-                register_simple_domain_type_cmp!(registry, NumberType<NUM_TYPE>);
+                register_fixed_width_type_cmp!(registry, NumberType<NUM_TYPE>);
             }
             NumberClass::Decimal128 => {
                 register_decimal_compare_op(registry)
@@ -245,6 +387,51 @@ fn register_number_cmp(registry: &mut FunctionRegistry) {
                 // already registered in Decimal128 branch
             }
         });
+
+        // Overloads are tried in the order they are registered: the mixed ones must come
+        // after `Int64` so that smaller integers still widen to it, but before the floats.
+        if matches!(ty, NumberClass::Int64) {
+            register_signed_unsigned_cmp(registry);
+        }
+    }
+}
+
+macro_rules! register_signed_unsigned_cmp_op {
+    ($registry:ident, $name:expr, $domain_cmp:ident, $op:tt) => {
+        $registry.register_passthrough_nullable_2_arg::<UInt64Type, Int64Type, BooleanType, _, _>(
+            $name,
+            |_, d1, d2| widen_domain(d1).$domain_cmp(&widen_domain(d2)),
+            vectorize_2_arg::<UInt64Type, Int64Type, BooleanType>(|lhs, rhs, _| {
+                (lhs as i128) $op (rhs as i128)
+            }),
+        );
+        $registry.register_passthrough_nullable_2_arg::<Int64Type, UInt64Type, BooleanType, _, _>(
+            $name,
+            |_, d1, d2| widen_domain(d1).$domain_cmp(&widen_domain(d2)),
+            vectorize_2_arg::<Int64Type, UInt64Type, BooleanType>(|lhs, rhs, _| {
+                (lhs as i128) $op (rhs as i128)
+            }),
+        );
+    };
+}
+
+/// Compare `UInt64` with `Int64` without casting one to the other: a `u64` above `i64::MAX`
+/// or a negative `i64` does not fit in the other type, and `Float64` loses precision.
+///
+/// The auto cast from `UInt64` to `Int64` is removed for comparisons, see `cast_rules.rs`.
+fn register_signed_unsigned_cmp(registry: &mut FunctionRegistry) {
+    register_signed_unsigned_cmp_op!(registry, "eq", domain_eq, ==);
+    register_signed_unsigned_cmp_op!(registry, "noteq", domain_noteq, !=);
+    register_signed_unsigned_cmp_op!(registry, "gt", domain_gt, >);
+    register_signed_unsigned_cmp_op!(registry, "gte", domain_gte, >=);
+    register_signed_unsigned_cmp_op!(registry, "lt", domain_lt, <);
+    register_signed_unsigned_cmp_op!(registry, "lte", domain_lte, <=);
+}
+
+fn widen_domain<T: Copy + Into<i128>>(domain: &SimpleDomain<T>) -> SimpleDomain<i128> {
+    SimpleDomain {
+        min: domain.min.into(),
+        max: domain.max.into(),
     }
 }
 
@@ -280,6 +467,10 @@ fn register_array_cmp(registry: &mut FunctionRegistry) {
         |_, _, _| true,
     );
 
+    // Arrays are compared element-wise, arrays of different lengths are never equal.
+    //
+    // NULL elements are compared as values (two NULL elements are equal, like PostgreSQL),
+    // only a NULL array as a whole makes the result NULL.
     registry
         .register_2_arg::<ArrayType<GenericType<0>>, ArrayType<GenericType<0>>, BooleanType, _, _>(
             "eq",
@@ -329,13 +520,16 @@ fn register_tuple_cmp(registry: &mut FunctionRegistry) {
     ) {
         let name_cloned = name.to_string();
         registry.register_function_factory(name, move |_, args_type| {
-            let fields_generics = match args_type {
-                [DataType::Tuple(lhs_fields_ty), _] => (0..lhs_fields_ty.len())
+            let [lhs_ty, rhs_ty] = args_type else {
+                return None;
+            };
+            let fields_generics = match lhs_ty.remove_nullable() {
+                DataType::Tuple(lhs_fields_ty) => (0..lhs_fields_ty.len())
                     .map(DataType::Generic)
                     .collect::<Vec<_>>(),
                 _ => return None,
             };
-            Some(Arc::new(Function {
+            let f = Function {
                 signature: FunctionSignature {
                     name: name_cloned.clone(),
                     args_type: vec![
@@ -398,7 +592,15 @@ fn register_tuple_cmp(registry: &mut FunctionRegistry) {
                         }
                     }),
                 },
-            }))
+            };
+
+            // A NULL tuple as a whole makes the result NULL,
+            // while NULL fields are compared as values, the same as the elements of arrays.
+            if lhs_ty.is_nullable_or_null() || rhs_ty.is_nullable_or_null() {
+                Some(Arc::new(f.passthrough_nullable()))
+            } else {
+                Some(Arc::new(f))
+            }
         });
     }
 
@@ -438,6 +640,340 @@ fn register_tuple_cmp(registry: &mut FunctionRegistry) {
     });
 }
 
+fn register_ip_contains(registry: &mut FunctionRegistry) {
+    // `ip_contains(network, address)` returns whether the address is in the network,
+    // e.g. `ip_contains('10.0.0.0/8', '10.1.2.3')`.
+    //
+    // A malformed network or address results in NULL,
+    // and an IPv4 address is never in an IPv6 network, or vice versa.
+    registry.register_combine_nullable_2_arg::<StringType, StringType, BooleanType, _, _>(
+        "ip_contains",
+        |_, _, _| FunctionDomain::Full,
+        |network, addr, ctx| match network {
+            ValueRef::Scalar(network) => {
+                // Parse a constant network once, instead of once for every row.
+                let network = IpNetwork::parse(network);
+                vectorize_with_builder_1_arg::<StringType, NullableType<BooleanType>>(
+                    |addr, output, _| match (&network, parse_ip_addr(addr)) {
+                        (Some(network), Some(addr)) => output.push(network.contains(addr)),
+                        _ => output.push_null(),
+                    },
+                )(addr, ctx)
+            }
+            ValueRef::Column(_) => vectorize_with_builder_2_arg::<
+                StringType,
+                StringType,
+                NullableType<BooleanType>,
+            >(|network, addr, output, _| {
+                match (IpNetwork::parse(network), parse_ip_addr(addr)) {
+                    (Some(network), Some(addr)) => output.push(network.contains(addr)),
+                    _ => output.push_null(),
+                }
+            })(network, addr, ctx),
+        },
+    );
+}
+
+fn parse_ip_addr(s: &[u8]) -> Option<IpAddr> {
+    std::str::from_utf8(s).ok()?.trim().parse().ok()
+}
+
+/// An IPv4 or IPv6 network in CIDR notation, such as `192.168.0.0/16` or `2001:db8::/32`.
+///
+/// An address without a prefix length is a network of only itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u32,
+}
+
+impl IpNetwork {
+    fn parse(s: &[u8]) -> Option<Self> {
+        let s = std::str::from_utf8(s).ok()?.trim();
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+
+        let addr: IpAddr = addr.parse().ok()?;
+        let max_len = Self::addr_bits(&addr);
+        let prefix_len = match prefix_len {
+            Some(len) => len.parse::<u32>().ok().filter(|len| *len <= max_len)?,
+            None => max_len,
+        };
+
+        Some(Self { addr, prefix_len })
+    }
+
+    fn addr_bits(addr: &IpAddr) -> u32 {
+        match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    fn contains(&self, addr: IpAddr) -> bool {
+        let (network, addr) = match (self.addr, addr) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                (u32::from(network) as u128, u32::from(addr) as u128)
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => (u128::from(network), u128::from(addr)),
+            _ => return false,
+        };
+
+        if self.prefix_len == 0 {
+            return true;
+        }
+
+        // Both are aligned to the lowest bits, so only the `prefix_len` bits above the host bits are compared.
+        let host_bits = Self::addr_bits(&self.addr) - self.prefix_len;
+        (network ^ addr) >> host_bits == 0
+    }
+}
+
+fn register_variant_contains(registry: &mut FunctionRegistry) {
+    // `json_contains_in_left(left, right)`, i.e. `left @> right`, returns whether `right` is contained in `left`.
+    registry.register_passthrough_nullable_2_arg::<VariantType, VariantType, BooleanType, _, _>(
+        "json_contains_in_left",
+        |_, _, _| FunctionDomain::Full,
+        |left, right, ctx| match right {
+            ValueRef::Scalar(right) => {
+                // Decode a constant operand once, instead of once for every row.
+                let right = jsonb::from_slice(right).ok();
+                vectorize_with_builder_1_arg::<VariantType, BooleanType>(|left, output, _| {
+                    let contains = match (jsonb::from_slice(left), &right) {
+                        (Ok(left), Some(right)) => json_contains(&left, right),
+                        _ => false,
+                    };
+                    output.push(contains);
+                })(left, ctx)
+            }
+            ValueRef::Column(_) => {
+                vectorize_with_builder_2_arg::<VariantType, VariantType, BooleanType>(
+                    |left, right, output, _| {
+                        let contains = match (jsonb::from_slice(left), jsonb::from_slice(right)) {
+                            (Ok(left), Ok(right)) => json_contains(&left, &right),
+                            _ => false,
+                        };
+                        output.push(contains);
+                    },
+                )(left, right, ctx)
+            }
+        },
+    );
+
+    // `json_path_eq(json, path, value)` returns whether the first value selected by the JSON path equals `value`,
+    // e.g. `json_path_eq(parse_json('{"a":1}'), '$.a', 1::variant)`.
+    //
+    // A path that selects nothing results in NULL,
+    // and a selected value of a different type than `value` is not equal to it.
+    registry
+        .register_combine_nullable_3_arg::<VariantType, StringType, VariantType, BooleanType, _, _>(
+            "json_path_eq",
+            |_, _, _, _| FunctionDomain::MayThrow,
+            |val, path, operand, ctx| match path {
+                ValueRef::Scalar(path) => match parse_json_path(path) {
+                    // Parse a constant path once, instead of once for every row.
+                    Ok(json_path) => vectorize_with_builder_2_arg::<
+                        VariantType,
+                        VariantType,
+                        NullableType<BooleanType>,
+                    >(|val, operand, output, ctx| {
+                        if let Some(validity) = &ctx.validity {
+                            if !validity.get_bit(output.len()) {
+                                output.push_null();
+                                return;
+                            }
+                        }
+                        match json_path_eq(val, json_path.clone(), operand) {
+                            Some(eq) => output.push(eq),
+                            None => output.push_null(),
+                        }
+                    })(val, operand, ctx),
+                    Err(_) => {
+                        ctx.set_error(
+                            0,
+                            format!("Invalid JSON Path '{}'", &String::from_utf8_lossy(path)),
+                        );
+                        Value::Scalar(None)
+                    }
+                },
+                ValueRef::Column(_) => vectorize_with_builder_3_arg::<
+                    VariantType,
+                    StringType,
+                    VariantType,
+                    NullableType<BooleanType>,
+                >(|val, path, operand, output, ctx| {
+                    if let Some(validity) = &ctx.validity {
+                        if !validity.get_bit(output.len()) {
+                            output.push_null();
+                            return;
+                        }
+                    }
+                    match parse_json_path(path) {
+                        Ok(json_path) => match json_path_eq(val, json_path, operand) {
+                            Some(eq) => output.push(eq),
+                            None => output.push_null(),
+                        },
+                        Err(_) => {
+                            ctx.set_error(
+                                output.len(),
+                                format!("Invalid JSON Path '{}'", &String::from_utf8_lossy(path)),
+                            );
+                            output.push_null();
+                        }
+                    }
+                })(val, path, operand, ctx),
+            },
+        );
+}
+
+/// Returns whether `right` is contained in `left`, in the same way as `@>` of PostgreSQL:
+///
+/// - An object contains another object if it has every key of it, with a value that contains the other value.
+/// - An array contains another array if every element of the other array is contained in one of its elements.
+/// - An array contains a scalar if one of its elements is equal to the scalar.
+/// - A scalar contains an equal scalar.
+///
+/// Values of different types never contain each other.
+fn json_contains(left: &jsonb::Value, right: &jsonb::Value) -> bool {
+    use jsonb::Value as JsonbValue;
+
+    match (left, right) {
+        (JsonbValue::Object(left), JsonbValue::Object(right)) => right
+            .iter()
+            .all(|(k, rv)| left.get(k).map(|lv| json_contains(lv, rv)).unwrap_or(false)),
+        (JsonbValue::Array(left), JsonbValue::Array(right)) => right
+            .iter()
+            .all(|rv| left.iter().any(|lv| json_contains(lv, rv))),
+        (JsonbValue::Array(_), JsonbValue::Object(_)) => false,
+        (JsonbValue::Array(left), right) => left.iter().any(|lv| json_scalar_eq(lv, right)),
+        (JsonbValue::Object(_), _) | (_, JsonbValue::Object(_)) | (_, JsonbValue::Array(_)) => {
+            false
+        }
+        (left, right) => json_scalar_eq(left, right),
+    }
+}
+
+/// Compare two JSON values in the same way as the `eq` of variants.
+fn json_scalar_eq(left: &jsonb::Value, right: &jsonb::Value) -> bool {
+    jsonb::compare(&left.to_vec(), &right.to_vec())
+        .map(|ord| ord == Ordering::Equal)
+        .unwrap_or(false)
+}
+
+/// Returns whether the first value selected by `json_path` in `value` equals `operand`,
+/// or None if the path selects nothing.
+fn json_path_eq(value: &[u8], json_path: JsonPath, operand: &[u8]) -> Option<bool> {
+    let mut data = Vec::new();
+    let mut offsets = Vec::new();
+    get_by_path_first(value, json_path, &mut data, &mut offsets);
+    if offsets.is_empty() {
+        return None;
+    }
+
+    let eq = jsonb::compare(&data, operand)
+        .map(|ord| ord == Ordering::Equal)
+        .unwrap_or(false);
+    Some(eq)
+}
+
+fn register_approx_eq(registry: &mut FunctionRegistry) {
+    // `approx_eq(a, b, abs_eps)` returns whether `|a - b| <= abs_eps`,
+    // e.g. `approx_eq(0.1 + 0.2, 0.3, 1e-9)`.
+    registry.register_3_arg::<Float64Type, Float64Type, Float64Type, BooleanType, _, _>(
+        "approx_eq",
+        |_, _, _, _| FunctionDomain::Full,
+        |a, b, abs_eps, _| approx_eq(a, b, abs_eps, F64::from(0.0)),
+    );
+
+    // `approx_eq(a, b, abs_eps, rel_eps)` also accepts a difference
+    // up to `rel_eps` times the greater magnitude of `a` and `b`.
+    registry
+        .register_4_arg::<Float64Type, Float64Type, Float64Type, Float64Type, BooleanType, _, _>(
+            "approx_eq",
+            |_, _, _, _, _| FunctionDomain::Full,
+            |a, b, abs_eps, rel_eps, _| approx_eq(a, b, abs_eps, rel_eps),
+        );
+}
+
+/// Returns whether `a` and `b` differ by at most `max(abs_eps, rel_eps * max(|a|, |b|))`.
+///
+/// NaN is never approximately equal to anything, including itself,
+/// and an infinity is only approximately equal to the same infinity.
+fn approx_eq(a: F64, b: F64, abs_eps: F64, rel_eps: F64) -> bool {
+    let (a, b) = (a.0, b.0);
+    if a.is_nan() || b.is_nan() {
+        return false;
+    }
+    if a.is_infinite() || b.is_infinite() {
+        return a == b;
+    }
+
+    let eps = abs_eps.0.max(rel_eps.0 * a.abs().max(b.abs()));
+    (a - b).abs() <= eps
+}
+
+fn register_mysql_to_float64(registry: &mut FunctionRegistry) {
+    // The string operand of a comparison with a number is wrapped in `mysql_to_float64`
+    // in the MySQL dialect, so that `'10' = 10` is true, as it is in MySQL.
+    registry.register_1_arg::<StringType, Float64Type, _, _>(
+        "mysql_to_float64",
+        |_| FunctionDomain::Full,
+        |val, _| F64::from(mysql_str_to_f64(val)),
+    );
+}
+
+/// Converts a string to a number the way MySQL does when comparing a string with a number.
+///
+/// Leading whitespace is skipped and the longest numeric prefix is parsed,
+/// e.g. `' 12abc'` is `12` and `'1.5e2x'` is `150`.
+/// A string without a numeric prefix, such as `'abc'` or `''`, is `0` instead of an error.
+fn mysql_str_to_f64(s: &[u8]) -> f64 {
+    let start = s
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(s.len());
+    let s = &s[start..];
+
+    let digits = |from: usize| s[from..].iter().take_while(|c| c.is_ascii_digit()).count();
+
+    let mut end = 0;
+    if matches!(s.first(), Some(b'+' | b'-')) {
+        end += 1;
+    }
+    let int_digits = digits(end);
+    end += int_digits;
+
+    let mut frac_digits = 0;
+    if s.get(end) == Some(&b'.') {
+        frac_digits = digits(end + 1);
+        if int_digits > 0 || frac_digits > 0 {
+            end += 1 + frac_digits;
+        }
+    }
+    if int_digits == 0 && frac_digits == 0 {
+        return 0.0;
+    }
+
+    if matches!(s.get(end), Some(b'e' | b'E')) {
+        let mut exp_end = end + 1;
+        if matches!(s.get(exp_end), Some(b'+' | b'-')) {
+            exp_end += 1;
+        }
+        let exp_digits = digits(exp_end);
+        if exp_digits > 0 {
+            end = exp_end + exp_digits;
+        }
+    }
+
+    // safe unwrap(): the prefix only contains ASCII sign, digits, '.' and exponent.
+    std::str::from_utf8(&s[..end])
+        .unwrap()
+        .parse::<f64>()
+        .unwrap_or(0.0)
+}
+
 fn register_like(registry: &mut FunctionRegistry) {
     registry.register_aliases("regexp", &["rlike"]);
 
@@ -569,7 +1105,7 @@ fn register_like(registry: &mut FunctionRegistry) {
                 builder.push(re.is_match(str));
             } else {
                 // TODO error
-                match regexp::build_regexp_from_pattern("regexp", pat, None) {
+                match cached_regexp("regexp", pat) {
                     Ok(re) => {
                         builder.push(re.is_match(str));
                         map.insert(pat.to_vec(), re);
@@ -682,6 +1218,42 @@ fn variant_vectorize_like(
     }
 }
 
+/// The maximum number of compiled patterns kept in [`REGEXP_CACHE`].
+const REGEXP_CACHE_CAPACITY: u64 = 1024;
+
+type RegexpCache = Mutex<LruCache<(&'static str, Vec<u8>), Regex>>;
+
+/// Compiled regexp patterns keyed by (function name, pattern), shared by all queries.
+///
+/// Workloads tend to repeat the same constant pattern. The map in `vectorize_regexp`
+/// only lives for one block, so without this cache the pattern is compiled again for
+/// every block of every query.
+static REGEXP_CACHE: Lazy<RegexpCache> =
+    Lazy::new(|| Mutex::new(LruCache::new(REGEXP_CACHE_CAPACITY)));
+
+fn cached_regexp(fn_name: &'static str, pat: &[u8]) -> Result<Regex, String> {
+    get_or_build_regexp(&REGEXP_CACHE, fn_name, pat, || {
+        regexp::build_regexp_from_pattern(fn_name, pat, None)
+    })
+}
+
+fn get_or_build_regexp(
+    cache: &RegexpCache,
+    fn_name: &'static str,
+    pat: &[u8],
+    build: impl FnOnce() -> Result<Regex, String>,
+) -> Result<Regex, String> {
+    let key = (fn_name, pat.to_vec());
+    if let Some(re) = cache.lock().unwrap().get(&key) {
+        return Ok(re.clone());
+    }
+
+    // Build outside of the lock, a slow pattern should not block other queries.
+    let re = build()?;
+    cache.lock().unwrap().put(key, re.clone());
+    Ok(re)
+}
+
 fn vectorize_regexp(
     func: impl Fn(
         &[u8],
@@ -1047,3 +1619,197 @@ fn test_check_pattern_type() {
         assert_eq!(pattern_type, check_pattern_type(pattern.as_bytes(), false));
     }
 }
+
+#[test]
+fn test_regexp_cache_builds_pattern_once() {
+    let cache: RegexpCache = Mutex::new(LruCache::new(2));
+    let builds = std::cell::Cell::new(0);
+    let build = |pat: &[u8]| {
+        builds.set(builds.get() + 1);
+        regexp::build_regexp_from_pattern("regexp", pat, None)
+    };
+
+    for _ in 0..2 {
+        let re = get_or_build_regexp(&cache, "regexp", b"^data.*", || build(b"^data.*")).unwrap();
+        assert!(re.is_match(b"databend"));
+    }
+    assert_eq!(builds.get(), 1);
+
+    // The same pattern used by another function is a different entry.
+    get_or_build_regexp(&cache, "rlike", b"^data.*", || build(b"^data.*")).unwrap();
+    assert_eq!(builds.get(), 2);
+
+    // Invalid patterns are not cached.
+    for _ in 0..2 {
+        assert!(get_or_build_regexp(&cache, "regexp", b"(", || build(b"(")).is_err());
+    }
+    assert_eq!(builds.get(), 4);
+}
+
+#[test]
+fn test_compare_column_with_scalar() {
+    let ops: [(&str, fn(i32, i32) -> bool); 6] = [
+        ("eq", |l, r| l == r),
+        ("noteq", |l, r| l != r),
+        ("gt", |l, r| l > r),
+        ("gte", |l, r| l >= r),
+        ("lt", |l, r| l < r),
+        ("lte", |l, r| l <= r),
+    ];
+
+    // Lengths around the 8-rows chunk boundary.
+    for len in [0, 1, 7, 8, 9, 16, 17, 100] {
+        let col = (0..len).map(|i| (i * 7 % 13) - 6).collect::<Vec<i32>>();
+
+        for (name, op) in ops {
+            for scalar in [-7, 0, 3, 7] {
+                let got = compare_column_with_scalar(&col, |v| op(v, scalar));
+                let want = col.iter().map(|v| op(*v, scalar)).collect::<Bitmap>();
+                assert_eq!(got, want, "{} {} with len {}", name, scalar, len);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_compare_float_total_order() {
+    use common_expression::types::number::F64;
+
+    let nan = F64::from(f64::NAN);
+    let inf = F64::from(f64::INFINITY);
+    let one = F64::from(1.0);
+    let zero = F64::from(0.0);
+    let neg_zero = F64::from(-0.0);
+
+    // NaN equals itself and is greater than any other value, including infinity.
+    assert!(nan == nan);
+    assert!(nan > inf);
+    assert!(nan > one);
+    assert!(one < nan);
+
+    // Negative zero equals positive zero.
+    assert!(neg_zero == zero);
+    assert!(neg_zero >= zero);
+    assert!(!(neg_zero < zero));
+
+    let col = vec![nan, inf, one, zero, neg_zero, F64::from(-1.0)];
+    let got = compare_column_with_scalar(&col, |v| v > one);
+    assert_eq!(
+        got,
+        [true, true, false, false, false, false]
+            .into_iter()
+            .collect::<Bitmap>()
+    );
+
+    let got = compare_column_with_scalar(&col, |v| v == zero);
+    assert_eq!(
+        got,
+        [false, false, false, true, true, false]
+            .into_iter()
+            .collect::<Bitmap>()
+    );
+
+    let got = compare_column_with_scalar(&col, |v| v == nan);
+    assert_eq!(
+        got,
+        [true, false, false, false, false, false]
+            .into_iter()
+            .collect::<Bitmap>()
+    );
+}
+
+#[test]
+fn test_compare_selected() {
+    let col = [3, 1, 4, 1, 5, 9, 2, 6];
+
+    // Empty input is returned at once, without comparing any row.
+    let got = compare_selected(0, None, |_| unreachable!(), || unreachable!());
+    assert!(got.is_empty());
+
+    let empty = Bitmap::new();
+    let got = compare_selected(0, Some(&empty), |_| unreachable!(), || unreachable!());
+    assert!(got.is_empty());
+
+    // Nothing is selected by the prior predicate.
+    let none: Bitmap = MutableBitmap::from_len_zeroed(col.len()).into();
+    let got = compare_selected(
+        col.len(),
+        Some(&none),
+        |_| unreachable!(),
+        || unreachable!(),
+    );
+    assert_eq!(got, none);
+
+    // Only the selected rows are compared, the others are false.
+    let selection = [true, false, true, false, true, false, true, false]
+        .into_iter()
+        .collect::<Bitmap>();
+    let compared = std::cell::RefCell::new(vec![]);
+    let got = compare_selected(
+        col.len(),
+        Some(&selection),
+        |i| {
+            compared.borrow_mut().push(i);
+            col[i] > 2
+        },
+        || unreachable!(),
+    );
+    assert_eq!(compared.into_inner(), vec![0, 2, 4, 6]);
+    assert_eq!(
+        got,
+        [true, false, true, false, true, false, false, false]
+            .into_iter()
+            .collect::<Bitmap>()
+    );
+
+    // All rows selected, or no selection: compare all of them at once.
+    let all = [true; 8].into_iter().collect::<Bitmap>();
+    for selection in [None, Some(&all)] {
+        let got = compare_selected(
+            col.len(),
+            selection,
+            |_| unreachable!(),
+            || compare_column_with_scalar(&col[..], |v| v > 2),
+        );
+        assert_eq!(got, col.iter().map(|v| *v > 2).collect::<Bitmap>());
+    }
+}
+
+#[test]
+fn test_ip_network_contains() {
+    let contains = |network: &str, addr: &str| {
+        let network = IpNetwork::parse(network.as_bytes())?;
+        let addr = parse_ip_addr(addr.as_bytes())?;
+        Some(network.contains(addr))
+    };
+
+    // IPv4
+    assert_eq!(Some(true), contains("10.0.0.0/8", "10.1.2.3"));
+    assert_eq!(Some(false), contains("10.0.0.0/8", "11.0.0.1"));
+    assert_eq!(Some(true), contains("192.168.1.0/24", "192.168.1.255"));
+    assert_eq!(Some(false), contains("192.168.1.0/24", "192.168.2.0"));
+    assert_eq!(Some(true), contains("0.0.0.0/0", "8.8.8.8"));
+    assert_eq!(Some(true), contains("8.8.8.8", "8.8.8.8"));
+    assert_eq!(Some(false), contains("8.8.8.8/32", "8.8.8.9"));
+    // Host bits in the network address are ignored.
+    assert_eq!(Some(true), contains("10.1.2.3/8", "10.200.0.1"));
+
+    // IPv6
+    assert_eq!(Some(true), contains("2001:db8::/32", "2001:db8::1"));
+    assert_eq!(Some(true), contains("2001:db8::/32", "2001:db8:ffff::1"));
+    assert_eq!(Some(false), contains("2001:db8::/32", "2001:db9::1"));
+    assert_eq!(Some(true), contains("::/0", "fe80::1"));
+    assert_eq!(Some(true), contains("::1/128", "::1"));
+
+    // Mixing IPv4 and IPv6 never matches.
+    assert_eq!(Some(false), contains("0.0.0.0/0", "::1"));
+    assert_eq!(Some(false), contains("::/0", "127.0.0.1"));
+
+    // Malformed input
+    assert_eq!(None, contains("10.0.0.0/33", "10.0.0.1"));
+    assert_eq!(None, contains("2001:db8::/129", "2001:db8::1"));
+    assert_eq!(None, contains("10.0.0.0/x", "10.0.0.1"));
+    assert_eq!(None, contains("10.0.0/8", "10.0.0.1"));
+    assert_eq!(None, contains("10.0.0.0/8", "10.0.0.256"));
+    assert_eq!(None, contains("10.0.0.0/8", ""));
+}
@@ -22,6 +22,7 @@ use common_expression::types::number::SimpleDomain;
 use common_expression::types::number::UInt64Type;
 use common_expression::types::string::StringColumn;
 use common_expression::types::string::StringColumnBuilder;
+use common_expression::types::string::StringDomain;
 use common_expression::types::ArrayType;
 use common_expression::types::NumberType;
 use common_expression::types::StringType;
@@ -70,7 +71,7 @@ pub fn register(registry: &mut FunctionRegistry) {
 
     registry.register_passthrough_nullable_1_arg::<StringType, StringType, _, _>(
         "lower",
-        |_, _| FunctionDomain::Full,
+        |_, domain| lower_domain(domain),
         vectorize_string_to_string(
             |col| col.data().len(),
             |val, output, _| {
@@ -955,6 +956,50 @@ fn substr_utf8(builder: &mut StringColumnBuilder, str: &str, pos: i64, len: u64)
     builder.commit_row();
 }
 
+/// The domain of `lower(x)`, which keeps a case-insensitive comparison of `x` prunable.
+///
+/// Every string in the domain starts with the common prefix of `min` and `max`,
+/// thus the lowercase of it starts with the lowercase of the prefix,
+/// and is in `[lower(prefix), lower(prefix) with the last byte incremented]`.
+fn lower_domain(domain: &StringDomain) -> FunctionDomain<StringType> {
+    let max = match &domain.max {
+        Some(max) => max,
+        None => return FunctionDomain::Full,
+    };
+
+    let common_len = domain
+        .min
+        .iter()
+        .zip(max.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let prefix = &domain.min[..common_len];
+    // Only the complete chars are lowercased in the same way as in every string.
+    let prefix = match std::str::from_utf8(prefix) {
+        Ok(prefix) => prefix,
+        Err(e) => std::str::from_utf8(&prefix[..e.valid_up_to()]).unwrap(),
+    };
+
+    // Lowercase char by char, as `lower` does, e.g., a final sigma is not special.
+    let min = prefix
+        .chars()
+        .flat_map(|c| c.to_lowercase())
+        .collect::<String>()
+        .into_bytes();
+
+    // A byte of UTF-8 is never 0xFF, the last byte can be incremented.
+    let mut max = min.clone();
+    match max.last_mut() {
+        Some(last) => *last += 1,
+        None => return FunctionDomain::Full,
+    }
+
+    FunctionDomain::Domain(StringDomain {
+        min,
+        max: Some(max),
+    })
+}
+
 /// String to String scalar function with estimated output column capacity.
 pub fn vectorize_string_to_string(
     estimate_bytes: impl Fn(&StringColumn) -> usize + Copy,
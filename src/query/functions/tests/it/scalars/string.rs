@@ -84,6 +84,10 @@ fn test_lower(file: &mut impl Write) {
         "a",
         StringType::from_data(&["Abc", "DOBRÝ DEN", "İ😀山"]),
     )]);
+    run_ast(file, "lower(a)", &[(
+        "a",
+        StringType::from_data(&["ABc", "ABd"]),
+    )]);
 }
 
 fn test_bit_length(file: &mut impl Write) {
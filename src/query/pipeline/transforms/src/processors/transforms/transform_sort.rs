@@ -37,8 +37,8 @@ pub fn build_full_sort_pipeline(
     after_exchange: bool,
 ) -> Result<()> {
     // Partial sort
-    if limit.is_none() || !after_exchange {
-        // If the sort plan is after an exchange plan, the blocks are already partially sorted on other nodes.
+    if !after_exchange {
+        // If the sort plan is after an exchange plan, the blocks are already sorted on other nodes.
         pipeline.add_transform(|input, output| {
             let transform =
                 TransformSortPartial::try_create(input, output, limit, sort_desc.clone())?;
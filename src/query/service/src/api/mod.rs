@@ -26,6 +26,7 @@ pub use rpc::DatabendQueryFlightService;
 pub use rpc::DefaultExchangeInjector;
 pub use rpc::ExchangeDeserializeMeta;
 pub use rpc::ExchangeInjector;
+pub use rpc::ExchangeKind;
 pub use rpc::ExchangeSerializeMeta;
 pub use rpc::ExchangeShuffleMeta;
 pub use rpc::ExchangeSorting;
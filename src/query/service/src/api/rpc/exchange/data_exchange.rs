@@ -14,6 +14,19 @@
 
 use common_expression::RemoteExpr;
 
+/// How the output of a fragment is sent to the fragment consuming it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeKind {
+    /// The output stays on the node, e.g. the root fragment.
+    None,
+    /// The output of all nodes is gathered to one node.
+    Merge,
+    /// The output of every node is sent to all destinations.
+    Broadcast,
+    /// The output is partitioned by keys among the destinations.
+    Shuffle,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum DataExchange {
     Merge(MergeExchange),
@@ -30,6 +43,14 @@ impl DataExchange {
         }
     }
 
+    pub fn kind(&self) -> ExchangeKind {
+        match self {
+            DataExchange::Merge(_) => ExchangeKind::Merge,
+            DataExchange::Broadcast(_) => ExchangeKind::Broadcast,
+            DataExchange::ShuffleDataExchange(_) => ExchangeKind::Shuffle,
+        }
+    }
+
     pub fn from_multiple_nodes(&self) -> bool {
         match self {
             DataExchange::Merge(_) => true,
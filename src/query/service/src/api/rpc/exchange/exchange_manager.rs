@@ -667,7 +667,8 @@ impl QueryCoordinator {
 
         let info = self.info.as_ref().expect("Query info is None");
 
-        let max_threads = info.query_ctx.get_settings().get_max_threads()?;
+        // The settings of this executor: the changes made by the query on top of its own defaults.
+        let max_threads = info.query_ctx.get_settings().get_max_threads()? as usize;
         let mut pipelines = Vec::with_capacity(self.fragments_coordinator.len());
 
         let mut params = Vec::with_capacity(self.fragments_coordinator.len());
@@ -686,7 +687,10 @@ impl QueryCoordinator {
 
         for ((_, coordinator), params) in self.fragments_coordinator.iter_mut().zip(params) {
             if let Some(mut build_res) = coordinator.pipeline_build_res.take() {
-                build_res.set_max_threads(max_threads as usize);
+                build_res.set_max_threads(FragmentPlanPacket::executor_threads(
+                    max_threads,
+                    coordinator.parallelism,
+                ));
 
                 if !build_res.main_pipeline.is_pulling_pipeline()? {
                     return Err(ErrorCode::Internal("Logical error, It's a bug"));
@@ -752,6 +756,7 @@ struct FragmentCoordinator {
     fragment_id: usize,
     physical_plan: PhysicalPlan,
     data_exchange: Option<DataExchange>,
+    parallelism: usize,
     pipeline_build_res: Option<PipelineBuildResult>,
 }
 
@@ -762,6 +767,7 @@ impl FragmentCoordinator {
             physical_plan: packet.physical_plan.clone(),
             fragment_id: packet.fragment_id,
             data_exchange: packet.data_exchange.clone(),
+            parallelism: packet.parallelism,
             pipeline_build_res: None,
         })
     }
@@ -30,6 +30,7 @@ mod statistics_sender;
 
 pub use data_exchange::BroadcastExchange;
 pub use data_exchange::DataExchange;
+pub use data_exchange::ExchangeKind;
 pub use data_exchange::MergeExchange;
 pub use data_exchange::ShuffleDataExchange;
 pub use exchange_injector::DefaultExchangeInjector;
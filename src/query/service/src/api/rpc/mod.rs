@@ -33,6 +33,7 @@ pub use exchange::DataExchangeManager;
 pub use exchange::DefaultExchangeInjector;
 pub use exchange::ExchangeDeserializeMeta;
 pub use exchange::ExchangeInjector;
+pub use exchange::ExchangeKind;
 pub use exchange::ExchangeSerializeMeta;
 pub use exchange::ExchangeShuffleMeta;
 pub use exchange::ExchangeSorting;
@@ -23,6 +23,10 @@ pub struct FragmentPlanPacket {
     pub physical_plan: PhysicalPlan,
     pub fragment_id: usize,
     pub data_exchange: Option<DataExchange>,
+    /// The cpu cores of the executor known by the coordinator, 0 if unknown.
+    ///
+    /// The executor runs this fragment with its own `max_threads`, but no more than this.
+    pub parallelism: usize,
 }
 
 impl FragmentPlanPacket {
@@ -30,11 +34,21 @@ impl FragmentPlanPacket {
         fragment_id: usize,
         physical_plan: PhysicalPlan,
         data_exchange: Option<DataExchange>,
+        parallelism: usize,
     ) -> FragmentPlanPacket {
         FragmentPlanPacket {
             physical_plan,
             fragment_id,
             data_exchange,
+            parallelism,
+        }
+    }
+
+    /// The number of threads to run a fragment on an executor with `max_threads`.
+    pub fn executor_threads(max_threads: usize, parallelism: usize) -> usize {
+        match parallelism {
+            0 => max_threads,
+            parallelism => std::cmp::min(max_threads, parallelism),
         }
     }
 }
@@ -45,6 +59,7 @@ impl Debug for FragmentPlanPacket {
             .field("physical_plan", &self.physical_plan)
             .field("fragment_id", &self.fragment_id)
             .field("exchange", &self.data_exchange)
+            .field("parallelism", &self.parallelism)
             .finish()
     }
 }
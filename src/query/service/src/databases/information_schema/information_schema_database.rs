@@ -12,23 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
-
 use common_meta_app::schema::DatabaseIdent;
 use common_meta_app::schema::DatabaseInfo;
 use common_meta_app::schema::DatabaseMeta;
 use common_meta_app::schema::DatabaseNameIdent;
-use common_storages_information_schema::ColumnsTable;
-use common_storages_information_schema::KeyColumnUsageTable;
-use common_storages_information_schema::KeywordsTable;
-use common_storages_information_schema::SchemataTable;
-use common_storages_information_schema::StatisticsTable;
-use common_storages_information_schema::TablesTable;
-use common_storages_information_schema::ViewsTable;
+use common_storages_information_schema::register_all;
 
 use crate::catalogs::InMemoryMetas;
 use crate::databases::Database;
-use crate::storages::Table;
 
 #[derive(Clone)]
 pub struct InformationSchemaDatabase {
@@ -37,15 +28,7 @@ pub struct InformationSchemaDatabase {
 
 impl InformationSchemaDatabase {
     pub fn create(sys_db_meta: &mut InMemoryMetas) -> Self {
-        let table_list: Vec<Arc<dyn Table>> = vec![
-            ColumnsTable::create(sys_db_meta.next_table_id()),
-            TablesTable::create(sys_db_meta.next_table_id()),
-            KeywordsTable::create(sys_db_meta.next_table_id()),
-            ViewsTable::create(sys_db_meta.next_table_id()),
-            SchemataTable::create(sys_db_meta.next_table_id()),
-            StatisticsTable::create(sys_db_meta.next_table_id()),
-            KeyColumnUsageTable::create(sys_db_meta.next_table_id()),
-        ];
+        let table_list = register_all(|| sys_db_meta.next_table_id());
 
         let db = "information_schema";
 
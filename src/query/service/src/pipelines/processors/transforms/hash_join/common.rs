@@ -20,6 +20,8 @@ use common_expression::types::nullable::NullableColumn;
 use common_expression::types::AnyType;
 use common_expression::types::BooleanType;
 use common_expression::types::DataType;
+use common_expression::types::NullableType;
+use common_expression::types::ValueType;
 use common_expression::BlockEntry;
 use common_expression::Column;
 use common_expression::DataBlock;
@@ -30,9 +32,7 @@ use common_expression::Value;
 use common_functions::BUILTIN_FUNCTIONS;
 use common_sql::executor::cast_expr_to_non_null_boolean;
 
-use super::desc::MARKER_KIND_FALSE;
-use super::desc::MARKER_KIND_NULL;
-use super::desc::MARKER_KIND_TRUE;
+use super::desc::ComparisonOutcome;
 use super::HashJoinState;
 use crate::pipelines::processors::transforms::hash_join::HashJoinProbeState;
 
@@ -60,30 +60,16 @@ impl HashJoinProbeState {
     pub(crate) fn create_marker_block(
         &self,
         has_null: bool,
-        markers: &mut [u8],
+        markers: &mut [ComparisonOutcome],
         num_rows: usize,
     ) -> Result<DataBlock> {
         let mut validity = MutableBitmap::with_capacity(num_rows);
         let mut boolean_bit_map = MutableBitmap::with_capacity(num_rows);
-        let mut row_index = 0;
-        while row_index < num_rows {
-            let marker = if markers[row_index] == MARKER_KIND_FALSE && has_null {
-                MARKER_KIND_NULL
-            } else {
-                markers[row_index]
-            };
-            if marker == MARKER_KIND_NULL {
-                validity.push(false);
-            } else {
-                validity.push(true);
-            }
-            if marker == MARKER_KIND_TRUE {
-                boolean_bit_map.push(true);
-            } else {
-                boolean_bit_map.push(false);
-            }
-            markers[row_index] = MARKER_KIND_FALSE;
-            row_index += 1;
+        for marker in markers.iter_mut().take(num_rows) {
+            let outcome = marker.resolve(has_null);
+            validity.push(outcome != ComparisonOutcome::Unknown);
+            boolean_bit_map.push(outcome == ComparisonOutcome::True);
+            *marker = ComparisonOutcome::False;
         }
         let boolean_column = Column::Boolean(boolean_bit_map.into());
         let marker_column = Column::Nullable(Box::new(NullableColumn {
@@ -136,6 +122,26 @@ impl HashJoinProbeState {
             }))),
         }
     }
+
+    /// Evaluates `filter` like [`Self::get_nullable_filter_column`], but returns the
+    /// three-valued outcome of each row instead of a nullable boolean column.
+    pub(crate) fn get_comparison_outcomes(
+        &self,
+        merged_block: &DataBlock,
+        filter: &Expr,
+        func_ctx: &FunctionContext,
+    ) -> Result<Vec<ComparisonOutcome>> {
+        let filter = self.get_nullable_filter_column(merged_block, filter, func_ctx)?;
+        let filter_viewer = NullableType::<BooleanType>::try_downcast_column(&filter).unwrap();
+        let validity = &filter_viewer.validity;
+        let data = &filter_viewer.column;
+
+        Ok((0..filter_viewer.len())
+            .map(|idx| {
+                ComparisonOutcome::from_nullable(validity.get_bit(idx).then(|| data.get_bit(idx)))
+            })
+            .collect())
+    }
 }
 
 impl HashJoinState {
@@ -143,7 +149,7 @@ impl HashJoinState {
         &self,
         cols: &[(Column, DataType)],
         num_rows: usize,
-        markers: &mut [u8],
+        markers: &mut [ComparisonOutcome],
     ) {
         if cols
             .iter()
@@ -172,7 +178,7 @@ impl HashJoinState {
                 let mut idx = 0;
                 while idx < num_rows {
                     if !v.get_bit(idx) {
-                        markers[idx] = MARKER_KIND_NULL;
+                        markers[idx] = ComparisonOutcome::Unknown;
                     }
                     idx += 1;
                 }
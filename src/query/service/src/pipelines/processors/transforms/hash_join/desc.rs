@@ -22,9 +22,48 @@ use parking_lot::RwLock;
 
 use crate::sql::plans::JoinType;
 
-pub const MARKER_KIND_TRUE: u8 = 0;
-pub const MARKER_KIND_FALSE: u8 = 1;
-pub const MARKER_KIND_NULL: u8 = 2;
+/// The three-valued result of comparing a row against the other side of a mark join.
+///
+/// A comparison involving NULL is `Unknown`, which is neither `True` nor `False`:
+/// `1 NOT IN (2, NULL)` is `Unknown`, so a filter drops the row instead of keeping it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ComparisonOutcome {
+    True,
+    False,
+    Unknown,
+}
+
+impl ComparisonOutcome {
+    /// Converts a nullable boolean, where NULL means `Unknown`.
+    pub fn from_nullable(value: Option<bool>) -> Self {
+        match value {
+            Some(true) => ComparisonOutcome::True,
+            Some(false) => ComparisonOutcome::False,
+            None => ComparisonOutcome::Unknown,
+        }
+    }
+
+    /// Folds another comparison of the same row into this one, as `OR` does:
+    /// `True` wins over everything, and `Unknown` wins over `False`.
+    pub fn merge(&mut self, other: ComparisonOutcome) {
+        match (*self, other) {
+            (ComparisonOutcome::True, _) => {}
+            (_, ComparisonOutcome::True) => *self = ComparisonOutcome::True,
+            (_, ComparisonOutcome::Unknown) => *self = ComparisonOutcome::Unknown,
+            (_, ComparisonOutcome::False) => {}
+        }
+    }
+
+    /// A row that matched nothing is `Unknown` rather than `False` if the other side contains NULL.
+    pub fn resolve(self, has_null: bool) -> Self {
+        if self == ComparisonOutcome::False && has_null {
+            ComparisonOutcome::Unknown
+        } else {
+            self
+        }
+    }
+}
 
 pub struct MarkJoinDesc {
     // pub(crate) marker_index: Option<IndexType>,
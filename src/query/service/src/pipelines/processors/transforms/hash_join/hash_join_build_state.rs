@@ -54,7 +54,7 @@ use parking_lot::Mutex;
 use parking_lot::RwLock;
 
 use crate::pipelines::processors::transforms::hash_join::common::wrap_true_validity;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_FALSE;
+use crate::pipelines::processors::transforms::hash_join::desc::ComparisonOutcome;
 use crate::pipelines::processors::transforms::hash_join::hash_join_state::FixedKeyHashJoinHashTable;
 use crate::pipelines::processors::transforms::hash_join::hash_join_state::HashJoinHashTable;
 use crate::pipelines::processors::transforms::hash_join::hash_join_state::SerializerHashJoinHashTable;
@@ -169,7 +169,7 @@ impl HashJoinBuildState {
         };
 
         let block_mark_scan_map = if self.hash_join_state.need_outer_scan() {
-            vec![MARKER_KIND_FALSE; data_block.num_rows()]
+            vec![ComparisonOutcome::False; data_block.num_rows()]
         } else {
             vec![]
         };
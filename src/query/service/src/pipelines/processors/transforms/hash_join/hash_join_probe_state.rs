@@ -47,9 +47,7 @@ use parking_lot::RwLock;
 
 use super::ProbeState;
 use crate::pipelines::processors::transforms::hash_join::common::wrap_true_validity;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_FALSE;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_NULL;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_TRUE;
+use crate::pipelines::processors::transforms::hash_join::desc::ComparisonOutcome;
 use crate::pipelines::processors::transforms::hash_join::hash_join_state::HashJoinHashTable;
 use crate::pipelines::processors::transforms::hash_join::util::probe_schema_wrap_nullable;
 use crate::pipelines::processors::HashJoinState;
@@ -199,7 +197,7 @@ impl HashJoinProbeState {
 
         if self.hash_join_state.hash_join_desc.join_type == JoinType::RightMark {
             if input.num_rows() > probe_state.markers.as_ref().unwrap().len() {
-                probe_state.markers = Some(vec![MARKER_KIND_FALSE; input.num_rows()]);
+                probe_state.markers = Some(vec![ComparisonOutcome::False; input.num_rows()]);
             }
             if self
                 .hash_join_state
@@ -620,21 +618,9 @@ impl HashJoinProbeState {
             let mut validity = MutableBitmap::with_capacity(block_size);
             let mut boolean_bit_map = MutableBitmap::with_capacity(block_size);
             while build_indexes_occupied < block_size {
-                let marker = if markers[row_index] == MARKER_KIND_FALSE && has_null {
-                    MARKER_KIND_NULL
-                } else {
-                    markers[row_index]
-                };
-                if marker == MARKER_KIND_NULL {
-                    validity.push(false);
-                } else {
-                    validity.push(true);
-                }
-                if marker == MARKER_KIND_TRUE {
-                    boolean_bit_map.push(true);
-                } else {
-                    boolean_bit_map.push(false);
-                }
+                let marker = markers[row_index].resolve(has_null);
+                validity.push(marker != ComparisonOutcome::Unknown);
+                boolean_bit_map.push(marker == ComparisonOutcome::True);
                 build_indexes[build_indexes_occupied].chunk_index = chunk_index as u32;
                 build_indexes[build_indexes_occupied].row_index = row_index as u32;
                 build_indexes_occupied += 1;
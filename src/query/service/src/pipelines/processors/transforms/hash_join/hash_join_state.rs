@@ -40,6 +40,7 @@ use common_sql::ColumnSet;
 use ethnum::U256;
 use parking_lot::RwLock;
 
+use crate::pipelines::processors::transforms::hash_join::desc::ComparisonOutcome;
 use crate::pipelines::processors::transforms::hash_join::row::RowSpace;
 use crate::pipelines::processors::transforms::hash_join::util::build_schema_wrap_nullable;
 use crate::pipelines::processors::HashJoinDesc;
@@ -111,7 +112,7 @@ pub struct HashJoinState {
     /// OuterScan map, initialized at `HashJoinBuildState`, used in `HashJoinProbeState`
     pub(crate) outer_scan_map: SyncUnsafeCell<Vec<Vec<bool>>>,
     /// LeftMarkScan map, initialized at `HashJoinBuildState`, used in `HashJoinProbeState`
-    pub(crate) mark_scan_map: SyncUnsafeCell<Vec<Vec<u8>>>,
+    pub(crate) mark_scan_map: SyncUnsafeCell<Vec<Vec<ComparisonOutcome>>>,
 
     /// Spill related states
     /// Spill partition set
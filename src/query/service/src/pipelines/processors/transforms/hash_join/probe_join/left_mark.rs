@@ -17,15 +17,10 @@ use std::sync::atomic::Ordering;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
-use common_expression::types::BooleanType;
-use common_expression::types::NullableType;
-use common_expression::types::ValueType;
 use common_expression::DataBlock;
 use common_hashtable::HashJoinHashtableLike;
 
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_FALSE;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_NULL;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_TRUE;
+use crate::pipelines::processors::transforms::hash_join::desc::ComparisonOutcome;
 use crate::pipelines::processors::transforms::hash_join::HashJoinProbeState;
 use crate::pipelines::processors::transforms::hash_join::ProbeState;
 
@@ -92,7 +87,7 @@ impl HashJoinProbeState {
             loop {
                 for probed_row in &build_index[0..matched_num] {
                     mark_scan_map[probed_row.chunk_index as usize][probed_row.row_index as usize] =
-                        MARKER_KIND_TRUE;
+                        ComparisonOutcome::True;
                 }
                 matched_num = 0;
                 if incomplete_ptr == 0 {
@@ -213,29 +208,16 @@ impl HashJoinProbeState {
                     };
                     let result_block = self.merge_eq_block(probe_block, build_block, matched_num);
 
-                    let filter = self.get_nullable_filter_column(
+                    let outcomes = self.get_comparison_outcomes(
                         &result_block,
                         other_predicate,
                         &self.func_ctx,
                     )?;
-                    let filter_viewer =
-                        NullableType::<BooleanType>::try_downcast_column(&filter).unwrap();
-                    let validity = &filter_viewer.validity;
-                    let data = &filter_viewer.column;
 
-                    for (idx, build_index) in build_indexes.iter().enumerate() {
-                        if !validity.get_bit(idx) {
-                            if mark_scan_map[build_index.chunk_index as usize]
-                                [build_index.row_index as usize]
-                                == MARKER_KIND_FALSE
-                            {
-                                mark_scan_map[build_index.chunk_index as usize]
-                                    [build_index.row_index as usize] = MARKER_KIND_NULL;
-                            }
-                        } else if data.get_bit(idx) {
-                            mark_scan_map[build_index.chunk_index as usize]
-                                [build_index.row_index as usize] = MARKER_KIND_TRUE;
-                        }
+                    for (build_index, outcome) in build_indexes.iter().zip(outcomes) {
+                        mark_scan_map[build_index.chunk_index as usize]
+                            [build_index.row_index as usize]
+                            .merge(outcome);
                     }
                     matched_num = 0;
                     if incomplete_ptr == 0 {
@@ -302,24 +284,12 @@ impl HashJoinProbeState {
         };
         let result_block = self.merge_eq_block(probe_block, build_block, matched_num);
 
-        let filter =
-            self.get_nullable_filter_column(&result_block, other_predicate, &self.func_ctx)?;
-        let filter_viewer = NullableType::<BooleanType>::try_downcast_column(&filter).unwrap();
-        let validity = &filter_viewer.validity;
-        let data = &filter_viewer.column;
+        let outcomes =
+            self.get_comparison_outcomes(&result_block, other_predicate, &self.func_ctx)?;
 
-        for (idx, build_index) in (build_indexes[0..matched_num]).iter().enumerate() {
-            if !validity.get_bit(idx) {
-                if mark_scan_map[build_index.chunk_index as usize][build_index.row_index as usize]
-                    == MARKER_KIND_FALSE
-                {
-                    mark_scan_map[build_index.chunk_index as usize]
-                        [build_index.row_index as usize] = MARKER_KIND_NULL;
-                }
-            } else if data.get_bit(idx) {
-                mark_scan_map[build_index.chunk_index as usize][build_index.row_index as usize] =
-                    MARKER_KIND_TRUE;
-            }
+        for (build_index, outcome) in build_indexes[0..matched_num].iter().zip(outcomes) {
+            mark_scan_map[build_index.chunk_index as usize][build_index.row_index as usize]
+                .merge(outcome);
         }
 
         Ok(vec![])
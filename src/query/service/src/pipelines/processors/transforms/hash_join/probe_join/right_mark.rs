@@ -17,15 +17,10 @@ use std::sync::atomic::Ordering;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
-use common_expression::types::BooleanType;
-use common_expression::types::NullableType;
-use common_expression::types::ValueType;
 use common_expression::DataBlock;
 use common_hashtable::HashJoinHashtableLike;
 
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_FALSE;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_NULL;
-use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_TRUE;
+use crate::pipelines::processors::transforms::hash_join::desc::ComparisonOutcome;
 use crate::pipelines::processors::transforms::hash_join::HashJoinProbeState;
 use crate::pipelines::processors::transforms::hash_join::ProbeState;
 
@@ -60,7 +55,7 @@ impl HashJoinProbeState {
             };
 
             if contains {
-                markers[i] = MARKER_KIND_TRUE;
+                markers[i] = ComparisonOutcome::True;
             }
         }
 
@@ -174,25 +169,14 @@ impl HashJoinProbeState {
                     };
                     let result_block = self.merge_eq_block(probe_block, build_block, matched_num);
 
-                    let filter = self.get_nullable_filter_column(
+                    let outcomes = self.get_comparison_outcomes(
                         &result_block,
                         other_predicate,
                         &self.func_ctx,
                     )?;
-                    let filter_viewer =
-                        NullableType::<BooleanType>::try_downcast_column(&filter).unwrap();
-                    let validity = &filter_viewer.validity;
-                    let data = &filter_viewer.column;
 
-                    for (idx, index) in probe_indexes.iter().enumerate() {
-                        let marker = &mut markers[*index as usize];
-                        if !validity.get_bit(idx) {
-                            if *marker == MARKER_KIND_FALSE {
-                                *marker = MARKER_KIND_NULL;
-                            }
-                        } else if data.get_bit(idx) {
-                            *marker = MARKER_KIND_TRUE;
-                        }
+                    for (index, outcome) in probe_indexes.iter().zip(outcomes) {
+                        markers[*index as usize].merge(outcome);
                     }
                     matched_num = 0;
 
@@ -245,21 +229,11 @@ impl HashJoinProbeState {
             };
             let result_block = self.merge_eq_block(probe_block, build_block, matched_num);
 
-            let filter =
-                self.get_nullable_filter_column(&result_block, other_predicate, &self.func_ctx)?;
-            let filter_viewer = NullableType::<BooleanType>::try_downcast_column(&filter).unwrap();
-            let validity = &filter_viewer.validity;
-            let data = &filter_viewer.column;
+            let outcomes =
+                self.get_comparison_outcomes(&result_block, other_predicate, &self.func_ctx)?;
 
-            for (idx, index) in probe_indexes.iter().enumerate().take(matched_num) {
-                let marker = &mut markers[*index as usize];
-                if !validity.get_bit(idx) {
-                    if *marker == MARKER_KIND_FALSE {
-                        *marker = MARKER_KIND_NULL;
-                    }
-                } else if data.get_bit(idx) {
-                    *marker = MARKER_KIND_TRUE;
-                }
+            for (index, outcome) in probe_indexes.iter().take(matched_num).zip(outcomes) {
+                markers[*index as usize].merge(outcome);
             }
         }
 
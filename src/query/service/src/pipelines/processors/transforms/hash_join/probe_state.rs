@@ -16,7 +16,7 @@ use common_arrow::arrow::bitmap::Bitmap;
 use common_expression::FunctionContext;
 use common_hashtable::RowPtr;
 
-use super::desc::MARKER_KIND_FALSE;
+use super::desc::ComparisonOutcome;
 use crate::sql::plans::JoinType;
 
 /// ProbeState used for probe phase of hash join.
@@ -35,7 +35,7 @@ pub struct ProbeState {
     pub(crate) row_state: Option<Vec<usize>>,
     pub(crate) row_state_indexes: Option<Vec<usize>>,
     pub(crate) probe_unmatched_indexes: Option<Vec<u32>>,
-    pub(crate) markers: Option<Vec<u8>>,
+    pub(crate) markers: Option<Vec<ComparisonOutcome>>,
     pub(crate) string_items_buf: Option<Vec<(u64, usize)>>,
 }
 
@@ -71,7 +71,7 @@ impl ProbeState {
             _ => (None, None, None),
         };
         let markers = if matches!(&join_type, JoinType::RightMark) {
-            Some(vec![MARKER_KIND_FALSE; max_block_size])
+            Some(vec![ComparisonOutcome::False; max_block_size])
         } else {
             None
         };
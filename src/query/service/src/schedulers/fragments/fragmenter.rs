@@ -25,6 +25,7 @@ use common_sql::executor::ReplaceInto;
 
 use crate::api::BroadcastExchange;
 use crate::api::DataExchange;
+use crate::api::ExchangeKind;
 use crate::api::MergeExchange;
 use crate::api::ShuffleDataExchange;
 use crate::clusters::ClusterHelper;
@@ -128,6 +129,18 @@ impl Fragmenter {
         Ok(root_fragment)
     }
 
+    /// Whether every executor already holds all the rows of `plan`,
+    /// i.e., it reads from a source fragment that is broadcast.
+    fn is_broadcast(&self, plan: &PhysicalPlan) -> bool {
+        match plan {
+            PhysicalPlan::ExchangeSource(source) => self.fragments.iter().any(|fragment| {
+                fragment.fragment_id == source.source_fragment_id
+                    && fragment.connection_kind() == ExchangeKind::Broadcast
+            }),
+            _ => false,
+        }
+    }
+
     fn resolve_fragment_connection(fragment: &mut PlanFragment) {
         for source_fragment in fragment.source_fragments.iter_mut() {
             if let PhysicalPlan::ExchangeSink(ExchangeSink {
@@ -253,6 +266,13 @@ impl PhysicalPlanReplacer for Fragmenter {
     fn replace_exchange(&mut self, plan: &Exchange) -> Result<PhysicalPlan> {
         // Recursively rewrite input
         let input = self.replace(plan.input.as_ref())?;
+
+        // Broadcasting an input that is already broadcast sends every row to each executor again,
+        // thus the input is used as is, without another fragment.
+        if plan.kind == FragmentKind::Expansive && self.is_broadcast(&input) {
+            return Ok(input);
+        }
+
         let input_schema = input.output_schema()?;
 
         let plan_id = plan.plan_id;
@@ -289,7 +309,7 @@ impl PhysicalPlanReplacer for Fragmenter {
             &plan,
             self.fragments
                 .iter()
-                .all(|fragment| !matches!(&fragment.exchange, Some(DataExchange::Merge(_)))),
+                .all(|fragment| fragment.connection_kind() != ExchangeKind::Merge),
         )?;
 
         let mut source_fragment = PlanFragment {
@@ -18,6 +18,7 @@ mod query_fragment_actions;
 mod query_fragment_actions_display;
 
 pub use fragmenter::Fragmenter;
+pub use plan_fragment::FragmentType;
 pub use plan_fragment::PlanFragment;
 pub use plan_fragment::ReplaceReadSource;
 pub use query_fragment_actions::QueryFragmentAction;
@@ -34,6 +34,7 @@ use storages_common_table_meta::meta::BlockSlotDescription;
 use storages_common_table_meta::meta::Location;
 
 use crate::api::DataExchange;
+use crate::api::ExchangeKind;
 use crate::schedulers::Fragmenter;
 use crate::schedulers::QueryFragmentAction;
 use crate::schedulers::QueryFragmentActions;
@@ -78,6 +79,14 @@ pub struct PlanFragment {
 }
 
 impl PlanFragment {
+    /// The kind of exchange that sends the output of this fragment to its parent.
+    pub fn connection_kind(&self) -> ExchangeKind {
+        match &self.exchange {
+            None => ExchangeKind::None,
+            Some(exchange) => exchange.kind(),
+        }
+    }
+
     pub fn get_actions(
         &self,
         ctx: Arc<QueryContext>,
@@ -101,7 +110,7 @@ impl PlanFragment {
                 if self
                     .source_fragments
                     .iter()
-                    .any(|fragment| matches!(&fragment.exchange, Some(DataExchange::Merge(_))))
+                    .any(|fragment| fragment.connection_kind() == ExchangeKind::Merge)
                 {
                     // If this is a intermediate fragment with merge input,
                     // we will only send it to coordinator node.
@@ -112,7 +121,7 @@ impl PlanFragment {
                     fragment_actions.add_action(action);
                 } else {
                     // Otherwise distribute the fragment to all the executors.
-                    for executor in Fragmenter::get_executors(ctx) {
+                    for executor in self.get_executors(ctx, actions)? {
                         let action = QueryFragmentAction::create(executor, self.plan.clone());
                         fragment_actions.add_action(action);
                     }
@@ -120,7 +129,8 @@ impl PlanFragment {
             }
             FragmentType::Source => {
                 // Redistribute partitions
-                self.redistribute_source_fragment(ctx, &mut fragment_actions)?;
+                let executors = self.get_executors(ctx, actions)?;
+                self.redistribute_source_fragment(executors, &mut fragment_actions)?;
             }
             FragmentType::DeleteLeaf => {
                 self.redistribute_delete_leaf(ctx, &mut fragment_actions)?;
@@ -137,16 +147,60 @@ impl PlanFragment {
             }
         }
 
+        if self.skips_broadcast_excluded(actions) {
+            // Broadcast the inputs only to the executors this fragment runs on.
+            let executors = fragment_actions
+                .get_actions()
+                .iter()
+                .map(|action| action.executor.clone())
+                .collect::<Vec<_>>();
+            for input in self.source_fragments.iter() {
+                actions.set_broadcast_destinations(input.fragment_id, executors.clone())?;
+            }
+        }
+
         if let Some(ref exchange) = self.exchange {
             fragment_actions.set_exchange(exchange.clone());
         }
         actions.add_fragment_actions(fragment_actions)
     }
 
+    /// Returns true if this fragment does not run on the executors excluded from broadcast.
+    ///
+    /// An excluded executor receives no broadcast data, so a fragment reading a broadcast input
+    /// must not run on it either: e.g. a broadcast join there would see an empty build side.
+    /// It is only done for a fragment that reads nothing but broadcast inputs,
+    /// and is not bound to the coordinator, because other exchanges still send to every executor.
+    fn skips_broadcast_excluded(&self, actions: &QueryFragmentsActions) -> bool {
+        actions.has_broadcast_exclusions()
+            && matches!(
+                self.fragment_type,
+                FragmentType::Intermediate | FragmentType::Source
+            )
+            && !self.source_fragments.is_empty()
+            && self
+                .source_fragments
+                .iter()
+                .all(|fragment| fragment.connection_kind() == ExchangeKind::Broadcast)
+    }
+
+    /// The executors to distribute this fragment to.
+    fn get_executors(
+        &self,
+        ctx: Arc<QueryContext>,
+        actions: &QueryFragmentsActions,
+    ) -> Result<Vec<String>> {
+        let executors = Fragmenter::get_executors(ctx);
+        match self.skips_broadcast_excluded(actions) {
+            true => actions.broadcast_receivers(executors),
+            false => Ok(executors),
+        }
+    }
+
     /// Redistribute partitions of current source fragment to executors.
     fn redistribute_source_fragment(
         &self,
-        ctx: Arc<QueryContext>,
+        executors: Vec<String>,
         fragment_actions: &mut QueryFragmentActions,
     ) -> Result<()> {
         if self.fragment_type != FragmentType::Source {
@@ -157,7 +211,6 @@ impl PlanFragment {
 
         let read_source = self.get_read_source()?;
 
-        let executors = Fragmenter::get_executors(ctx);
         // Redistribute partitions of ReadDataSourcePlan.
         let partitions = &read_source.parts;
         let partition_reshuffle = partitions.reshuffle(executors)?;
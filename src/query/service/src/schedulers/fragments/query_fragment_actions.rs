@@ -14,6 +14,7 @@
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
@@ -24,6 +25,7 @@ use common_expression::DataSchemaRef;
 use common_meta_types::NodeInfo;
 use itertools::Itertools;
 
+use crate::api::BroadcastExchange;
 use crate::api::ConnectionInfo;
 use crate::api::DataExchange;
 use crate::api::ExecutePartialQueryPacket;
@@ -106,6 +108,8 @@ impl QueryFragmentActions {
 pub struct QueryFragmentsActions {
     ctx: Arc<QueryContext>,
     enable_profiling: bool,
+    // Executors that never receive data from a broadcast exchange.
+    broadcast_excluded: HashSet<String>,
     pub fragments_actions: Vec<QueryFragmentActions>,
 }
 
@@ -114,10 +118,79 @@ impl QueryFragmentsActions {
         QueryFragmentsActions {
             ctx,
             enable_profiling,
+            broadcast_excluded: HashSet::new(),
             fragments_actions: Vec::new(),
         }
     }
 
+    /// Exclude an executor from the destinations of broadcast exchanges,
+    /// e.g. to keep a busy coordinator from receiving broadcast data.
+    ///
+    /// A fragment that reads only broadcast inputs is then not run on the excluded executor,
+    /// see [`PlanFragment::get_actions`](crate::schedulers::PlanFragment::get_actions).
+    pub fn exclude_from_broadcast(&mut self, executor: impl Into<String>) {
+        self.broadcast_excluded.insert(executor.into());
+    }
+
+    /// Exclude the coordinator from broadcast exchanges if `exclude_coordinator_from_broadcast` is set.
+    ///
+    /// A single node cluster has no other executor to broadcast to, and is left as is.
+    pub fn apply_broadcast_settings(&mut self) -> Result<()> {
+        let settings = self.ctx.get_settings();
+        if settings.get_exclude_coordinator_from_broadcast()? && self.get_executors().len() > 1 {
+            self.exclude_from_broadcast(self.get_local_executor());
+        }
+        Ok(())
+    }
+
+    /// The executors to run a fragment that reads only broadcast inputs on.
+    pub fn broadcast_receivers(&self, executors: Vec<String>) -> Result<Vec<String>> {
+        let receivers = executors
+            .into_iter()
+            .filter(|id| !self.broadcast_excluded.contains(id))
+            .collect::<Vec<_>>();
+
+        if receivers.is_empty() {
+            return Err(ErrorCode::Internal(format!(
+                "All executors of broadcast exchange are excluded, excluded: {:?}",
+                self.broadcast_excluded
+            )));
+        }
+
+        Ok(receivers)
+    }
+
+    pub fn has_broadcast_exclusions(&self) -> bool {
+        !self.broadcast_excluded.is_empty()
+    }
+
+    /// Send the broadcast output of the fragment `fragment_id` only to `destination_ids`.
+    pub fn set_broadcast_destinations(
+        &mut self,
+        fragment_id: usize,
+        destination_ids: Vec<String>,
+    ) -> Result<()> {
+        let fragment_actions = self
+            .fragments_actions
+            .iter_mut()
+            .find(|actions| actions.fragment_id == fragment_id)
+            .ok_or_else(|| {
+                ErrorCode::Internal(format!(
+                    "Logical error, cannot find actions of fragment {}",
+                    fragment_id
+                ))
+            })?;
+
+        if let Some(DataExchange::Broadcast(exchange)) = &fragment_actions.data_exchange {
+            let from_multiple_nodes = exchange.from_multiple_nodes;
+            fragment_actions.set_exchange(BroadcastExchange::create(
+                from_multiple_nodes,
+                destination_ids,
+            ));
+        }
+        Ok(())
+    }
+
     pub fn get_executors(&self) -> Vec<String> {
         let cluster = self.ctx.get_cluster();
         let cluster_nodes = cluster.get_nodes();
@@ -168,7 +241,7 @@ impl QueryFragmentsActions {
     ) -> Result<(QueryFragmentsPlanPacket, Vec<QueryFragmentsPlanPacket>)> {
         let nodes_info = Self::nodes_info(&self.ctx);
 
-        let mut fragments_packets = self.get_executors_fragments();
+        let mut fragments_packets = self.get_executors_fragments(&nodes_info);
         let mut query_fragments_plan_packets = Vec::with_capacity(fragments_packets.len());
 
         let cluster = self.ctx.get_cluster();
@@ -350,14 +423,30 @@ impl QueryFragmentsActions {
         nodes_info
     }
 
-    fn get_executors_fragments(&self) -> HashMap<String, Vec<FragmentPlanPacket>> {
+    /// The parallelism of an executor planned by the coordinator: the cpu cores of it, 0 if unknown.
+    ///
+    /// The `max_threads` of the coordinator is not applied, an executor caps it with its own one.
+    pub fn executor_parallelism(node: &NodeInfo) -> usize {
+        node.cpu_nums as usize
+    }
+
+    fn get_executors_fragments(
+        &self,
+        nodes_info: &HashMap<String, Arc<NodeInfo>>,
+    ) -> HashMap<String, Vec<FragmentPlanPacket>> {
         let mut fragments_packets = HashMap::new();
         for fragment_actions in &self.fragments_actions {
             for fragment_action in &fragment_actions.fragment_actions {
+                let parallelism = match nodes_info.get(&fragment_action.executor) {
+                    Some(node) => Self::executor_parallelism(node),
+                    None => 0,
+                };
+
                 let fragment_packet = FragmentPlanPacket::create(
                     fragment_actions.fragment_id,
                     fragment_action.physical_plan.clone(),
                     fragment_actions.data_exchange.clone(),
+                    parallelism,
                 );
 
                 match fragments_packets.entry(fragment_action.executor.clone()) {
@@ -15,7 +15,10 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use common_functions::BUILTIN_FUNCTIONS;
 use common_profile::SharedProcessorProfiles;
+use common_sql::executor::HashJoin;
+use common_sql::executor::PhysicalPlan;
 use common_sql::MetadataRef;
 
 use crate::api::DataExchange;
@@ -43,7 +46,12 @@ impl<'a> Display for QueryFragmentsActionsWrap<'a> {
                 writeln!(f)?;
             }
 
-            writeln!(f, "{}", fragment_actions.display_indent(self.metadata))?;
+            let wrap = QueryFragmentActionsWrap {
+                inner: fragment_actions,
+                metadata: self.metadata,
+                fragments: &self.inner.fragments_actions,
+            };
+            writeln!(f, "{}", wrap)?;
         }
 
         Ok(())
@@ -55,6 +63,7 @@ impl QueryFragmentActions {
         QueryFragmentActionsWrap {
             inner: self,
             metadata,
+            fragments: &[],
         }
     }
 }
@@ -62,6 +71,72 @@ impl QueryFragmentActions {
 struct QueryFragmentActionsWrap<'a> {
     inner: &'a QueryFragmentActions,
     metadata: &'a MetadataRef,
+    /// All fragments of the query, to find the exchange that feeds a join.
+    fragments: &'a [QueryFragmentActions],
+}
+
+impl<'a> QueryFragmentActionsWrap<'a> {
+    fn source_fragment(&self, plan: &PhysicalPlan) -> Option<&'a QueryFragmentActions> {
+        match plan {
+            PhysicalPlan::ExchangeSource(source) => self
+                .fragments
+                .iter()
+                .find(|fragment| fragment.fragment_id == source.source_fragment_id),
+            _ => None,
+        }
+    }
+
+    /// The estimated rows of a join input, which is the output of the source fragment if it is exchanged.
+    fn input_rows(&self, plan: &PhysicalPlan) -> String {
+        let rows = match self.source_fragment(plan) {
+            Some(fragment) => fragment
+                .fragment_actions
+                .first()
+                .and_then(|action| action.physical_plan.estimated_rows()),
+            None => plan.estimated_rows(),
+        };
+
+        match rows {
+            Some(rows) => format!("{:.2}", rows),
+            None => "unknown".to_string(),
+        }
+    }
+
+    /// Describe how the inputs of a join are exchanged: the exchange of the build side
+    /// decides whether it is a broadcast or a shuffle join.
+    fn join_exchange(&self, join: &HashJoin) -> String {
+        let exchange = self
+            .source_fragment(&join.build)
+            .or_else(|| self.source_fragment(&join.probe))
+            .and_then(|fragment| fragment.data_exchange.as_ref());
+
+        match exchange {
+            None => "Local".to_string(),
+            Some(DataExchange::Merge(_)) => "Merge".to_string(),
+            Some(DataExchange::Broadcast(exchange)) => {
+                format!("Broadcast ({} nodes)", exchange.destination_ids.len())
+            }
+            Some(DataExchange::ShuffleDataExchange(exchange)) => {
+                let keys = join
+                    .build_keys
+                    .iter()
+                    .map(|key| key.as_expr(&BUILTIN_FUNCTIONS).sql_display())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("Hash({}) ({} nodes)", keys, exchange.destination_ids.len())
+            }
+        }
+    }
+}
+
+fn collect_hash_joins<'a>(plan: &'a PhysicalPlan, joins: &mut Vec<&'a HashJoin>) {
+    if let PhysicalPlan::HashJoin(join) = plan {
+        joins.push(join);
+    }
+
+    for child in plan.children() {
+        collect_hash_joins(child, joins);
+    }
 }
 
 impl<'a> Display for QueryFragmentActionsWrap<'a> {
@@ -78,6 +153,23 @@ impl<'a> Display for QueryFragmentActionsWrap<'a> {
 
         if !self.inner.fragment_actions.is_empty() {
             let fragment_action = &self.inner.fragment_actions[0];
+
+            if !self.fragments.is_empty() {
+                let mut joins = vec![];
+                collect_hash_joins(&fragment_action.physical_plan, &mut joins);
+
+                for join in joins {
+                    writeln!(
+                        f,
+                        "  HashJoin: {}, Exchange: {}, Estimated Rows: build {}, probe {}",
+                        join.join_type,
+                        self.join_exchange(join),
+                        self.input_rows(&join.build),
+                        self.input_rows(&join.probe)
+                    )?;
+                }
+            }
+
             let plan_display_string = fragment_action
                 .physical_plan
                 .format(self.metadata.clone(), SharedProcessorProfiles::default())
@@ -103,6 +103,7 @@ pub async fn build_distributed_pipeline(
 
     let root_fragment = fragmenter.build_fragment(plan)?;
     let mut fragments_actions = QueryFragmentsActions::create(ctx.clone(), enable_profiling);
+    fragments_actions.apply_broadcast_settings()?;
     root_fragment.get_actions(ctx.clone(), &mut fragments_actions)?;
 
     let exchange_manager = ctx.get_exchange_manager();
@@ -29,6 +29,7 @@ use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
 use chrono_tz::Tz;
+use common_ast::Dialect;
 use common_base::base::tokio::task::JoinHandle;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
@@ -545,12 +546,14 @@ impl TableContext for QueryContext {
         let tz = TzFactory::instance().get_by_name(&tz)?;
         let numeric_cast_option = self.get_settings().get_numeric_cast_option()?;
         let rounding_mode = numeric_cast_option.as_str() == "rounding";
+        let mysql_compat = self.get_settings().get_sql_dialect()? == Dialect::MySQL;
 
         let query_config = &GlobalConfig::instance().query;
 
         Ok(FunctionContext {
             tz,
             rounding_mode,
+            mysql_compat,
 
             openai_api_key: query_config.openai_api_key.clone(),
             openai_api_version: query_config.openai_api_version.clone(),
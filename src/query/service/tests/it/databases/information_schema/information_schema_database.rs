@@ -0,0 +1,58 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use common_exception::Result;
+use common_storages_information_schema::register_all;
+use databend_query::catalogs::InMemoryMetas;
+use databend_query::catalogs::SYS_DB_ID_BEGIN;
+use databend_query::catalogs::SYS_TBL_ID_BEGIN;
+use databend_query::databases::InformationSchemaDatabase;
+use databend_query::storages::Table;
+
+#[test]
+fn test_information_schema_register_all() -> Result<()> {
+    let sys_db_meta = InMemoryMetas::create(SYS_DB_ID_BEGIN, SYS_TBL_ID_BEGIN);
+    let tables = register_all(|| sys_db_meta.next_table_id());
+
+    let mut names = tables.iter().map(|t| t.name()).collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(
+        vec![
+            "columns",
+            "key_column_usage",
+            "keywords",
+            "schemata",
+            "statistics",
+            "tables",
+            "views",
+        ],
+        names
+    );
+
+    let ids = tables.iter().map(|t| t.get_id()).collect::<HashSet<_>>();
+    assert_eq!(tables.len(), ids.len(), "table ids must be unique");
+
+    // The database registers every table.
+    let mut sys_db_meta = InMemoryMetas::create(SYS_DB_ID_BEGIN, SYS_TBL_ID_BEGIN);
+    sys_db_meta.init_db("information_schema");
+    let _ = InformationSchemaDatabase::create(&mut sys_db_meta);
+    for name in names {
+        let t = sys_db_meta.get_by_name("information_schema", name);
+        assert!(t.is_ok(), "information_schema.{} is not registered", name);
+    }
+
+    Ok(())
+}
@@ -30,6 +30,7 @@ mod interpreters;
 mod metrics;
 mod parquet_rs;
 mod pipelines;
+mod schedulers;
 mod servers;
 mod sessions;
 mod spillers;
@@ -0,0 +1,66 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_expression::DataSchemaRefExt;
+use databend_query::api::BroadcastExchange;
+use databend_query::api::DataExchange;
+use databend_query::api::ExchangeKind;
+use databend_query::api::MergeExchange;
+use databend_query::api::ShuffleDataExchange;
+use databend_query::schedulers::FragmentType;
+use databend_query::schedulers::PlanFragment;
+use databend_query::sql::executor::ExchangeSource;
+use databend_query::sql::executor::PhysicalPlan;
+
+fn fragment(fragment_type: FragmentType, exchange: Option<DataExchange>) -> PlanFragment {
+    PlanFragment {
+        plan: PhysicalPlan::ExchangeSource(ExchangeSource {
+            plan_id: 0,
+            schema: DataSchemaRefExt::create(vec![]),
+            source_fragment_id: 0,
+            query_id: "query".to_string(),
+        }),
+        fragment_type,
+        fragment_id: 1,
+        exchange,
+        query_id: "query".to_string(),
+        source_fragments: vec![],
+    }
+}
+
+#[test]
+fn test_plan_fragment_connection_kind() {
+    let destinations = vec!["node1".to_string(), "node2".to_string()];
+
+    let root = fragment(FragmentType::Root, None);
+    assert_eq!(ExchangeKind::None, root.connection_kind());
+
+    let merge = fragment(
+        FragmentType::Intermediate,
+        Some(MergeExchange::create("node1".to_string(), false)),
+    );
+    assert_eq!(ExchangeKind::Merge, merge.connection_kind());
+
+    let broadcast = fragment(
+        FragmentType::Intermediate,
+        Some(BroadcastExchange::create(true, destinations.clone())),
+    );
+    assert_eq!(ExchangeKind::Broadcast, broadcast.connection_kind());
+
+    let shuffle = fragment(
+        FragmentType::Source,
+        Some(ShuffleDataExchange::create(destinations, vec![])),
+    );
+    assert_eq!(ExchangeKind::Shuffle, shuffle.connection_kind());
+}
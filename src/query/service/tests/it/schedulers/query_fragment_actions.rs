@@ -0,0 +1,324 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_expression::block_debug::pretty_format_blocks;
+use common_expression::DataBlock;
+use common_expression::DataSchemaRefExt;
+use common_meta_types::NodeInfo;
+use databend_query::api::BroadcastExchange;
+use databend_query::api::DataExchange;
+use databend_query::api::ExchangeKind;
+use databend_query::api::FragmentPlanPacket;
+use databend_query::schedulers::FragmentType;
+use databend_query::schedulers::Fragmenter;
+use databend_query::schedulers::PlanFragment;
+use databend_query::schedulers::QueryFragmentsActions;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::TableContext;
+use databend_query::sql::executor::Exchange;
+use databend_query::sql::executor::ExchangeSource;
+use databend_query::sql::executor::FragmentKind;
+use databend_query::sql::executor::PhysicalPlan;
+use databend_query::test_kits::create_query_context_with_cluster;
+use databend_query::test_kits::execute_query;
+use databend_query::test_kits::ClusterDescriptor;
+use databend_query::test_kits::TestGuard;
+use futures::TryStreamExt;
+
+async fn create_cluster_context() -> Result<(TestGuard, Arc<QueryContext>)> {
+    let cluster_desc = ClusterDescriptor::new()
+        .with_node("node1", "127.0.0.1:9091")
+        .with_node("node2", "127.0.0.1:9092")
+        .with_node("node3", "127.0.0.1:9093")
+        .with_local_id("node1");
+
+    create_query_context_with_cluster(cluster_desc).await
+}
+
+fn broadcast_fragment(ctx: Arc<QueryContext>) -> PlanFragment {
+    PlanFragment {
+        plan: PhysicalPlan::ExchangeSource(ExchangeSource {
+            plan_id: 0,
+            schema: DataSchemaRefExt::create(vec![]),
+            source_fragment_id: 0,
+            query_id: ctx.get_id(),
+        }),
+        fragment_type: FragmentType::Intermediate,
+        fragment_id: 1,
+        exchange: Some(BroadcastExchange::create(
+            true,
+            Fragmenter::get_executors(ctx.clone()),
+        )),
+        query_id: ctx.get_id(),
+        source_fragments: vec![],
+    }
+}
+
+fn broadcast_destinations(actions: &QueryFragmentsActions) -> Vec<String> {
+    let mut destinations = vec![];
+    for fragment_actions in &actions.fragments_actions {
+        if let Some(DataExchange::Broadcast(exchange)) = &fragment_actions.data_exchange {
+            destinations.extend(exchange.destination_ids.iter().cloned());
+        }
+    }
+    destinations.sort();
+    destinations
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_broadcast_to_all_executors_by_default() -> Result<()> {
+    let (_guard, ctx) = create_cluster_context().await?;
+
+    let mut actions = QueryFragmentsActions::create(ctx.clone(), false);
+    broadcast_fragment(ctx.clone()).get_actions(ctx.clone(), &mut actions)?;
+
+    assert_eq!(broadcast_destinations(&actions), vec![
+        "node1".to_string(),
+        "node2".to_string(),
+        "node3".to_string(),
+    ]);
+    Ok(())
+}
+
+fn consumer_fragment(
+    ctx: Arc<QueryContext>,
+    fragment_type: FragmentType,
+    input: PlanFragment,
+) -> PlanFragment {
+    PlanFragment {
+        plan: PhysicalPlan::ExchangeSource(ExchangeSource {
+            plan_id: 0,
+            schema: DataSchemaRefExt::create(vec![]),
+            source_fragment_id: input.fragment_id,
+            query_id: ctx.get_id(),
+        }),
+        fragment_type,
+        fragment_id: 2,
+        exchange: None,
+        query_id: ctx.get_id(),
+        source_fragments: vec![input],
+    }
+}
+
+fn fragment_executors(actions: &QueryFragmentsActions, fragment_id: usize) -> Vec<String> {
+    let mut executors = actions
+        .fragments_actions
+        .iter()
+        .filter(|fragment_actions| fragment_actions.fragment_id == fragment_id)
+        .flat_map(|fragment_actions| fragment_actions.get_actions())
+        .map(|action| action.executor.clone())
+        .collect::<Vec<_>>();
+    executors.sort();
+    executors
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_broadcast_exclude_coordinator() -> Result<()> {
+    let (_guard, ctx) = create_cluster_context().await?;
+    let coordinator = Fragmenter::get_local_executor(ctx.clone());
+
+    let input = broadcast_fragment(ctx.clone());
+    let consumer = consumer_fragment(ctx.clone(), FragmentType::Intermediate, input);
+
+    let mut actions = QueryFragmentsActions::create(ctx.clone(), false);
+    actions.exclude_from_broadcast(coordinator.clone());
+    consumer.get_actions(ctx.clone(), &mut actions)?;
+
+    // The coordinator neither receives the broadcast data, nor runs the fragment reading it.
+    let destinations = broadcast_destinations(&actions);
+    assert!(!destinations.contains(&coordinator));
+    assert_eq!(destinations, vec!["node2".to_string(), "node3".to_string()]);
+    assert_eq!(destinations, fragment_executors(&actions, 2));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_broadcast_exclude_coordinator_bound_fragment() -> Result<()> {
+    let (_guard, ctx) = create_cluster_context().await?;
+    let coordinator = Fragmenter::get_local_executor(ctx.clone());
+
+    let input = broadcast_fragment(ctx.clone());
+    let consumer = consumer_fragment(ctx.clone(), FragmentType::Root, input);
+
+    let mut actions = QueryFragmentsActions::create(ctx.clone(), false);
+    actions.exclude_from_broadcast(coordinator.clone());
+    consumer.get_actions(ctx.clone(), &mut actions)?;
+
+    // The root runs on the coordinator, which still has to receive the broadcast data.
+    assert!(broadcast_destinations(&actions).contains(&coordinator));
+    assert_eq!(vec![coordinator], fragment_executors(&actions, 2));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_broadcast_exclude_coordinator_setting() -> Result<()> {
+    let (_guard, ctx) = create_cluster_context().await?;
+    ctx.get_settings().set_setting(
+        "exclude_coordinator_from_broadcast".to_string(),
+        "1".to_string(),
+    )?;
+
+    let input = broadcast_fragment(ctx.clone());
+    let consumer = consumer_fragment(ctx.clone(), FragmentType::Intermediate, input);
+
+    let mut actions = QueryFragmentsActions::create(ctx.clone(), false);
+    actions.apply_broadcast_settings()?;
+    consumer.get_actions(ctx.clone(), &mut actions)?;
+
+    let executors = fragment_executors(&actions, 2);
+    assert_eq!(executors, vec!["node2".to_string(), "node3".to_string()]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fragment_packets_parallelism() -> Result<()> {
+    let (_guard, ctx) = create_cluster_context().await?;
+
+    let mut actions = QueryFragmentsActions::create(ctx.clone(), false);
+    broadcast_fragment(ctx.clone()).get_actions(ctx.clone(), &mut actions)?;
+
+    let (local, remotes) = actions.get_query_fragments_plan_packets()?;
+
+    // The cpu cores of the test nodes are unknown,
+    // the coordinator does not limit an executor, e.g., with its own max_threads.
+    for packet in std::iter::once(&local).chain(remotes.iter()) {
+        assert!(!packet.fragments.is_empty());
+        for fragment in &packet.fragments {
+            assert_eq!(0, fragment.parallelism, "executor: {}", packet.executor);
+        }
+    }
+
+    Ok(())
+}
+
+fn broadcast_exchange(input: PhysicalPlan) -> PhysicalPlan {
+    PhysicalPlan::Exchange(Exchange {
+        plan_id: 0,
+        input: Box::new(input),
+        kind: FragmentKind::Expansive,
+        keys: vec![],
+        ignore_exchange: false,
+    })
+}
+
+fn count_broadcast_actions(actions: &QueryFragmentsActions) -> usize {
+    actions
+        .fragments_actions
+        .iter()
+        .filter(|fragment_actions| {
+            matches!(
+                fragment_actions.data_exchange,
+                Some(DataExchange::Broadcast(_))
+            )
+        })
+        .count()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_skip_broadcast_of_broadcast_input() -> Result<()> {
+    let (_guard, ctx) = create_cluster_context().await?;
+
+    let source = PhysicalPlan::ExchangeSource(ExchangeSource {
+        plan_id: 0,
+        schema: DataSchemaRefExt::create(vec![]),
+        source_fragment_id: usize::MAX,
+        query_id: ctx.get_id(),
+    });
+    let plan = broadcast_exchange(broadcast_exchange(source));
+
+    let root = Fragmenter::try_create(ctx.clone())?.build_fragment(&plan)?;
+
+    // The root reads the only broadcast fragment directly.
+    assert_eq!(1, root.source_fragments.len());
+    let broadcast = &root.source_fragments[0];
+    assert_eq!(ExchangeKind::Broadcast, broadcast.connection_kind());
+    assert!(broadcast.source_fragments.is_empty());
+    match &root.plan {
+        PhysicalPlan::ExchangeSource(source) => {
+            assert_eq!(broadcast.fragment_id, source.source_fragment_id)
+        }
+        _ => unreachable!("expect an exchange source at root"),
+    }
+
+    let mut actions = QueryFragmentsActions::create(ctx.clone(), false);
+    root.get_actions(ctx.clone(), &mut actions)?;
+
+    assert_eq!(2, actions.fragments_actions.len());
+    assert_eq!(1, count_broadcast_actions(&actions));
+    Ok(())
+}
+
+#[test]
+fn test_executor_parallelism() {
+    let node =
+        |cpu_nums| NodeInfo::create("node".to_string(), cpu_nums, "".to_string(), "".to_string());
+
+    assert_eq!(0, QueryFragmentsActions::executor_parallelism(&node(0)));
+    assert_eq!(2, QueryFragmentsActions::executor_parallelism(&node(2)));
+    assert_eq!(16, QueryFragmentsActions::executor_parallelism(&node(16)));
+}
+
+#[test]
+fn test_executor_threads() {
+    // An executor runs with its own max_threads, no more than the planned parallelism.
+    assert_eq!(8, FragmentPlanPacket::executor_threads(8, 0));
+    assert_eq!(2, FragmentPlanPacket::executor_threads(8, 2));
+    assert_eq!(8, FragmentPlanPacket::executor_threads(8, 16));
+}
+
+async fn explain_join_fragments(prefer_broadcast_join: &str) -> Result<String> {
+    let (_guard, ctx) = create_cluster_context().await?;
+    ctx.get_settings().set_setting(
+        "prefer_broadcast_join".to_string(),
+        prefer_broadcast_join.to_string(),
+    )?;
+
+    let sql =
+        "explain fragments select * from numbers(1) t, numbers(2) t1 where t.number = t1.number";
+    let blocks = execute_query(ctx, sql)
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    pretty_format_blocks(&blocks)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_explain_fragments_broadcast_join() -> Result<()> {
+    let explained = explain_join_fragments("1").await?;
+
+    assert!(
+        explained.contains("Exchange: Broadcast (3 nodes)"),
+        "explained: {}",
+        explained
+    );
+    assert!(explained.contains("Estimated Rows: build 1.00, probe 2.00"));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_explain_fragments_shuffle_join() -> Result<()> {
+    let explained = explain_join_fragments("0").await?;
+
+    assert!(
+        explained.contains("Exchange: Hash(t.number (#0)) (3 nodes)"),
+        "explained: {}",
+        explained
+    );
+    assert!(explained.contains("Estimated Rows: build 1.00, probe 2.00"));
+    Ok(())
+}
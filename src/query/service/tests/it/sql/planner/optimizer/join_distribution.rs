@@ -0,0 +1,90 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_sql::optimizer::Distribution;
+use common_sql::plans::prefer_broadcast_join;
+use common_sql::plans::BoundColumnRef;
+use common_sql::plans::Join;
+use common_sql::ColumnBindingBuilder;
+use common_sql::ScalarExpr;
+use common_sql::Visibility;
+
+fn column(name: &str, index: usize, data_type: DataType) -> ScalarExpr {
+    ScalarExpr::BoundColumnRef(BoundColumnRef {
+        span: None,
+        column: ColumnBindingBuilder::new(
+            name.to_string(),
+            index,
+            Box::new(data_type),
+            Visibility::Visible,
+        )
+        .build(),
+    })
+}
+
+#[test]
+fn test_tiny_build_side_prefers_broadcast() {
+    // 3 nodes: broadcasting the build side costs (3 - 1) copies of it.
+    assert!(prefer_broadcast_join(1_000_000.0, 10.0, 3));
+    assert!(prefer_broadcast_join(1_000.0, 499.0, 3));
+    // A single node never moves data, the same as broadcasting.
+    assert!(prefer_broadcast_join(1.0, 1_000_000.0, 1));
+}
+
+#[test]
+fn test_large_sides_prefer_shuffle() {
+    assert!(!prefer_broadcast_join(1_000_000.0, 1_000_000.0, 3));
+    assert!(!prefer_broadcast_join(1_000.0, 500.0, 3));
+    // The larger the cluster, the more costly the broadcast.
+    assert!(!prefer_broadcast_join(1_000_000.0, 100_000.0, 11));
+    assert!(prefer_broadcast_join(1_000_000.0, 100_000.0, 5));
+}
+
+#[test]
+fn test_hash_partition_satisfied_by_same_keys() {
+    let a = column("a", 0, DataType::Number(NumberDataType::Int32));
+    let b = column("b", 1, DataType::Number(NumberDataType::Int32));
+
+    let required = Distribution::Hash(vec![a.clone(), b.clone()]);
+    assert!(required.satisfied_by(&Distribution::Hash(vec![a.clone(), b.clone()])));
+
+    // Rows are placed by the hash of all the keys in order.
+    assert!(!required.satisfied_by(&Distribution::Hash(vec![b.clone(), a.clone()])));
+    assert!(!required.satisfied_by(&Distribution::Hash(vec![a.clone()])));
+    assert!(!required.satisfied_by(&Distribution::Random));
+    assert!(Distribution::Any.satisfied_by(&Distribution::Hash(vec![a])));
+}
+
+#[test]
+fn test_join_key_types() -> common_exception::Result<()> {
+    let int32 = DataType::Number(NumberDataType::Int32);
+    let join = Join {
+        left_conditions: vec![column("a", 0, int32.clone())],
+        right_conditions: vec![column("b", 1, int32)],
+        ..Default::default()
+    };
+    assert!(join.has_same_key_types()?);
+
+    // The keys would be cast to a common type before shuffled.
+    let join = Join {
+        left_conditions: vec![column("a", 0, DataType::Number(NumberDataType::Int32))],
+        right_conditions: vec![column("b", 1, DataType::Number(NumberDataType::Int64))],
+        ..Default::default()
+    };
+    assert!(!join.has_same_key_types()?);
+
+    Ok(())
+}
@@ -197,8 +197,8 @@ impl DefaultSettings {
                 }),
                 ("collation", DefaultSettingValue {
                     value: UserSettingValue::String("binary".to_owned()),
-                    desc: "Sets the character collation. Available values include \"binary\" and \"utf8\".",
-                    possible_values: Some(vec!["binary", "utf8"]),
+                    desc: "Sets the character collation. Available values include \"binary\", \"utf8\" and the case-insensitive \"utf8_ci\".",
+                    possible_values: Some(vec!["binary", "utf8", "utf8_ci"]),
                     display_in_show_settings: true,
                 }),
                 ("max_result_rows", DefaultSettingValue {
@@ -213,6 +213,12 @@ impl DefaultSettings {
                     possible_values: None,
                     display_in_show_settings: true,
                 }),
+                ("exclude_coordinator_from_broadcast", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Keeps the coordinator from receiving broadcast data in cluster mode.",
+                    possible_values: None,
+                    display_in_show_settings: true,
+                }),
                 ("storage_fetch_part_num", DefaultSettingValue {
                     value: UserSettingValue::UInt64(2),
                     desc: "Sets the number of partitions that are fetched in parallel from storage during query execution.",
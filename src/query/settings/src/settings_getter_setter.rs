@@ -209,6 +209,10 @@ impl Settings {
         Ok(self.try_get_u64("prefer_broadcast_join")? != 0)
     }
 
+    pub fn get_exclude_coordinator_from_broadcast(&self) -> Result<bool> {
+        Ok(self.try_get_u64("exclude_coordinator_from_broadcast")? != 0)
+    }
+
     pub fn get_sql_dialect(&self) -> Result<Dialect> {
         match self.try_get_string("sql_dialect")?.as_str() {
             "hive" => Ok(Dialect::Hive),
@@ -220,6 +224,7 @@ impl Settings {
     pub fn get_collation(&self) -> Result<&str> {
         match self.try_get_string("collation")?.as_str() {
             "utf8" => Ok("utf8"),
+            "utf8_ci" => Ok("utf8_ci"),
             _ => Ok("binary"),
         }
     }
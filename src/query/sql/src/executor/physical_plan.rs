@@ -349,6 +349,53 @@ impl PhysicalPlan {
         }
     }
 
+    /// The estimated number of output rows, looking through the exchanges.
+    ///
+    /// Returns None if the plan node does not carry statistics.
+    pub fn estimated_rows(&self) -> Option<f64> {
+        let stat_info = match self {
+            PhysicalPlan::TableScan(plan) => &plan.stat_info,
+            PhysicalPlan::Filter(plan) => &plan.stat_info,
+            PhysicalPlan::Project(plan) => &plan.stat_info,
+            PhysicalPlan::EvalScalar(plan) => &plan.stat_info,
+            PhysicalPlan::ProjectSet(plan) => &plan.stat_info,
+            PhysicalPlan::AggregateExpand(plan) => &plan.stat_info,
+            PhysicalPlan::AggregatePartial(plan) => &plan.stat_info,
+            PhysicalPlan::AggregateFinal(plan) => &plan.stat_info,
+            PhysicalPlan::Lambda(plan) => &plan.stat_info,
+            PhysicalPlan::Sort(plan) => &plan.stat_info,
+            PhysicalPlan::Limit(plan) => &plan.stat_info,
+            PhysicalPlan::RowFetch(plan) => &plan.stat_info,
+            PhysicalPlan::HashJoin(plan) => &plan.stat_info,
+            PhysicalPlan::RangeJoin(plan) => &plan.stat_info,
+            PhysicalPlan::UnionAll(plan) => &plan.stat_info,
+            PhysicalPlan::Exchange(plan) => return plan.input.estimated_rows(),
+            PhysicalPlan::ExchangeSink(plan) => return plan.input.estimated_rows(),
+            PhysicalPlan::Window(_)
+            | PhysicalPlan::RuntimeFilterSource(_)
+            | PhysicalPlan::CteScan(_)
+            | PhysicalPlan::MaterializedCte(_)
+            | PhysicalPlan::ConstantTableScan(_)
+            | PhysicalPlan::DistributedInsertSelect(_)
+            | PhysicalPlan::ExchangeSource(_)
+            | PhysicalPlan::DeleteSource(_)
+            | PhysicalPlan::CopyIntoTable(_)
+            | PhysicalPlan::ReplaceAsyncSourcer(_)
+            | PhysicalPlan::ReplaceDeduplicate(_)
+            | PhysicalPlan::ReplaceInto(_)
+            | PhysicalPlan::MergeIntoSource(_)
+            | PhysicalPlan::MergeInto(_)
+            | PhysicalPlan::MergeIntoAppendNotMatched(_)
+            | PhysicalPlan::MergeIntoAddRowNumber(_)
+            | PhysicalPlan::CompactSource(_)
+            | PhysicalPlan::CommitSink(_)
+            | PhysicalPlan::ReclusterSource(_)
+            | PhysicalPlan::ReclusterSink(_) => return None,
+        };
+
+        stat_info.as_ref().map(|info| info.estimated_rows)
+    }
+
     pub fn is_distributed_plan(&self) -> bool {
         self.children().any(|child| child.is_distributed_plan())
             || matches!(
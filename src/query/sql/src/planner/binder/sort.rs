@@ -70,14 +70,7 @@ impl Binder {
         distinct: bool,
     ) -> Result<OrderItems> {
         bind_context.set_expr_context(ExprContext::OrderByClause);
-        // null is the largest value in databend, smallest in hive
-        // TODO: rewrite after https://github.com/jorgecarleitao/arrow2/pull/1286 is merged
-        let default_nulls_first = !self
-            .ctx
-            .get_settings()
-            .get_sql_dialect()
-            .unwrap()
-            .is_null_biggest();
+        let default_nulls_first = self.default_nulls_first();
 
         let mut order_items = Vec::with_capacity(order_by.len());
         for order in order_by {
@@ -266,6 +259,7 @@ impl Binder {
             self.ctes_map.clone(),
         );
         scalar_binder.allow_pushdown();
+        let default_nulls_first = self.default_nulls_first();
         let mut order_by_items = Vec::with_capacity(order_by.len());
         for order in order_by.iter() {
             match order.expr {
@@ -276,7 +270,7 @@ impl Binder {
                             let order_by_item = SortItem {
                                 index: column.index,
                                 asc: order.asc.unwrap_or(true),
-                                nulls_first: order.nulls_first.unwrap_or(false),
+                                nulls_first: order.nulls_first.unwrap_or(default_nulls_first),
                             };
                             order_by_items.push(order_by_item);
                         }
@@ -306,6 +300,18 @@ impl Binder {
         ))
     }
 
+    /// Where NULLs go when an ORDER BY item doesn't say `NULLS FIRST` or `NULLS LAST`.
+    fn default_nulls_first(&self) -> bool {
+        // null is the largest value in databend, smallest in hive
+        // TODO: rewrite after https://github.com/jorgecarleitao/arrow2/pull/1286 is merged
+        !self
+            .ctx
+            .get_settings()
+            .get_sql_dialect()
+            .unwrap()
+            .is_null_biggest()
+    }
+
     #[allow(clippy::only_used_in_recursion)]
     pub(crate) fn rewrite_scalar_with_replacement<F>(
         &self,
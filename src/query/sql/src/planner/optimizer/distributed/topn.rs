@@ -17,71 +17,63 @@ use std::sync::Arc;
 use common_exception::Result;
 
 use crate::optimizer::SExpr;
+use crate::plans::Exchange;
 use crate::plans::Limit;
 use crate::plans::PatternPlan;
 use crate::plans::RelOp;
 use crate::plans::Sort;
 
 pub(super) struct TopNPushDownOptimizer {
-    topn_pattern: SExpr,
+    sort_pattern: SExpr,
     limit_pattern: SExpr,
 }
 
 impl TopNPushDownOptimizer {
     pub fn create() -> Self {
         Self {
-            topn_pattern: Self::topn_pattern(),
+            sort_pattern: Self::sort_pattern(),
             limit_pattern: Self::limit_pattern(),
         }
     }
 
-    fn topn_pattern() -> SExpr {
+    fn sort_pattern() -> SExpr {
         // Input:
-        // Limit
+        // Sort
         //  \
-        //   Sort
+        //   Exchange
         //    \
-        //     Exchange
-        //      \
-        //       *
+        //     *
         // Output:
-        // Limit
+        // Sort (after_exchange = true)
         //  \
-        //   Sort (after_exchange = true)
+        //   Exchange
         //    \
-        //     Exchange
+        //     Sort (after_exchange = false)
         //      \
-        //       Sort (after_exchange = false)
-        //        \
-        //         *
+        //       *
+        //
+        // Every node sorts its own data, and the node gathering the data
+        // only needs to merge the sorted streams.
         SExpr::create_unary(
             Arc::new(
                 PatternPlan {
-                    plan_type: RelOp::Limit,
+                    plan_type: RelOp::Sort,
                 }
                 .into(),
             ),
             Arc::new(SExpr::create_unary(
                 Arc::new(
                     PatternPlan {
-                        plan_type: RelOp::Sort,
+                        plan_type: RelOp::Exchange,
                     }
                     .into(),
                 ),
-                Arc::new(SExpr::create_unary(
-                    Arc::new(
-                        PatternPlan {
-                            plan_type: RelOp::Exchange,
-                        }
-                        .into(),
-                    ),
-                    Arc::new(SExpr::create_leaf(Arc::new(
-                        PatternPlan {
-                            plan_type: RelOp::Pattern,
-                        }
-                        .into(),
-                    ))),
-                )),
+                Arc::new(SExpr::create_leaf(Arc::new(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ))),
             )),
         )
     }
@@ -132,22 +124,22 @@ impl TopNPushDownOptimizer {
             replaced_children.push(Arc::new(new_child));
         }
         let new_sexpr = s_expr.replace_children(replaced_children);
-        let apply_topn_res = self.apply_topn(&new_sexpr)?;
-        self.apply_limit(&apply_topn_res)
+        let apply_sort_res = self.apply_sort(&new_sexpr)?;
+        self.apply_limit(&apply_sort_res)
     }
 
-    fn apply_topn(&self, s_expr: &SExpr) -> Result<SExpr> {
-        if !s_expr.match_pattern(&self.topn_pattern) {
+    fn apply_sort(&self, s_expr: &SExpr) -> Result<SExpr> {
+        if !s_expr.match_pattern(&self.sort_pattern) {
             return Ok(s_expr.clone());
         }
 
-        let sort_sexpr = s_expr.child(0)?;
-        let exchange_sexpr = sort_sexpr.child(0)?;
+        let exchange_sexpr = s_expr.child(0)?;
 
-        let mut sort: Sort = sort_sexpr.plan().clone().try_into()?;
+        let exchange: Exchange = exchange_sexpr.plan().clone().try_into()?;
+        let mut sort: Sort = s_expr.plan().clone().try_into()?;
 
-        if sort.limit.is_none() {
-            // It could be a ORDER BY ... OFFSET ... clause. (No LIMIT)
+        if exchange != Exchange::Merge || sort.after_exchange {
+            // Only an order-preserving gather can merge the sorted streams.
             return Ok(s_expr.clone());
         }
 
@@ -158,9 +150,10 @@ impl TopNPushDownOptimizer {
             SExpr::create_unary(Arc::new(sort.clone().into()), Arc::new(child));
         let new_exchange = exchange_sexpr.replace_children(vec![Arc::new(before_exchange_sort)]);
         sort.after_exchange = true;
-        let new_sort = SExpr::create_unary(Arc::new(sort.into()), Arc::new(new_exchange));
-        let new_plan = s_expr.replace_children(vec![Arc::new(new_sort)]);
-        Ok(new_plan)
+        Ok(SExpr::create_unary(
+            Arc::new(sort.into()),
+            Arc::new(new_exchange),
+        ))
     }
 
     fn apply_limit(&self, s_expr: &SExpr) -> Result<SExpr> {
@@ -47,6 +47,7 @@ pub static DEFAULT_REWRITE_RULES: Lazy<Vec<RuleID>> = Lazy::new(|| {
         RuleID::PushDownFilterJoin,
         RuleID::PushDownFilterProjectSet,
         RuleID::FoldCountAggregate,
+        RuleID::FoldMinMaxAggregate,
         RuleID::TryApplyAggIndex,
         RuleID::SplitAggregate,
         RuleID::PushDownFilterScan,
@@ -92,7 +92,14 @@ pub fn require_property(
             }
         }
 
-        if required.satisfied_by(&physical) {
+        // The keys of join are cast to a common type before shuffling, which makes them hashed
+        // differently from an input that is already partitioned by the keys of the original type.
+        let reuse_partition = match (s_expr.plan.as_ref(), &required.distribution) {
+            (RelOperator::Join(join), Distribution::Hash(_)) => join.has_same_key_types()?,
+            _ => true,
+        };
+
+        if reuse_partition && required.satisfied_by(&physical) {
             children.push(Arc::new(optimized_expr.child(index)?.clone()));
             continue;
         }
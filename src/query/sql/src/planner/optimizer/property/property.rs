@@ -92,11 +92,15 @@ impl Distribution {
             | (Distribution::Serial, Distribution::Serial)
             | (Distribution::Broadcast, Distribution::Broadcast) => true,
 
-            // TODO(leiysky): this is actually broken by https://github.com/datafuselabs/databend/pull/7451
-            // , would be fixed later.
-            // (Distribution::Hash(ref keys), Distribution::Hash(ref other_keys)) => keys
-            //     .iter()
-            //     .all(|key| other_keys.iter().any(|other_key| key == other_key)),
+            // A row is sent to the node by the hash of all the keys in order,
+            // thus only the same keys place the same rows on the same node.
+            //
+            // This arm used to be disabled: it accepted an input partitioned by any keys that
+            // contain the required ones, in any order, e.g., `Hash(a, b)` for `Hash(a)`,
+            // where rows with the same `a` may be on different nodes. And since the join keys are
+            // cast to a common type before shuffling, equal keys of different types may be hashed
+            // differently, which is checked by `Join::has_same_key_types` in the enforcer.
+            (Distribution::Hash(keys), Distribution::Hash(other_keys)) => keys == other_keys,
             _ => false,
         }
     }
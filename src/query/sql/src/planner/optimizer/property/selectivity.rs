@@ -130,6 +130,24 @@ impl<'a> SelectivityEstimator<'a> {
                 return Ok(DEFAULT_SELECTIVITY);
             };
 
+            if op != ComparisonOp::Equal
+                && op != ComparisonOp::NotEqual
+                && column_stat.histogram.is_none()
+            {
+                // Without a histogram, fall back to the range between min and max.
+                let (selectivity, new_min, new_max) =
+                    if let Some(res) = evaluate_range(column_stat, op, &const_datum) {
+                        res
+                    } else {
+                        return Ok(DEFAULT_SELECTIVITY);
+                    };
+                if update {
+                    update_statistic(column_stat, new_min, new_max, selectivity)?;
+                    self.updated_column_indexes.insert(column_ref.column.index);
+                }
+                return Ok(selectivity);
+            }
+
             return match op {
                 ComparisonOp::Equal => {
                     // For equal predicate, we just use cardinality of a single
@@ -165,7 +183,6 @@ impl<'a> SelectivityEstimator<'a> {
                     let col_hist = if let Some(hist) = column_stat.histogram.as_ref() {
                         hist
                     } else {
-                        // Todo(xudong): use ndv to estimate the selectivity, not directly return `DEFAULT_SELECTIVITY`.
                         return Ok(DEFAULT_SELECTIVITY);
                     };
                     // For greater than predicate, we use the number of values
@@ -363,6 +380,52 @@ fn compare_equal(datum: &Option<Datum>, column_stat: &ColumnStat) -> f64 {
     }
 }
 
+/// Estimate the selectivity of a range predicate `column <op> constant` with the min and max of the column,
+/// assuming the values are uniformly distributed between them.
+///
+/// Returns the selectivity and the new min and max of the column,
+/// or `None` if the column or the constant is not numeric.
+fn evaluate_range(
+    column_stat: &ColumnStat,
+    op: ComparisonOp,
+    const_datum: &Datum,
+) -> Option<(f64, Datum, Datum)> {
+    let (col_min, col_max) = (&column_stat.min, &column_stat.max);
+    if !col_min.is_numeric() || !col_max.is_numeric() || !const_datum.is_numeric() {
+        return None;
+    }
+
+    let min = col_min.to_double().ok()?;
+    let max = col_max.to_double().ok()?;
+    let value = const_datum.to_double().ok()?;
+
+    if max <= min {
+        // All values are the same.
+        let passed = match op {
+            ComparisonOp::GT => min > value,
+            ComparisonOp::GTE => min >= value,
+            ComparisonOp::LT => min < value,
+            ComparisonOp::LTE => min <= value,
+            ComparisonOp::Equal | ComparisonOp::NotEqual => return None,
+        };
+        let selectivity = if passed { 1.0 } else { 0.0 };
+        return Some((selectivity, col_min.clone(), col_max.clone()));
+    }
+
+    let value = value.clamp(min, max);
+    let (selectivity, new_min, new_max) = match op {
+        ComparisonOp::GT | ComparisonOp::GTE => ((max - value) / (max - min), value, max),
+        ComparisonOp::LT | ComparisonOp::LTE => ((value - min) / (max - min), min, value),
+        ComparisonOp::Equal | ComparisonOp::NotEqual => return None,
+    };
+
+    Some((
+        selectivity,
+        Datum::Float(F64::from(new_min)),
+        Datum::Float(F64::from(new_max)),
+    ))
+}
+
 fn update_statistic(
     column_stat: &mut ColumnStat,
     mut new_min: Datum,
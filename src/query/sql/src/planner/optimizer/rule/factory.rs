@@ -17,6 +17,7 @@ use common_exception::Result;
 use super::rewrite::RuleCommuteJoin;
 use super::rewrite::RuleEliminateEvalScalar;
 use super::rewrite::RuleFoldCountAggregate;
+use super::rewrite::RuleFoldMinMaxAggregate;
 use super::rewrite::RuleInferFilter;
 use super::rewrite::RuleNormalizeDisjunctiveFilter;
 use super::rewrite::RuleNormalizeScalarFilter;
@@ -77,6 +78,7 @@ impl RuleFactory {
             RuleID::NormalizeAggregate => Ok(Box::new(RuleNormalizeAggregate::new())),
             RuleID::SplitAggregate => Ok(Box::new(RuleSplitAggregate::new())),
             RuleID::FoldCountAggregate => Ok(Box::new(RuleFoldCountAggregate::new())),
+            RuleID::FoldMinMaxAggregate => Ok(Box::new(RuleFoldMinMaxAggregate::new(metadata))),
             RuleID::NormalizeDisjunctiveFilter => {
                 Ok(Box::new(RuleNormalizeDisjunctiveFilter::new()))
             }
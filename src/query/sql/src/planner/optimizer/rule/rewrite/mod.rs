@@ -18,6 +18,7 @@ mod rule_commute_join;
 mod rule_eliminate_eval_scalar;
 mod rule_eliminate_filter;
 mod rule_fold_count_aggregate;
+mod rule_fold_min_max_aggregate;
 mod rule_infer_filter;
 mod rule_merge_eval_scalar;
 mod rule_merge_filter;
@@ -46,6 +47,7 @@ pub use rule_commute_join::RuleCommuteJoin;
 pub use rule_eliminate_eval_scalar::RuleEliminateEvalScalar;
 pub use rule_eliminate_filter::RuleEliminateFilter;
 pub use rule_fold_count_aggregate::RuleFoldCountAggregate;
+pub use rule_fold_min_max_aggregate::RuleFoldMinMaxAggregate;
 pub use rule_infer_filter::RuleInferFilter;
 pub use rule_merge_eval_scalar::RuleMergeEvalScalar;
 pub use rule_merge_filter::RuleMergeFilter;
@@ -0,0 +1,204 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_expression::types::number::F32;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberScalar;
+use common_expression::Scalar;
+use common_storage::Datum;
+
+use crate::optimizer::rule::Rule;
+use crate::optimizer::rule::RuleID;
+use crate::optimizer::rule::TransformResult;
+use crate::optimizer::RelExpr;
+use crate::optimizer::SExpr;
+use crate::plans::Aggregate;
+use crate::plans::AggregateMode;
+use crate::plans::CastExpr;
+use crate::plans::ConstantExpr;
+use crate::plans::DummyTableScan;
+use crate::plans::EvalScalar;
+use crate::plans::PatternPlan;
+use crate::plans::RelOp;
+use crate::plans::ScalarExpr;
+use crate::MetadataRef;
+
+/// Fold simple `MIN(col)` and `MAX(col)` aggregates with the min and max in column statistics,
+/// instead of scanning the blocks.
+///
+/// Only columns whose statistics are exact are folded:
+/// the table engine must keep exact column statistics, see `Table::has_exact_column_statistics()`,
+/// and string statistics are truncated, so a string column is still scanned.
+pub struct RuleFoldMinMaxAggregate {
+    id: RuleID,
+    patterns: Vec<SExpr>,
+    metadata: MetadataRef,
+}
+
+impl RuleFoldMinMaxAggregate {
+    pub fn new(metadata: MetadataRef) -> Self {
+        Self {
+            id: RuleID::FoldMinMaxAggregate,
+            metadata,
+            //  Aggregate
+            //  \
+            //   *
+            patterns: vec![SExpr::create_unary(
+                Arc::new(
+                    PatternPlan {
+                        plan_type: RelOp::Aggregate,
+                    }
+                    .into(),
+                ),
+                Arc::new(SExpr::create_leaf(Arc::new(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ))),
+            )],
+        }
+    }
+}
+
+impl Rule for RuleFoldMinMaxAggregate {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, s_expr: &SExpr, state: &mut TransformResult) -> Result<()> {
+        let agg: Aggregate = s_expr.plan().clone().try_into()?;
+
+        if agg.mode == AggregateMode::Final || agg.mode == AggregateMode::Partial {
+            return Ok(());
+        }
+
+        if !agg.group_items.is_empty() || agg.aggregate_functions.is_empty() {
+            return Ok(());
+        }
+
+        let rel_expr = RelExpr::with_s_expr(s_expr);
+        let input_stat_info = rel_expr.derive_cardinality_child(0)?;
+
+        // Without a precise cardinality, e.g. below a filter, the statistics are only estimated.
+        let Some(table_card) = input_stat_info.statistics.precise_cardinality else {
+            return Ok(());
+        };
+        let column_stats = &input_stat_info.statistics.column_stats;
+
+        let mut scalars = agg.aggregate_functions;
+        for item in scalars.iter_mut() {
+            let ScalarExpr::AggregateFunction(agg_func) = &item.scalar else {
+                return Ok(());
+            };
+
+            let is_max = match agg_func.func_name.as_str() {
+                "min" => false,
+                "max" => true,
+                _ => return Ok(()),
+            };
+
+            let [ScalarExpr::BoundColumnRef(col)] = agg_func.args.as_slice() else {
+                return Ok(());
+            };
+
+            // The statistics of other engines, e.g. hive, may be estimated.
+            let Some(table_index) = col.column.table_index else {
+                return Ok(());
+            };
+            if !self
+                .metadata
+                .read()
+                .table(table_index)
+                .table()
+                .has_exact_column_statistics()
+            {
+                return Ok(());
+            }
+
+            let Some(stat) = column_stats.get(&col.column.index) else {
+                return Ok(());
+            };
+
+            // The result of an empty or all-NULL input is NULL, which is not in the statistics.
+            if stat.null_count >= table_card {
+                return Ok(());
+            }
+
+            let datum = if is_max { &stat.max } else { &stat.min };
+            let Some(value) = datum_to_scalar(datum, col.column.data_type.remove_nullable())
+            else {
+                return Ok(());
+            };
+
+            let span = item.scalar.span();
+            let constant = ScalarExpr::ConstantExpr(ConstantExpr { span, value });
+            item.scalar = ScalarExpr::CastExpr(CastExpr {
+                span,
+                is_try: false,
+                argument: Box::new(constant),
+                target_type: agg_func.return_type.clone(),
+            });
+        }
+
+        let eval_scalar = EvalScalar { items: scalars };
+        let dummy_table_scan = DummyTableScan;
+        state.add_result(SExpr::create_unary(
+            Arc::new(eval_scalar.into()),
+            Arc::new(SExpr::create_leaf(Arc::new(dummy_table_scan.into()))),
+        ));
+        Ok(())
+    }
+
+    fn patterns(&self) -> &Vec<SExpr> {
+        &self.patterns
+    }
+}
+
+/// Convert a min or max in column statistics back to a scalar of the column type.
+///
+/// Returns None for a type whose statistics are not exact, or a datum that does not fit the type.
+fn datum_to_scalar(datum: &Datum, data_type: DataType) -> Option<Scalar> {
+    let scalar = match (data_type, datum) {
+        (DataType::Boolean, Datum::Bool(v)) => Scalar::Boolean(*v),
+        (DataType::Number(ty), Datum::Int(v)) => Scalar::Number(match ty {
+            NumberDataType::Int8 => NumberScalar::Int8(i8::try_from(*v).ok()?),
+            NumberDataType::Int16 => NumberScalar::Int16(i16::try_from(*v).ok()?),
+            NumberDataType::Int32 => NumberScalar::Int32(i32::try_from(*v).ok()?),
+            NumberDataType::Int64 => NumberScalar::Int64(*v),
+            _ => return None,
+        }),
+        (DataType::Number(ty), Datum::UInt(v)) => Scalar::Number(match ty {
+            NumberDataType::UInt8 => NumberScalar::UInt8(u8::try_from(*v).ok()?),
+            NumberDataType::UInt16 => NumberScalar::UInt16(u16::try_from(*v).ok()?),
+            NumberDataType::UInt32 => NumberScalar::UInt32(u32::try_from(*v).ok()?),
+            NumberDataType::UInt64 => NumberScalar::UInt64(*v),
+            _ => return None,
+        }),
+        (DataType::Number(NumberDataType::Float32), Datum::Float(v)) => {
+            Scalar::Number(NumberScalar::Float32(F32::from(v.0 as f32)))
+        }
+        (DataType::Number(NumberDataType::Float64), Datum::Float(v)) => {
+            Scalar::Number(NumberScalar::Float64(*v))
+        }
+        (DataType::Date, Datum::Int(v)) => Scalar::Date(i32::try_from(*v).ok()?),
+        (DataType::Timestamp, Datum::Int(v)) => Scalar::Timestamp(*v),
+        _ => return None,
+    };
+    Some(scalar)
+}
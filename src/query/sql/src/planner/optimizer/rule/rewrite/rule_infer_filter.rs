@@ -41,6 +41,7 @@ use crate::plans::ScalarExpr;
 // The rule tries to infer new predicates from existing predicates, for example:
 // 1. [A > 1 and A > 5] => [A > 5], [A > 1 and A <= 1 => false], [A = 1 and A < 10] => [A = 1]
 // 2. [A = 10 and A = B] => [B = 10]
+// 3. [A < 10 or A < 20] => [A < 20], [A = 1 or A < 10] => [A < 10]
 // TODO(Dousir9): [A = B and A = C] => [B = C]
 pub struct RuleInferFilter {
     id: RuleID,
@@ -308,6 +309,48 @@ impl PredicateSet {
         }
     }
 
+    /// Merge `left OR right` into one of them if it includes the other one.
+    ///
+    /// `MergeResult::Left` or `MergeResult::Right` tells the one to keep,
+    /// `MergeResult::All` means both have to be kept.
+    fn merge_disjunction(left: &Predicate, right: &Predicate) -> MergeResult {
+        match (&left.op, &right.op) {
+            (_, ComparisonOp::Equal) if Self::holds(left, &right.constant) => MergeResult::Left,
+            (ComparisonOp::Equal, _) if Self::holds(right, &left.constant) => MergeResult::Right,
+            (ComparisonOp::LT | ComparisonOp::LTE, ComparisonOp::LT | ComparisonOp::LTE) => {
+                // Keep the greater upper bound.
+                let left_is_wider = left.constant > right.constant
+                    || (left.constant == right.constant && left.op == ComparisonOp::LTE);
+                match left_is_wider {
+                    true => MergeResult::Left,
+                    false => MergeResult::Right,
+                }
+            }
+            (ComparisonOp::GT | ComparisonOp::GTE, ComparisonOp::GT | ComparisonOp::GTE) => {
+                // Keep the less lower bound.
+                let left_is_wider = left.constant < right.constant
+                    || (left.constant == right.constant && left.op == ComparisonOp::GTE);
+                match left_is_wider {
+                    true => MergeResult::Left,
+                    false => MergeResult::Right,
+                }
+            }
+            _ => MergeResult::All,
+        }
+    }
+
+    /// Whether the predicate is true for the value `constant`.
+    fn holds(predicate: &Predicate, constant: &ConstantExpr) -> bool {
+        match predicate.op {
+            ComparisonOp::Equal => constant == &predicate.constant,
+            ComparisonOp::NotEqual => constant != &predicate.constant,
+            ComparisonOp::LT => constant < &predicate.constant,
+            ComparisonOp::LTE => constant <= &predicate.constant,
+            ComparisonOp::GT => constant > &predicate.constant,
+            ComparisonOp::GTE => constant >= &predicate.constant,
+        }
+    }
+
     fn find(parent: &mut [usize], x: usize) -> usize {
         if parent[x] != x {
             parent[x] = Self::find(parent, parent[x]);
@@ -490,6 +533,106 @@ pub fn adjust_scalar(scalar: Scalar, data_type: DataType) -> (bool, ConstantExpr
     })
 }
 
+/// Convert a comparison between a column and a constant to the column and a `Predicate` on it.
+fn column_predicate(scalar: &ScalarExpr) -> Result<Option<(ScalarExpr, Predicate)>> {
+    let ScalarExpr::FunctionCall(func) = scalar else {
+        return Ok(None);
+    };
+    let Some(op) = ComparisonOp::try_from_func_name(&func.func_name) else {
+        return Ok(None);
+    };
+
+    let (left, right) =
+        remove_trivial_type_cast(func.arguments[0].clone(), func.arguments[1].clone());
+    let (column, constant, op) = match (&left, &right) {
+        (column, ScalarExpr::ConstantExpr(constant)) if column.is_column_ref() => {
+            (column, constant, op)
+        }
+        (ScalarExpr::ConstantExpr(constant), column) if column.is_column_ref() => {
+            (column, constant, op.reverse())
+        }
+        _ => return Ok(None),
+    };
+
+    let (is_adjusted, constant) = adjust_scalar(constant.value.clone(), column.data_type()?);
+    if !is_adjusted {
+        return Ok(None);
+    }
+    Ok(Some((column.clone(), Predicate { op, constant })))
+}
+
+fn flatten_disjunction(scalar: &ScalarExpr, disjuncts: &mut Vec<ScalarExpr>) {
+    match scalar {
+        ScalarExpr::FunctionCall(func) if func.func_name == "or" => {
+            for argument in func.arguments.iter() {
+                flatten_disjunction(argument, disjuncts);
+            }
+        }
+        _ => disjuncts.push(scalar.clone()),
+    }
+}
+
+/// Widen the comparisons on a same column in a disjunction into one comparison,
+/// e.g. `A < 10 OR B = 1 OR A < 20` => `A < 20 OR B = 1`.
+///
+/// Returns `None` if nothing is merged.
+fn widen_disjunction(predicate: &ScalarExpr) -> Result<Option<ScalarExpr>> {
+    let mut disjuncts = vec![];
+    flatten_disjunction(predicate, &mut disjuncts);
+    if disjuncts.len() < 2 {
+        return Ok(None);
+    }
+
+    let mut is_merged = false;
+    let mut column_predicates: Vec<(ScalarExpr, Predicate)> = vec![];
+    let mut others = vec![];
+    'disjunct: for disjunct in disjuncts {
+        let Some((column, predicate)) = column_predicate(&disjunct)? else {
+            others.push(disjunct);
+            continue;
+        };
+
+        for (existing_column, existing) in column_predicates.iter_mut() {
+            if existing_column != &column {
+                continue;
+            }
+            match PredicateSet::merge_disjunction(existing, &predicate) {
+                MergeResult::Left => {}
+                MergeResult::Right => *existing = predicate.clone(),
+                MergeResult::All | MergeResult::None => continue,
+            }
+            is_merged = true;
+            continue 'disjunct;
+        }
+        column_predicates.push((column, predicate));
+    }
+
+    if !is_merged {
+        return Ok(None);
+    }
+
+    let widened = column_predicates
+        .into_iter()
+        .map(|(column, predicate)| {
+            ScalarExpr::FunctionCall(FunctionCall {
+                span: None,
+                func_name: String::from(predicate.op.to_func_name()),
+                params: vec![],
+                arguments: vec![column, ScalarExpr::ConstantExpr(predicate.constant)],
+            })
+        })
+        .chain(others)
+        .reduce(|left, right| {
+            ScalarExpr::FunctionCall(FunctionCall {
+                span: None,
+                func_name: "or".to_string(),
+                params: vec![],
+                arguments: vec![left, right],
+            })
+        });
+    Ok(widened)
+}
+
 impl Rule for RuleInferFilter {
     fn id(&self) -> RuleID {
         self.id
@@ -502,6 +645,10 @@ impl Rule for RuleInferFilter {
         let mut is_rewritten = false;
         let mut predicate_set = PredicateSet::new();
         for predicate in predicates.iter_mut() {
+            if let Some(widened) = widen_disjunction(predicate)? {
+                is_rewritten = true;
+                *predicate = widened;
+            }
             if let ScalarExpr::FunctionCall(func) = predicate {
                 if ComparisonOp::try_from_func_name(&func.func_name).is_some() {
                     let (left, right) = remove_trivial_type_cast(
@@ -65,6 +65,7 @@ pub enum RuleID {
     MergeFilter,
     SplitAggregate,
     FoldCountAggregate,
+    FoldMinMaxAggregate,
     PushDownPrewhere,
     TryApplyAggIndex,
     CommuteJoin,
@@ -102,6 +103,7 @@ impl Display for RuleID {
             RuleID::NormalizeDisjunctiveFilter => write!(f, "NormalizeDisjunctiveFilter"),
             RuleID::InferFilter => write!(f, "InferFilter"),
             RuleID::FoldCountAggregate => write!(f, "FoldCountAggregate"),
+            RuleID::FoldMinMaxAggregate => write!(f, "FoldMinMaxAggregate"),
             RuleID::PushDownPrewhere => write!(f, "PushDownPrewhere"),
 
             RuleID::CommuteJoin => write!(f, "CommuteJoin"),
@@ -97,7 +97,18 @@ impl Operator for Aggregate {
     }
 
     fn derive_physical_prop(&self, rel_expr: &RelExpr) -> Result<PhysicalProperty> {
-        rel_expr.derive_physical_prop_child(0)
+        let child_physical_prop = rel_expr.derive_physical_prop_child(0)?;
+
+        // The rows may be shuffled by the hash of the serialized group keys instead,
+        // see `group_by_shuffle_mode`, and grouping sets output NULL keys.
+        // Thus the output is not known to be partitioned by the keys.
+        if let Distribution::Hash(_) = child_physical_prop.distribution {
+            return Ok(PhysicalProperty {
+                distribution: Distribution::Random,
+            });
+        }
+
+        Ok(child_physical_prop)
     }
 
     fn compute_required_prop_child(
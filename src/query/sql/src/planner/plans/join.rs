@@ -171,6 +171,23 @@ impl Default for Join {
 }
 
 impl Join {
+    /// Whether every pair of the equi-join keys has the same data type.
+    ///
+    /// Only then the keys of both sides are hashed the same way without a cast,
+    /// and an input already partitioned by its keys can be joined without another shuffle.
+    pub fn has_same_key_types(&self) -> Result<bool> {
+        for (left, right) in self
+            .left_conditions
+            .iter()
+            .zip(self.right_conditions.iter())
+        {
+            if left.data_type()? != right.data_type()? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     pub fn used_columns(&self) -> Result<ColumnSet> {
         let mut used_columns = ColumnSet::new();
         for cond in self
@@ -412,6 +429,20 @@ impl Operator for Join {
             (Distribution::Broadcast, Distribution::Broadcast) => Ok(PhysicalProperty {
                 distribution: Distribution::Random,
             }),
+            // The unmatched rows of build side are output with NULL in the columns of probe side,
+            // on a node that is not decided by the hash of the NULLs,
+            // thus the output is not partitioned by the keys of probe side.
+            //
+            // And if the keys are cast to a common type for shuffling, the probe side is not
+            // partitioned by the hash of its original keys either.
+            (Distribution::Hash(_), _)
+                if matches!(self.join_type, JoinType::Right | JoinType::Full)
+                    || !self.has_same_key_types()? =>
+            {
+                Ok(PhysicalProperty {
+                    distribution: Distribution::Random,
+                })
+            }
             // Otherwise pass through probe side.
             _ => Ok(PhysicalProperty {
                 distribution: probe_prop.distribution.clone(),
@@ -501,10 +532,11 @@ impl Operator for Join {
         {
             let left_stat_info = rel_expr.derive_cardinality_child(0)?;
             let right_stat_info = rel_expr.derive_cardinality_child(1)?;
-            // The broadcast join is cheaper than the hash join when one input is at least (n − 1)× larger than the other
-            // where n is the number of servers in the cluster.
-            let broadcast_join_threshold = (ctx.get_cluster().nodes.len() - 1) as f64;
-            if right_stat_info.cardinality * broadcast_join_threshold < left_stat_info.cardinality {
+            if prefer_broadcast_join(
+                left_stat_info.cardinality,
+                right_stat_info.cardinality,
+                ctx.get_cluster().nodes.len(),
+            ) {
                 required.distribution = Distribution::Broadcast;
                 return Ok(required);
             }
@@ -519,6 +551,20 @@ impl Operator for Join {
     }
 }
 
+/// Whether to broadcast the build side of a distributed join to every node,
+/// instead of shuffling both sides by the join keys.
+///
+/// The broadcast join is cheaper than the hash join when the probe side is at least (n − 1)× larger
+/// than the build side, where n is the number of servers in the cluster.
+pub fn prefer_broadcast_join(
+    probe_cardinality: f64,
+    build_cardinality: f64,
+    num_nodes: usize,
+) -> bool {
+    let broadcast_join_threshold = num_nodes.saturating_sub(1) as f64;
+    build_cardinality * broadcast_join_threshold < probe_cardinality
+}
+
 fn evaluate_by_histogram(
     left_hist: &Histogram,
     right_hist: &Histogram,
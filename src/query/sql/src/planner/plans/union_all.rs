@@ -83,6 +83,14 @@ impl Operator for UnionAll {
 
     fn derive_physical_prop(&self, rel_expr: &RelExpr) -> Result<PhysicalProperty> {
         let left_child = rel_expr.derive_physical_prop_child(0)?;
+
+        // The rows of the right child are not partitioned by the keys of the left child.
+        if let Distribution::Hash(_) = left_child.distribution {
+            return Ok(PhysicalProperty {
+                distribution: Distribution::Random,
+            });
+        }
+
         Ok(PhysicalProperty {
             distribution: left_child.distribution,
         })
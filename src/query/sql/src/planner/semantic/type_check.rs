@@ -1815,9 +1815,24 @@ impl<'a> TypeChecker<'a> {
             )));
         }
 
+        // rewrite string comparisons under case-insensitive collation, e.g. `a = b` -> `lower(a) = lower(b)`.
+        // Columns do not carry a collation, it is the session setting `collation`.
+        // The domain of `lower(a)` is derived from the common prefix of the min and max of `a`,
+        // thus a rewritten comparison is still used by range pruning.
+        if self.comparison_need_case_folding(func_name, &args)? {
+            let mut folded_args = Vec::with_capacity(args.len());
+            for arg in args {
+                let box (arg, _) = self
+                    .resolve_scalar_function_call(span, "lower", vec![], vec![arg])
+                    .await?;
+                folded_args.push(arg);
+            }
+            args = folded_args;
+        }
+
         // rewrite_collation
         let func_name = if self.function_need_collation(func_name, &args)?
-            && self.ctx.get_settings().get_collation()? == "utf8"
+            && matches!(self.ctx.get_settings().get_collation()?, "utf8" | "utf8_ci")
         {
             format!("{func_name}_utf8")
         } else {
@@ -1836,6 +1851,12 @@ impl<'a> TypeChecker<'a> {
         params: Vec<usize>,
         args: Vec<ScalarExpr>,
     ) -> Result<Box<(ScalarExpr, DataType)>> {
+        let args = if self.func_ctx.mysql_compat {
+            Self::coerce_string_compared_with_number(func_name, args)?
+        } else {
+            args
+        };
+
         // Type check
         let arguments = args.iter().map(|v| v.as_raw_expr()).collect::<Vec<_>>();
         let raw_expr = RawExpr::FunctionCall {
@@ -1890,6 +1911,46 @@ impl<'a> TypeChecker<'a> {
         )))
     }
 
+    /// In the MySQL dialect, wrap the string operand of a comparison with a number
+    /// or a decimal in `mysql_to_float64`, e.g. `'10' = 10` is checked as `mysql_to_float64('10') = 10`.
+    /// Otherwise a string is not implicitly cast to a number for a comparison.
+    fn coerce_string_compared_with_number(
+        func_name: &str,
+        mut args: Vec<ScalarExpr>,
+    ) -> Result<Vec<ScalarExpr>> {
+        if !matches!(func_name, "eq" | "noteq" | "lt" | "lte" | "gt" | "gte") || args.len() != 2 {
+            return Ok(args);
+        }
+
+        let is_string = |ty: &DataType| ty.remove_nullable() == DataType::String;
+        let is_number = |ty: &DataType| {
+            matches!(
+                ty.remove_nullable(),
+                DataType::Number(_) | DataType::Decimal(_)
+            )
+        };
+
+        let left = args[0].data_type()?;
+        let right = args[1].data_type()?;
+        let string_index = if is_string(&left) && is_number(&right) {
+            0
+        } else if is_number(&left) && is_string(&right) {
+            1
+        } else {
+            return Ok(args);
+        };
+
+        let arg = args[string_index].clone();
+        args[string_index] = FunctionCall {
+            span: arg.span(),
+            func_name: "mysql_to_float64".to_string(),
+            params: vec![],
+            arguments: vec![arg],
+        }
+        .into();
+        Ok(args)
+    }
+
     /// Resolve binary expressions. Most of the binary expressions
     /// would be transformed into `FunctionCall`, except comparison
     /// expressions, conjunction(`AND`) and disjunction(`OR`).
@@ -1971,6 +2032,32 @@ impl<'a> TypeChecker<'a> {
                 // Omit unary + operator
                 self.resolve(child).await
             }
+            UnaryOperator::Not => {
+                // Push `NOT` into a comparison by swapping to the contrary operator,
+                // e.g. `NOT (a < b)` to `a >= b`, to avoid an extra negation over the result.
+                // Both produce NULL if either side is NULL.
+                // A comparison with `ANY`/`ALL` subquery is not a plain comparison and is kept as is.
+                if let Expr::BinaryOp {
+                    span: op_span,
+                    op,
+                    left,
+                    right,
+                } = child
+                {
+                    let is_quantified = matches!(**right, Expr::Subquery {
+                        modifier: Some(_),
+                        ..
+                    });
+                    if !is_quantified {
+                        if let Ok(contrary_op) = op.to_contrary() {
+                            return self
+                                .resolve_binary_op(*op_span, &contrary_op, left, right)
+                                .await;
+                        }
+                    }
+                }
+                self.resolve_function(span, "not", vec![], &[child]).await
+            }
             other => {
                 let name = other.to_func_name();
                 self.resolve_function(span, name.as_str(), vec![], &[child])
@@ -2574,30 +2661,76 @@ impl<'a> TypeChecker<'a> {
                 self.resolve_cast_to_variant(span, &data_type, &scalar, true)
                     .await
             }
-            ("greatest", args) => {
-                let (array, _) = *self
-                    .resolve_function(span, "array", vec![], args)
-                    .await
-                    .ok()?;
-                Some(
-                    self.resolve_scalar_function_call(span, "array_max", vec![], vec![array])
-                        .await,
-                )
-            }
-            ("least", args) => {
-                let (array, _) = *self
-                    .resolve_function(span, "array", vec![], args)
-                    .await
-                    .ok()?;
-                Some(
-                    self.resolve_scalar_function_call(span, "array_min", vec![], vec![array])
-                        .await,
-                )
-            }
+            ("greatest", args) => Some(self.resolve_greatest_least(span, "array_max", args).await),
+            ("least", args) => Some(self.resolve_greatest_least(span, "array_min", args).await),
             _ => None,
         }
     }
 
+    /// Rewrite `greatest(x, y, ...)` to `array_max([x, y, ...])` and
+    /// `least(x, y, ...)` to `array_min([x, y, ...])`.
+    ///
+    /// `array_max` and `array_min` skip NULL elements, but any NULL argument
+    /// makes the result NULL (as MySQL does), so the result is wrapped with
+    /// `if(is_not_null(x) AND is_not_null(y) ..., ..., NULL)` if any argument is nullable.
+    #[async_recursion::async_recursion]
+    #[async_backtrace::framed]
+    async fn resolve_greatest_least(
+        &mut self,
+        span: Span,
+        array_func: &str,
+        args: &[&Expr],
+    ) -> Result<Box<(ScalarExpr, DataType)>> {
+        let mut resolved_args = Vec::with_capacity(args.len());
+        for arg in args {
+            let box (arg, _) = self.resolve(arg).await?;
+            resolved_args.push(arg);
+        }
+
+        let box (array, array_type) = self
+            .resolve_scalar_function_call(span, "array", vec![], resolved_args.clone())
+            .await?;
+        let has_nullable_arg =
+            matches!(&array_type, DataType::Array(box ty) if ty.is_nullable_or_null());
+
+        let result = self
+            .resolve_scalar_function_call(span, array_func, vec![], vec![array])
+            .await?;
+        if !has_nullable_arg {
+            return Ok(result);
+        }
+
+        let mut all_not_null = None;
+        for arg in resolved_args {
+            let box (not_null, _) = self
+                .resolve_scalar_function_call(span, "is_not_null", vec![], vec![arg])
+                .await?;
+            all_not_null = match all_not_null {
+                None => Some(not_null),
+                Some(left) => {
+                    let box (and, _) = self
+                        .resolve_scalar_function_call(span, "and", vec![], vec![left, not_null])
+                        .await?;
+                    Some(and)
+                }
+            };
+        }
+        let all_not_null = match all_not_null {
+            Some(all_not_null) => all_not_null,
+            None => return Ok(result),
+        };
+
+        let null = ConstantExpr {
+            span,
+            value: Scalar::Null,
+        }
+        .into();
+        let (result, _) = *result;
+
+        self.resolve_scalar_function_call(span, "if", vec![], vec![all_not_null, result, null])
+            .await
+    }
+
     #[async_recursion::async_recursion]
     #[async_backtrace::framed]
     async fn resolve_trim_function(
@@ -3672,6 +3805,19 @@ impl<'a> TypeChecker<'a> {
         Ok(result)
     }
 
+    fn comparison_need_case_folding(&self, name: &str, args: &[ScalarExpr]) -> Result<bool> {
+        let names = ["eq", "noteq", "lt", "lte", "gt", "gte"];
+        if !names.contains(&name) || self.ctx.get_settings().get_collation()? != "utf8_ci" {
+            return Ok(false);
+        }
+        for arg in args {
+            if arg.data_type()?.remove_nullable() != DataType::String {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     fn try_fold_constant<Index: ColumnIndex>(
         &self,
         expr: &common_expression::Expr<Index>,
@@ -0,0 +1,124 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberScalar;
+use common_expression::Scalar;
+use common_sql::optimizer::ColumnStat;
+use common_sql::optimizer::SelectivityEstimator;
+use common_sql::optimizer::Statistics;
+use common_sql::optimizer::DEFAULT_SELECTIVITY;
+use common_sql::plans::BoundColumnRef;
+use common_sql::plans::ConstantExpr;
+use common_sql::plans::FunctionCall;
+use common_sql::ColumnBindingBuilder;
+use common_sql::ScalarExpr;
+use common_sql::Visibility;
+use common_storage::Datum;
+
+fn column_stat(min: i64, max: i64, ndv: f64) -> ColumnStat {
+    ColumnStat {
+        min: Datum::Int(min),
+        max: Datum::Int(max),
+        ndv,
+        null_count: 0,
+        histogram: None,
+    }
+}
+
+/// Build `column_0 <op> value`.
+fn compare(op: &str, value: i64) -> ScalarExpr {
+    let column = ColumnBindingBuilder::new(
+        "a".to_string(),
+        0,
+        Box::new(DataType::Number(NumberDataType::Int64)),
+        Visibility::Visible,
+    )
+    .build();
+
+    ScalarExpr::FunctionCall(FunctionCall {
+        span: None,
+        func_name: op.to_string(),
+        params: vec![],
+        arguments: vec![
+            ScalarExpr::BoundColumnRef(BoundColumnRef { span: None, column }),
+            ScalarExpr::ConstantExpr(ConstantExpr {
+                span: None,
+                value: Scalar::Number(NumberScalar::Int64(value)),
+            }),
+        ],
+    })
+}
+
+fn selectivity(stat: Option<ColumnStat>, pred: &ScalarExpr) -> Result<f64> {
+    let mut column_stats = HashMap::new();
+    if let Some(stat) = stat {
+        column_stats.insert(0, stat);
+    }
+    let mut statistics = Statistics {
+        precise_cardinality: Some(1000),
+        column_stats,
+    };
+
+    let mut sb = SelectivityEstimator::new(&mut statistics, HashSet::new());
+    sb.compute_selectivity(pred, false)
+}
+
+#[test]
+fn test_selectivity_eq_by_ndv() -> Result<()> {
+    let got = selectivity(Some(column_stat(0, 100, 50.0)), &compare("eq", 10))?;
+    assert_eq!(1.0 / 50.0, got);
+
+    // Out of the range of [min, max]
+    let got = selectivity(Some(column_stat(0, 100, 50.0)), &compare("eq", 200))?;
+    assert_eq!(0.0, got);
+
+    Ok(())
+}
+
+#[test]
+fn test_selectivity_range_by_min_max() -> Result<()> {
+    let got = selectivity(Some(column_stat(0, 100, 100.0)), &compare("lt", 25))?;
+    assert_eq!(0.25, got);
+
+    let got = selectivity(Some(column_stat(0, 100, 100.0)), &compare("gt", 25))?;
+    assert_eq!(0.75, got);
+
+    let got = selectivity(Some(column_stat(0, 100, 100.0)), &compare("lt", -10))?;
+    assert_eq!(0.0, got);
+
+    let got = selectivity(Some(column_stat(0, 100, 100.0)), &compare("gte", 200))?;
+    assert_eq!(0.0, got);
+
+    let got = selectivity(Some(column_stat(5, 5, 1.0)), &compare("lte", 5))?;
+    assert_eq!(1.0, got);
+
+    Ok(())
+}
+
+#[test]
+fn test_selectivity_without_stats() -> Result<()> {
+    let got = selectivity(None, &compare("lt", 25))?;
+    assert_eq!(DEFAULT_SELECTIVITY, got);
+
+    let got = selectivity(None, &compare("eq", 25))?;
+    assert_eq!(DEFAULT_SELECTIVITY, got);
+
+    Ok(())
+}
@@ -433,6 +433,10 @@ impl Table for FuseTable {
         true
     }
 
+    fn has_exact_column_statistics(&self) -> bool {
+        true
+    }
+
     fn cluster_keys(&self, ctx: Arc<dyn TableContext>) -> Vec<RemoteExpr<String>> {
         let table_meta = Arc::new(self.clone());
         if let Some((_, order)) = &self.cluster_key_meta {
@@ -15,8 +15,10 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use common_ast::parser::token::all_reserved_keywords;
+use common_ast::parser::all_keywords;
 use common_catalog::table::Table;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_functions::BUILTIN_FUNCTIONS;
 use common_meta_app::schema::TableIdent;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
@@ -27,9 +29,42 @@ pub struct KeywordsTable {}
 
 impl KeywordsTable {
     pub fn create(table_id: u64) -> Arc<dyn Table> {
-        let all_keywords_vec = all_reserved_keywords();
-        let all_keywords = all_keywords_vec.join(", ");
-        let query = "SELECT '".to_owned() + &all_keywords + "' AS KEYWORDS, 1 AS RESERVED";
+        // (word, reserved, category)
+        let mut rows: Vec<(String, bool, &str)> = vec![];
+
+        for token in all_keywords() {
+            let word = format!("{:?}", token);
+            let reserved = token.is_reserved_ident(false);
+            rows.push((word.clone(), reserved, "KEYWORD"));
+            if token.is_type_name() {
+                rows.push((word, reserved, "TYPE"));
+            }
+        }
+
+        let mut func_names = BUILTIN_FUNCTIONS.registered_names();
+        func_names.extend(AggregateFunctionFactory::instance().registered_names());
+        func_names.sort();
+        func_names.dedup();
+        for name in func_names {
+            rows.push((name.to_uppercase(), false, "FUNCTION"));
+        }
+
+        let values = rows
+            .iter()
+            .map(|(word, reserved, category)| {
+                format!(
+                    "('{}', {}, '{}')",
+                    word.replace('\'', "''"),
+                    *reserved as u8,
+                    category
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!(
+            "SELECT * FROM (VALUES {}) AS t(KEYWORDS, RESERVED, CATEGORY)",
+            values
+        );
 
         let mut options = BTreeMap::new();
         options.insert(QUERY.to_string(), query);
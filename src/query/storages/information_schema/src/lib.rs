@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+
 mod columns_table;
 mod key_column_usage_table;
 mod keywords_table;
@@ -27,3 +31,18 @@ pub use schemata_table::SchemataTable;
 pub use statistics_table::StatisticsTable;
 pub use tables_table::TablesTable;
 pub use views_table::ViewsTable;
+
+/// Create every table of the `information_schema` database, with ids from `next_table_id`.
+///
+/// A new view is added to the database by adding it here.
+pub fn register_all(mut next_table_id: impl FnMut() -> u64) -> Vec<Arc<dyn Table>> {
+    vec![
+        ColumnsTable::create(next_table_id()),
+        TablesTable::create(next_table_id()),
+        KeywordsTable::create(next_table_id()),
+        ViewsTable::create(next_table_id()),
+        SchemataTable::create(next_table_id()),
+        StatisticsTable::create(next_table_id()),
+        KeyColumnUsageTable::create(next_table_id()),
+    ]
+}
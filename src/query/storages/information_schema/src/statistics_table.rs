@@ -25,24 +25,33 @@ use common_storages_view::view_table::QUERY;
 pub struct StatisticsTable {}
 
 impl StatisticsTable {
+    /// Databend has no secondary index, the cluster key of a table is reported as a pseudo index,
+    /// with one row for every expression in `cluster_by`, such as `a` and `b` of `(a, b)`.
+    ///
+    /// `cluster_by` is split by `,`, thus an expression containing a comma is split too.
+    /// The cardinality is unknown and is always NULL.
     pub fn create(table_id: u64) -> Arc<dyn Table> {
-        let query = "SELECT \
-        NULL as table_catalog, \
-        NULL as table_schema, \
-        NULL as table_name, \
-        NULL as non_unique, \
-        NULL as index_schema, \
-        NULL as index_name, \
-        NULL as seq_in_index, \
-        NULL as column_name, \
-        NULL as collation, \
-        NULL as cardinality, \
-        NULL as sub_part, \
-        NULL as packed, \
-        NULL as nullable, \
-        NULL as index_type, \
-        NULL as comment, \
-        NULL as index_comment"
+        let query = "SELECT
+            'def' AS table_catalog,
+            t.database AS table_schema,
+            t.name AS table_name,
+            1 AS non_unique,
+            t.database AS index_schema,
+            'cluster_by' AS index_name,
+            n.seq AS seq_in_index,
+            trim(split_part(substr(t.cluster_by, 2, length(t.cluster_by) - 2), ',', n.seq)) AS column_name,
+            NULL AS collation,
+            NULL AS cardinality,
+            NULL AS sub_part,
+            NULL AS packed,
+            '' AS nullable,
+            'CLUSTER' AS index_type,
+            '' AS comment,
+            '' AS index_comment
+        FROM system.tables t
+        CROSS JOIN (SELECT CAST(number + 1 AS BIGINT) AS seq FROM numbers(64)) n
+        WHERE t.cluster_by <> ''
+            AND n.seq <= length(split(substr(t.cluster_by, 2, length(t.cluster_by) - 2), ','));"
             .to_string();
 
         let mut options = BTreeMap::new();
@@ -27,7 +27,7 @@ chmod +x ./target/${BUILD_PROFILE}/databend-meta
 # Give it a very big heartbeat interval to prevent election.
 # Election will change the `vote` in storage and thus fail the following `diff`
 # in this test.
-./target/${BUILD_PROFILE}/databend-meta --heartbeat-interval 100000 --single --raft-dir "$meta_dir" &
+./target/${BUILD_PROFILE}/databend-meta --heartbeat-interval 100000 --single --grpc-disable-password-auth --raft-dir "$meta_dir" &
 METASRV_PID=$!
 echo "meta-service pid:" $METASRV_PID
 sleep 10
@@ -51,7 +51,7 @@ echo " === "
 echo " === Test export after restart"
 echo " === "
 
-./target/${BUILD_PROFILE}/databend-meta --heartbeat-interval 100000 --single --raft-dir "$meta_dir" &
+./target/${BUILD_PROFILE}/databend-meta --heartbeat-interval 100000 --single --grpc-disable-password-auth --raft-dir "$meta_dir" &
 METASRV_PID=$!
 echo "meta-service pid:" $METASRV_PID
 sleep 10
@@ -38,7 +38,7 @@ chmod +x ./target/${BUILD_PROFILE}/databend-meta
 # Give it a very big heartbeat interval to prevent election.
 # Election will change the `vote` in storage and thus fail the following `diff`
 # in this test.
-./target/${BUILD_PROFILE}/databend-meta --heartbeat-interval 100000 --log-file-level DEBUG --single --id 1 --raft-dir "$meta_dir" &
+./target/${BUILD_PROFILE}/databend-meta --heartbeat-interval 100000 --log-file-level DEBUG --single --grpc-disable-password-auth --id 1 --raft-dir "$meta_dir" &
 METASRV_PID=$!
 echo "meta-service pid:" $METASRV_PID
 sleep 10
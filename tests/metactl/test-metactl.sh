@@ -67,7 +67,7 @@ metactl_import_export () {
     # Give it a very big heartbeat interval to prevent election.
     # Election will change the `vote` in storage and thus fail the following `diff`
     # in this test.
-    ./target/${BUILD_PROFILE}/databend-meta --single --heartbeat-interval 100000 --raft-dir "$meta_dir" --log-file-level=debug &
+    ./target/${BUILD_PROFILE}/databend-meta --single --grpc-disable-password-auth --heartbeat-interval 100000 --raft-dir "$meta_dir" --log-file-level=debug &
     METASRV_PID=$!
     echo " === pid: $METASRV_PID"
     sleep 10
@@ -97,7 +97,7 @@ rm -rf "$meta_dir"
 
 echo " === start a single node databend-meta"
 # test export from grpc
-./target/${BUILD_PROFILE}/databend-meta --single --raft-dir "$meta_dir" --log-file-level=debug &
+./target/${BUILD_PROFILE}/databend-meta --single --grpc-disable-password-auth --raft-dir "$meta_dir" --log-file-level=debug &
 METASRV_PID=$!
 echo $METASRV_PID
 sleep 10